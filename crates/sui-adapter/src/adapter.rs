@@ -89,6 +89,7 @@ pub fn default_verifier_config(
         max_basic_blocks_in_script: None,
         max_per_fun_meter_units,
         max_per_mod_meter_units,
+        verifier_threads: None,
     }
 }
 
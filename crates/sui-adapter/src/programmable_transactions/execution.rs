@@ -23,6 +23,7 @@ use move_vm_runtime::{
     session::{LoadedFunctionInstantiation, SerializedReturnValues},
 };
 use move_vm_types::loaded_data::runtime_types::{StructType, Type};
+use once_cell::sync::OnceCell;
 use serde::{de::DeserializeSeed, Deserialize};
 use sui_protocol_config::ProtocolConfig;
 use sui_types::{
@@ -50,6 +51,7 @@ use sui_verifier::{
         TxContextKind, RESOLVED_ASCII_STR, RESOLVED_STD_OPTION, RESOLVED_SUI_ID, RESOLVED_UTF8_STR,
     },
     private_generics::{EVENT_MODULE, PRIVATE_TRANSFER_FUNCTIONS, TRANSFER_MODULE},
+    verifier::VerifierThreadPool,
     INIT_FN_NAME,
 };
 
@@ -795,15 +797,32 @@ fn publish_and_verify_modules<S: StorageView>(
         .map_err(|e| context.convert_vm_error(e))?;
 
     // run the Sui verifier
+    let pool = context
+        .vm
+        .config()
+        .verifier
+        .verifier_threads
+        .and_then(verifier_thread_pool);
     for module in modules {
         // Run Sui bytecode verifier, which runs some additional checks that assume the Move
         // bytecode verifier has passed.
-        sui_verifier::verifier::verify_module(module, &BTreeMap::new())?;
+        match pool {
+            Some(pool) => pool.verify_module(module, &BTreeMap::new())?,
+            None => sui_verifier::verifier::verify_module(module, &BTreeMap::new())?,
+        }
     }
 
     Ok(())
 }
 
+/// The pool Sui verification of published modules runs on, shared by every VM of the process and
+/// sized by the first `verifier_threads` it is asked for. `None` if the pool could not be built,
+/// in which case modules are verified on the calling thread.
+fn verifier_thread_pool(num_threads: usize) -> Option<&'static VerifierThreadPool> {
+    static POOL: OnceCell<Option<VerifierThreadPool>> = OnceCell::new();
+    POOL.get_or_init(|| VerifierThreadPool::new(num_threads).ok()).as_ref()
+}
+
 fn init_modules<S: StorageView, Mode: ExecutionMode>(
     context: &mut ExecutionContext<S>,
     argument_updates: &mut Mode::ArgumentUpdates,
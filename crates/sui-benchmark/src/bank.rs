@@ -12,7 +12,7 @@ use move_core_types::language_storage::TypeTag;
 use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use sui_types::base_types::{ObjectRef, SuiAddress};
-use sui_types::crypto::AccountKeyPair;
+use sui_types::crypto::{get_key_pair, AccountKeyPair};
 use sui_types::gas_coin::GAS;
 use sui_types::messages::{CallArg, ObjectArg, TransactionData, VerifiedTransaction};
 use sui_types::utils::to_sender_signed_transaction;
@@ -31,6 +31,9 @@ pub struct BenchmarkBank {
     // multiple coins in case we no longer have one large coin that will support
     // generating all coins for the workloads.
     pub pay_coins: Vec<Gas>,
+    // Accounts the gas of every workload's payloads is sent to, round robin, when set. Otherwise
+    // each payload has an account of its own.
+    pub senders: Option<Vec<(SuiAddress, Arc<AccountKeyPair>)>>,
 }
 
 impl BenchmarkBank {
@@ -43,8 +46,25 @@ impl BenchmarkBank {
             proxy,
             primary_gas,
             pay_coins,
+            senders: None,
         }
     }
+    /// Send payloads from a pool of `num_accounts` new accounts shared by all workloads, payload
+    /// `i` is funded at and sends from account `i % num_accounts`. Lets the number of distinct
+    /// senders be set independently of the number of payloads. Every payload coin is funded with
+    /// the largest amount any workload asks for so coins at a shared account are interchangeable.
+    pub fn with_num_accounts(mut self, num_accounts: usize) -> Self {
+        assert!(num_accounts > 0, "Number of accounts must be positive");
+        self.senders = Some(
+            (0..num_accounts)
+                .map(|_| {
+                    let (address, keypair) = get_key_pair();
+                    (address, Arc::new(keypair))
+                })
+                .collect(),
+        );
+        self
+    }
     pub async fn generate(
         &mut self,
         builders: Vec<Box<dyn WorkloadBuilder<dyn Payload>>>,
@@ -58,6 +78,31 @@ impl BenchmarkBank {
             coin_configs.push_back(init_gas_config);
             coin_configs.push_back(payload_gas_config);
         }
+        if let Some(senders) = &self.senders {
+            // Coins are matched back to configs by address alone, so every payload coin is
+            // funded with the largest amount any payload asks for, making the coins at a
+            // shared address interchangeable between workloads.
+            let max_amount = coin_configs
+                .iter()
+                .skip(1)
+                .step_by(2)
+                .flatten()
+                .map(|c| c.amount)
+                .max()
+                .unwrap_or_default();
+            for (i, config) in coin_configs
+                .iter_mut()
+                .skip(1)
+                .step_by(2)
+                .flatten()
+                .enumerate()
+            {
+                let (address, keypair) = &senders[i % senders.len()];
+                config.address = *address;
+                config.keypair = keypair.clone();
+                config.amount = max_amount;
+            }
+        }
         let mut all_coin_configs = vec![];
         coin_configs
             .iter()
@@ -19,7 +19,9 @@ use crate::bank::BenchmarkBank;
 use crate::options::Opts;
 use crate::util::get_ed25519_keypair_from_keystore;
 use crate::workloads::workload::MAX_GAS_FOR_TESTING;
-use crate::{FullNodeProxy, LocalValidatorAggregatorProxy, ValidatorProxy};
+use crate::{
+    FullNodeProxy, LocalValidatorAggregatorProxy, QuorumThreshold, RoutingPolicy, ValidatorProxy,
+};
 use sui_types::object::generate_max_test_gas_objects_with_owner;
 use test_utils::authority::test_and_configure_authority_configs_with_objects;
 use test_utils::authority::{spawn_fullnode, spawn_test_authorities};
@@ -56,6 +58,8 @@ impl Env {
                     opts.committee_size as usize,
                     opts.server_metric_port,
                     opts.num_server_threads,
+                    opts.routing_policy,
+                    opts.quorum_threshold,
                 )
                 .await
             }
@@ -70,6 +74,8 @@ impl Env {
                     opts.use_fullnode_for_execution,
                     opts.fullnode_rpc_addresses.clone(),
                     opts.gas_request_chunk_size,
+                    opts.routing_policy,
+                    opts.quorum_threshold,
                 )
                 .await
             }
@@ -83,6 +89,8 @@ impl Env {
         committee_size: usize,
         server_metric_port: u16,
         num_server_threads: u64,
+        routing_policy: RoutingPolicy,
+        quorum_threshold: QuorumThreshold,
     ) -> Result<BenchmarkSetup> {
         info!("Running benchmark setup in local mode..");
         let (address, keypair): (SuiAddress, AccountKeyPair) = deterministic_random_account_key();
@@ -150,7 +158,10 @@ impl Env {
         info!("Fullnode rpc url: {fullnode_rpc_url}");
         fullnode_barrier.wait().await;
         let proxy: Arc<dyn ValidatorProxy + Send + Sync> = Arc::new(
-            LocalValidatorAggregatorProxy::from_genesis(&config.genesis, registry, None).await,
+            LocalValidatorAggregatorProxy::from_genesis(&config.genesis, registry, None)
+                .await
+                .with_routing_policy(routing_policy)
+                .with_quorum_threshold(quorum_threshold),
         );
         let keypair = Arc::new(keypair);
         let primary_gas = (
@@ -178,6 +189,8 @@ impl Env {
         use_fullnode_for_execution: bool,
         fullnode_rpc_address: Vec<String>,
         chunk_size: u64,
+        routing_policy: RoutingPolicy,
+        quorum_threshold: QuorumThreshold,
     ) -> Result<BenchmarkSetup> {
         info!("Running benchmark setup in remote mode ..");
         let (sender, recv) = tokio::sync::oneshot::channel::<()>();
@@ -222,7 +235,9 @@ impl Env {
                     registry,
                     reconfig_fullnode_rpc_url.map(|x| &**x),
                 )
-                .await,
+                .await
+                .with_routing_policy(routing_policy)
+                .with_quorum_threshold(quorum_threshold),
             )]
         };
         let proxy = proxies
@@ -8,7 +8,8 @@ use prometheus::Registry;
 use rand::seq::SliceRandom;
 
 use std::sync::Arc;
-use sui_benchmark::drivers::bench_driver::BenchDriver;
+use std::time::Duration;
+use sui_benchmark::drivers::bench_driver::{load_validator_regions, BenchDriver, BenchmarkAborted};
 use sui_benchmark::drivers::driver::Driver;
 use sui_benchmark::drivers::BenchmarkCmp;
 use sui_benchmark::drivers::BenchmarkStats;
@@ -17,9 +18,9 @@ use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
 use sui_node::metrics;
 
 use sui_benchmark::benchmark_setup::Env;
-use sui_benchmark::options::Opts;
+use sui_benchmark::options::{Opts, RunSpec};
 
-use sui_benchmark::workloads::workload_configuration::WorkloadConfiguration;
+use sui_benchmark::workloads::workload_configuration::{WorkloadConfiguration, WorkloadSchedule};
 
 use sui_benchmark::system_state_observer::SystemStateObserver;
 use tokio::runtime::Builder;
@@ -103,6 +104,12 @@ async fn main() -> Result<()> {
             "Found new state (reference gas price and/or protocol config) from system state object = {:?}",
             system_state_observer.state.borrow().reference_gas_price
         );
+        if let Some(gas_price) = opts.gas_price {
+            system_state_observer = system_state_observer.with_gas_price(gas_price);
+        }
+        if let Some(epochs_ahead) = opts.expiration_epochs {
+            system_state_observer = system_state_observer.with_expiration(epochs_ahead);
+        }
         Arc::new(system_state_observer)
     };
     let stress_stat_collection = opts.stress_stat_collection;
@@ -131,7 +138,37 @@ async fn main() -> Result<()> {
             // otherwise summarized benchmark results are
             // published in the end
             let show_progress = interval.is_unbounded();
-            let driver = BenchDriver::new(opts.stat_collection_interval, stress_stat_collection);
+            let mut driver =
+                BenchDriver::new(opts.stat_collection_interval, stress_stat_collection)
+                    .with_drain_timeout(Duration::from_secs(opts.drain_timeout_secs))
+                    .with_verify_effects(opts.verify_effects_rate)
+                    .with_fail_fast(opts.fail_fast)
+                    .with_finality_tracking(opts.track_finality);
+            if let Some(ramp) = opts.ramp.clone() {
+                driver = driver.with_ramp(ramp);
+            }
+            if let RunSpec::Bench {
+                workload_schedule: Some(path),
+                ..
+            } = &opts.run_spec
+            {
+                driver = driver.with_schedule(WorkloadSchedule::load(path)?);
+            }
+            if let Some(max_in_flight) = opts.max_in_flight {
+                driver = driver.with_max_in_flight(max_in_flight);
+            }
+            if let Some(latency_ms) = opts.backpressure_latency_ms {
+                driver = driver.with_backpressure(Duration::from_millis(latency_ms));
+            }
+            if let Some(record_path) = opts.record_path.clone() {
+                driver = driver.with_record_path(record_path);
+            }
+            if let Some(endpoint) = opts.otel_endpoint.clone() {
+                driver = driver.with_otel(endpoint);
+            }
+            if let Some(path) = &opts.validator_regions {
+                driver = driver.with_validator_regions(load_validator_regions(path)?);
+            }
             driver
                 .run(
                     bench_setup.proxies,
@@ -163,6 +200,46 @@ async fn main() -> Result<()> {
                     let benchmark_table = benchmark_stats.to_table();
                     eprintln!("Benchmark Report:");
                     eprintln!("{}", benchmark_table);
+                    if benchmark_stats.num_abandoned_txes > 0 {
+                        eprintln!(
+                            "{} transactions were still in flight after the drain timeout and were abandoned",
+                            benchmark_stats.num_abandoned_txes
+                        );
+                    }
+                    if benchmark_stats.num_semantic_failures > 0 {
+                        eprintln!(
+                            "{} successful transactions had unexpected effects",
+                            benchmark_stats.num_semantic_failures
+                        );
+                    }
+                    if benchmark_stats.stats_by_workload.len() > 1 {
+                        eprintln!("Per Workload Report:");
+                        eprintln!("{}", benchmark_stats.workloads_to_table());
+                    }
+                    if benchmark_stats.stats_by_epoch.len() > 1 {
+                        eprintln!("Per Epoch Report:");
+                        eprintln!("{}", benchmark_stats.epochs_to_table());
+                    }
+                    if !benchmark_stats.stats_by_phase.is_empty() {
+                        eprintln!("Per Phase Report:");
+                        eprintln!("{}", benchmark_stats.phases_to_table());
+                    }
+                    if benchmark_stats.num_finalized_txes() + benchmark_stats.num_finality_timeouts
+                        > 0
+                    {
+                        eprintln!("Finality Report:");
+                        eprintln!("{}", benchmark_stats.finality_to_table());
+                        if benchmark_stats.num_finality_timeouts > 0 {
+                            eprintln!(
+                                "{} successful transactions were not found in a checkpoint",
+                                benchmark_stats.num_finality_timeouts
+                            );
+                        }
+                    }
+                    if !benchmark_stats.latency_ms_by_region.is_empty() {
+                        eprintln!("Per Region Report:");
+                        eprintln!("{}", benchmark_stats.regions_to_table());
+                    }
 
                     if stress_stat_collection {
                         eprintln!("Stress Performance Report:");
@@ -172,7 +249,7 @@ async fn main() -> Result<()> {
 
                     if !prev_benchmark_stats_path.is_empty() {
                         let data = std::fs::read_to_string(&prev_benchmark_stats_path)?;
-                        let prev_stats: BenchmarkStats = serde_json::from_str(&data)?;
+                        let prev_stats = BenchmarkStats::from_json(&data)?;
                         let cmp = BenchmarkCmp {
                             new: &benchmark_stats,
                             old: &prev_stats,
@@ -185,11 +262,17 @@ async fn main() -> Result<()> {
                         eprintln!("{}", cmp_table);
                     }
                     if !curr_benchmark_stats_path.is_empty() {
-                        let serialized = serde_json::to_string(&benchmark_stats)?;
+                        let serialized = benchmark_stats.to_json()?;
                         std::fs::write(curr_benchmark_stats_path, serialized)?;
                     }
                 }
-                Err(e) => eprintln!("{e}"),
+                Err(e) => {
+                    eprintln!("{e}");
+                    if let Some(aborted) = e.downcast_ref::<BenchmarkAborted>() {
+                        eprintln!("Benchmark Report up to the error:");
+                        eprintln!("{}", aborted.stats.to_table());
+                    }
+                }
             },
             Err(e) => eprintln!("{e:?}"),
         }
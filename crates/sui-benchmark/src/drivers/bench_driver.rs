@@ -10,6 +10,8 @@ use futures::FutureExt;
 use futures::{stream::FuturesUnordered, StreamExt};
 use indicatif::ProgressBar;
 use indicatif::ProgressStyle;
+use opentelemetry::trace::{Span, SpanKind, Status, Tracer};
+use opentelemetry::KeyValue;
 use prometheus::HistogramVec;
 use prometheus::IntCounterVec;
 use prometheus::Registry;
@@ -18,7 +20,7 @@ use prometheus::{register_histogram_vec_with_registry, register_int_counter_with
 use prometheus::{register_int_counter_vec_with_registry, CounterVec};
 use prometheus::{GaugeVec, IntCounter};
 use rand::seq::SliceRandom;
-use tokio::sync::mpsc::Sender;
+use tokio::sync::mpsc::{Sender, UnboundedReceiver, UnboundedSender};
 use tokio::sync::OnceCell;
 use tokio_util::sync::CancellationToken;
 
@@ -26,11 +28,18 @@ use crate::drivers::driver::Driver;
 use crate::drivers::HistogramWrapper;
 use crate::system_state_observer::SystemStateObserver;
 use crate::workloads::payload::Payload;
+use crate::workloads::workload_configuration::WorkloadSchedule;
 use crate::workloads::WorkloadInfo;
-use crate::ValidatorProxy;
-use std::collections::{BTreeMap, VecDeque};
+use crate::{error_category, ExecutionEffects, ValidatorProxy};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
+use sui_types::base_types::{AuthorityName, TransactionDigest};
+use sui_types::committee::Committee;
 use sui_types::messages::{TransactionDataAPI, VerifiedTransaction};
 use sysinfo::{CpuExt, System, SystemExt};
 use tokio::sync::Barrier;
@@ -38,20 +47,43 @@ use tokio::{time, time::Instant};
 use tracing::{debug, error, info};
 
 use super::Interval;
-use super::{BenchmarkStats, StressStats};
+use super::{AimdRateController, BenchmarkStats, QpsLatencySample, RampSchedule, StressStats};
 pub struct BenchMetrics {
     pub benchmark_duration: IntCounter,
     pub num_success: IntCounterVec,
     pub num_error: IntCounterVec,
+    pub num_semantic_failures: IntCounterVec,
+    pub shared_object_conflicts: IntCounterVec,
     pub num_submitted: IntCounterVec,
     pub num_in_flight: GaugeVec,
     pub latency_s: HistogramVec,
     pub latency_squared_s: CounterVec,
+    pub read_latency_s: HistogramVec,
     pub validators_in_tx_cert: IntCounterVec,
     pub validators_in_effects_cert: IntCounterVec,
     pub cpu_usage: GaugeVec,
 }
 
+/// How often workers following a `RampSchedule` or `WorkloadSchedule` adjust their request rate
+const RAMP_UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often workers with backpressure compare their latency to the target and adjust their
+/// request rate
+const BACKPRESSURE_UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long workers wait for their in flight transactions at the end of the run by default
+const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often a worker tracking finality looks up whether a transaction is in a checkpoint yet
+const FINALITY_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long after its submission a transaction tracked for finality is looked for in a
+/// checkpoint, before it is counted in `BenchmarkStats::num_finality_timeouts`
+const FINALITY_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Error category of invalid transactions which were executed instead of rejected
+const INVALID_ACCEPTED: &str = "invalid_accepted";
+
 const LATENCY_SEC_BUCKETS: &[f64] = &[
     0.1, 0.25, 0.5, 0.75, 1., 1.25, 1.5, 1.75, 2., 2.5, 5., 10., 20., 30., 60., 90.,
 ];
@@ -79,6 +111,20 @@ impl BenchMetrics {
                 registry,
             )
             .unwrap(),
+            num_semantic_failures: register_int_counter_vec_with_registry!(
+                "num_semantic_failures",
+                "Total number of successful transactions whose sampled effects did not match what they should have done",
+                &["workload"],
+                registry,
+            )
+            .unwrap(),
+            shared_object_conflicts: register_int_counter_vec_with_registry!(
+                "shared_object_conflicts",
+                "Total number of successful transactions which found a shared object written by another transaction since the payload last wrote it",
+                &["workload"],
+                registry,
+            )
+            .unwrap(),
             num_submitted: register_int_counter_vec_with_registry!(
                 "num_submitted",
                 "Total number of transaction submitted to sui",
@@ -108,6 +154,14 @@ impl BenchMetrics {
                 registry,
             )
             .unwrap(),
+            read_latency_s: register_histogram_vec_with_registry!(
+                "read_latency_s",
+                "Total time in seconds to return a response to a read-only query",
+                &["workload"],
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
             validators_in_tx_cert: register_int_counter_vec_with_registry!(
                 "validators_in_tx_cert",
                 "Number of times a validator was included in tx cert",
@@ -135,6 +189,8 @@ impl BenchMetrics {
 
 struct Stats {
     pub id: usize,
+    /// Name of the workload the worker generates transactions for
+    pub workload: &'static str,
     pub num_no_gas: u64,
     pub num_submitted: u64,
     pub num_in_flight: u64,
@@ -153,8 +209,251 @@ enum NextOp {
         gas_used: u64,
         /// The payload updated with the effects of the transaction
         payload: Box<dyn Payload>,
+        /// Whether the effects were sampled for verification and did not match what the
+        /// transaction should have done, see `BenchDriver::with_verify_effects`
+        semantic_failure: bool,
+        /// Whether another transaction was sequenced on a shared object of the transaction since
+        /// the payload last wrote it, see `Payload::is_shared_object_conflict`
+        shared_object_conflict: bool,
+        /// Regions of the validators which signed the effects certificate, see
+        /// `BenchDriver::with_validator_regions`
+        quorum_regions: BTreeSet<String>,
+        /// The transaction and when it was submitted, to track its finality
+        digest: TransactionDigest,
+        submitted: Instant,
+    },
+    /// A transaction the payload made invalid on purpose completed after the given time, it
+    /// should have been rejected and is counted as an error otherwise
+    Invalid {
+        latency: Duration,
+        rejected: bool,
+        payload: Box<dyn Payload>,
+    },
+    /// The transaction failed with an error of the given category after the given time, and is
+    /// resubmitted later
+    Retry {
+        retry: RetryType,
+        category: String,
+        error: String,
+        latency: Duration,
+    },
+    /// A read-only query finished
+    Query {
+        latency: Duration,
+        success: bool,
+        payload: Box<dyn Payload>,
     },
-    Retry(RetryType),
+}
+
+/// One row of the per transaction record written when `BenchDriver::record_path` is set, also
+/// exported as a span when `BenchDriver::otel_endpoint` is set
+#[derive(Clone)]
+struct TxRecord {
+    /// Time from the start of the benchmark to the transaction being submitted
+    submitted: Duration,
+    latency: Duration,
+    workload: &'static str,
+    /// "success", or the category of the error the transaction failed with
+    status: String,
+    /// Wall clock time the transaction completed at
+    completed_at: SystemTime,
+}
+
+impl TxRecord {
+    const CSV_HEADER: &'static str = "submit_time_ms,confirm_time_ms,latency_ms,workload,status";
+
+    fn send(
+        record_txs: &[UnboundedSender<TxRecord>],
+        start_time: &Instant,
+        latency: Duration,
+        workload: &'static str,
+        status: impl Into<String>,
+    ) {
+        if record_txs.is_empty() {
+            return;
+        }
+        let record = TxRecord {
+            submitted: start_time.elapsed().saturating_sub(latency),
+            latency,
+            workload,
+            status: status.into(),
+            completed_at: SystemTime::now(),
+        };
+        for record_tx in record_txs {
+            // The writer only goes away if it failed to write, which it has logged
+            let _ = record_tx.send(record.clone());
+        }
+    }
+
+    fn to_csv_row(&self) -> String {
+        format!(
+            "{},{},{},{},{}",
+            self.submitted.as_millis(),
+            (self.submitted + self.latency).as_millis(),
+            self.latency.as_millis(),
+            self.workload,
+            self.status
+        )
+    }
+}
+
+/// Write every record received on `record_rx` to a CSV file at `path`, on a blocking thread so
+/// that file I/O stays off the runtime running the benchmark
+fn record_writer(
+    path: PathBuf,
+    mut record_rx: UnboundedReceiver<TxRecord>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::task::spawn_blocking(move || {
+        let write_all = || -> std::io::Result<()> {
+            let mut writer = BufWriter::new(File::create(&path)?);
+            writeln!(writer, "{}", TxRecord::CSV_HEADER)?;
+            while let Some(record) = record_rx.blocking_recv() {
+                writeln!(writer, "{}", record.to_csv_row())?;
+            }
+            writer.flush()
+        };
+        if let Err(e) = write_all() {
+            error!("Failed to write transaction records to {:?}: {}", path, e);
+        }
+    })
+}
+
+/// Export every record received on `record_rx` as a span of the transaction from its submission
+/// to its effects, with the workload, latency and outcome as attributes. Spans are batched and
+/// sent to the OTLP collector at `endpoint` in the background, and flushed once `record_rx` closes
+fn span_exporter(
+    endpoint: String,
+    mut record_rx: UnboundedReceiver<TxRecord>,
+) -> Result<tokio::task::JoinHandle<()>> {
+    let tracer = opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(endpoint.clone()),
+        )
+        .install_batch(opentelemetry::runtime::Tokio)
+        .with_context(|| format!("Failed to set up the OpenTelemetry exporter to {endpoint}"))?;
+    Ok(tokio::spawn(async move {
+        while let Some(record) = record_rx.recv().await {
+            let mut span = tracer
+                .span_builder("transaction")
+                .with_kind(SpanKind::Client)
+                .with_start_time(record.completed_at - record.latency)
+                .with_attributes(vec![
+                    KeyValue::new("workload", record.workload),
+                    KeyValue::new("latency_ms", record.latency.as_millis() as i64),
+                    KeyValue::new("result", record.status.clone()),
+                ])
+                .start(&tracer);
+            // Invalid transactions are expected to be rejected
+            if record.status != "success" && record.status != "rejected" {
+                span.set_status(Status::error(record.status));
+            }
+            span.end_with_timestamp(record.completed_at);
+        }
+        // Flushing blocks until the collector has received the last batch
+        tokio::task::spawn_blocking(opentelemetry::global::shutdown_tracer_provider)
+            .await
+            .unwrap();
+    }))
+}
+
+/// Size of the BCS serialization of `tx`, signatures included, as sent to the validators
+fn serialized_size(tx: &VerifiedTransaction) -> u64 {
+    bcs::serialized_size(tx.data()).unwrap() as u64
+}
+
+/// Check `effects` with `payload.verify_effects` for a random `sample_rate` fraction of calls,
+/// returning whether the check was made and failed
+fn verify_sampled_effects(
+    sample_rate: f64,
+    payload: &dyn Payload,
+    effects: &ExecutionEffects,
+) -> bool {
+    if sample_rate == 0.0 || rand::random::<f64>() >= sample_rate {
+        return false;
+    }
+    match payload.verify_effects(effects) {
+        Ok(()) => false,
+        Err(e) => {
+            error!("Unexpected effects for {payload}: {e}");
+            true
+        }
+    }
+}
+
+/// Load the region of each validator from `path`, a table from the hex encoded protocol public
+/// key of a validator, with or without its `k#` prefix, to the name of its region, e.g.
+/// `"k#8e1f..." = "us-east-1"`. Parsed as JSON if it has a `.json` extension and as TOML otherwise.
+pub fn load_validator_regions(path: &Path) -> Result<BTreeMap<AuthorityName, String>> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Failed to read validator regions from {path:?}: {e}"))?;
+    let regions: BTreeMap<String, String> = if path.extension().map_or(false, |ext| ext == "json") {
+        serde_json::from_str(&contents)?
+    } else {
+        toml::from_str(&contents)?
+    };
+    regions
+        .into_iter()
+        .map(|(name, region)| {
+            let key = name.strip_prefix("k#").unwrap_or(&name);
+            let name = AuthorityName::from_str(key)
+                .map_err(|e| anyhow!("Invalid validator public key {name:?}: {e}"))?;
+            Ok((name, region))
+        })
+        .collect()
+}
+
+/// Regions, as given by `validator_regions`, of the validators which signed the effects
+/// certificate of `effects`. Validators without a region are left out
+fn quorum_regions(
+    validator_regions: &BTreeMap<AuthorityName, String>,
+    committee: &Committee,
+    effects: &ExecutionEffects,
+) -> BTreeSet<String> {
+    match effects.quorum_sig() {
+        Some(sig_info) if !validator_regions.is_empty() => sig_info
+            .authorities(committee)
+            .filter_map(|name| validator_regions.get(name.ok()?).cloned())
+            .collect(),
+        _ => BTreeSet::new(),
+    }
+}
+
+/// Time from `submitted` until transaction `digest` is found in a checkpoint, polling `proxy`
+/// every `FINALITY_POLL_INTERVAL`. `None` if it is not found within `FINALITY_TIMEOUT` of its
+/// submission, or if `proxy` cannot look checkpoints up.
+async fn wait_for_finality(
+    proxy: Arc<dyn ValidatorProxy + Send + Sync>,
+    digest: TransactionDigest,
+    submitted: Instant,
+) -> Option<Duration> {
+    let deadline = submitted + FINALITY_TIMEOUT;
+    loop {
+        match proxy.get_transaction_checkpoint(digest).await {
+            Ok(Some(_)) => return Some(submitted.elapsed()),
+            Ok(None) => (),
+            Err(e) => {
+                debug!("Cannot track the finality of {digest}: {e}");
+                return None;
+            }
+        }
+        if Instant::now() + FINALITY_POLL_INTERVAL > deadline {
+            return None;
+        }
+        time::sleep(FINALITY_POLL_INTERVAL).await;
+    }
+}
+
+/// Interval for sending `qps` requests per second, starting at `start` or now if it has passed
+fn request_interval_at(start: Instant, qps: u64) -> time::Interval {
+    let mut interval = time::interval_at(
+        start.max(Instant::now()),
+        Duration::from_micros(1_000_000 / qps),
+    );
+    interval.set_missed_tick_behavior(time::MissedTickBehavior::Burst);
+    interval
 }
 
 async fn print_and_start_benchmark() -> &'static Instant {
@@ -167,6 +466,8 @@ async fn print_and_start_benchmark() -> &'static Instant {
 }
 
 pub struct BenchWorker {
+    /// Name of the workload all of `payload` was generated by
+    pub workload: &'static str,
     pub target_qps: u64,
     pub payload: Vec<Box<dyn Payload>>,
     pub proxy: Arc<dyn ValidatorProxy + Send + Sync>,
@@ -177,8 +478,64 @@ pub struct BenchDriver {
     pub stress_stat_collection: bool,
     pub start_time: Instant,
     pub token: CancellationToken,
+    /// Cancelled to stop sending new transactions, workers return once their in flight
+    /// transactions have completed or `drain_timeout` has passed
+    pub drain_token: CancellationToken,
+    /// How long workers wait for their in flight transactions once the run has ended or
+    /// `drain` was called, the ones still in flight after it are counted as abandoned
+    pub drain_timeout: Duration,
+    /// Transactions completing within this long of the start of the benchmark are executed but
+    /// left out of the returned stats
+    pub warmup: Duration,
+    /// Each worker sends its first request at a uniformly random time within this long of the
+    /// start of the benchmark, rather than all of them at once
+    pub stagger: Duration,
+    /// Varies the offered load over the run instead of holding every worker at its target QPS
+    pub ramp: Option<RampSchedule>,
+    /// Varies the share of the load of each workload over the run, the workloads must have been
+    /// built from it with `WorkloadConfiguration::build_workloads_from_schedule`
+    pub schedule: Option<Arc<WorkloadSchedule>>,
+    /// Where to write a CSV row for every completed transaction, nothing is recorded if unset
+    pub record_path: Option<PathBuf>,
+    /// OTLP collector to export a span of every completed transaction to, no spans are created
+    /// if unset
+    pub otel_endpoint: Option<String>,
+    /// Cap on the transactions in flight across all workers, otherwise only the number of
+    /// payloads (target QPS * in flight ratio) limits it
+    pub max_in_flight: Option<usize>,
+    /// Median latency workers try to stay under by lowering their request rate, the rate is
+    /// fixed (open-loop) if unset
+    pub backpressure: Option<Duration>,
+    /// Whether to look every successful transaction up until it is in a checkpoint
+    pub track_finality: bool,
+    /// Fraction of successful transactions whose effects are checked with
+    /// `Payload::verify_effects`, 0.0 checks none
+    pub verify_effects_rate: f64,
+    /// Region of each validator, latency is not broken down by region if empty
+    pub validator_regions: Arc<BTreeMap<AuthorityName, String>>,
+    /// Whether to end the run at the first transaction error, see `BenchmarkAborted`
+    pub fail_fast: bool,
+}
+
+/// Error `BenchDriver::run` returns when it ended the run at the first transaction error, see
+/// `BenchDriver::with_fail_fast`. Downcast the error to get the stats up to the end of the run.
+#[derive(Debug)]
+pub struct BenchmarkAborted {
+    /// The first error, other workers may fail concurrently before they stop
+    pub error: String,
+    /// Stats of the run, including the transactions in flight at the error, which completed or
+    /// were abandoned after the drain timeout
+    pub stats: BenchmarkStats,
+}
+
+impl std::fmt::Display for BenchmarkAborted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Benchmark aborted at the first error: {}", self.error)
+    }
 }
 
+impl std::error::Error for BenchmarkAborted {}
+
 impl BenchDriver {
     pub fn new(stat_collection_interval: u64, stress_stat_collection: bool) -> BenchDriver {
         BenchDriver {
@@ -186,11 +543,159 @@ impl BenchDriver {
             stress_stat_collection,
             start_time: Instant::now(),
             token: CancellationToken::new(),
+            drain_token: CancellationToken::new(),
+            drain_timeout: DEFAULT_DRAIN_TIMEOUT,
+            warmup: Duration::ZERO,
+            stagger: Duration::ZERO,
+            ramp: None,
+            schedule: None,
+            record_path: None,
+            otel_endpoint: None,
+            max_in_flight: None,
+            backpressure: None,
+            track_finality: false,
+            verify_effects_rate: 0.0,
+            validator_regions: Arc::new(BTreeMap::new()),
+            fail_fast: false,
         }
     }
+    /// Exclude the first `warmup` of the benchmark from the stats, the warmup still counts
+    /// towards the run duration.
+    pub fn with_warmup(mut self, warmup: Duration) -> Self {
+        self.warmup = warmup;
+        self
+    }
+    /// Spread the workers' first requests uniformly over the first `stagger` of the benchmark to
+    /// avoid a burst at the start, pair with `with_warmup` to leave the stagger out of the stats.
+    pub fn with_stagger(mut self, stagger: Duration) -> Self {
+        self.stagger = stagger;
+        self
+    }
+    /// Follow `ramp` for the total offered load, which is split between the workers in proportion
+    /// to their target QPS. Latency is sampled against the offered load once per stat interval.
+    pub fn with_ramp(mut self, ramp: RampSchedule) -> Self {
+        self.ramp = Some(ramp);
+        self
+    }
+    /// Run the phases of `schedule` one after the other, repeating them until the end of the
+    /// run, by slowing the workers of each workload down to its share of the current phase. The
+    /// workloads must have been built with `WorkloadConfiguration::build_workloads_from_schedule`.
+    /// Stats are broken down by phase, at the granularity of the stat collection interval.
+    pub fn with_schedule(mut self, schedule: WorkloadSchedule) -> Self {
+        self.schedule = Some(Arc::new(schedule));
+        self
+    }
+    /// Write a CSV row with the submit and confirm times, latency, workload and outcome of every
+    /// completed transaction and query to `record_path`. Rows are buffered and written on a
+    /// separate thread.
+    pub fn with_record_path(mut self, record_path: PathBuf) -> Self {
+        self.record_path = Some(record_path);
+        self
+    }
+    /// Export a span of every completed transaction and query, from its submission to its
+    /// effects, to the OpenTelemetry collector listening for OTLP over gRPC at `endpoint`, e.g.
+    /// Jaeger or Tempo. Spans carry the workload, latency and outcome of the transaction.
+    pub fn with_otel(mut self, endpoint: String) -> Self {
+        self.otel_endpoint = Some(endpoint);
+        self
+    }
+    /// Poll the proxy of every successful transaction until the transaction is in a checkpoint,
+    /// and record the time from its submission in `BenchmarkStats::finality_latency_ms`, next to
+    /// the latency up to its effects certificate. Off by default as the polling adds a query per
+    /// `FINALITY_POLL_INTERVAL` for every transaction waiting for its checkpoint. The proxies
+    /// must support `ValidatorProxy::get_transaction_checkpoint`, i.e. be full nodes.
+    pub fn with_finality_tracking(mut self, track_finality: bool) -> Self {
+        self.track_finality = track_finality;
+        self
+    }
+    /// Keep at most `max_in_flight` transactions in flight, split between the workers in
+    /// proportion to their target QPS. This only lowers the in flight limit, which is otherwise
+    /// the number of payloads the workloads created.
+    pub fn with_max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = Some(max_in_flight);
+        self
+    }
+    /// Lower the rate of each worker while the median latency of its transactions is above
+    /// `target_latency` and raise it back while below, AIMD-style, up to the rate it would offer
+    /// without backpressure. Stats report the offered load next to the throughput achieved.
+    pub fn with_backpressure(mut self, target_latency: Duration) -> Self {
+        self.backpressure = Some(target_latency);
+        self
+    }
+    /// Wait at most `drain_timeout` for the transactions in flight at the end of the run, see
+    /// `BenchmarkStats::num_abandoned_txes`.
+    pub fn with_drain_timeout(mut self, drain_timeout: Duration) -> Self {
+        self.drain_timeout = drain_timeout;
+        self
+    }
+    /// Check the effects of a random `sample_rate` fraction of the successful transactions against
+    /// what their payload should have done, see `BenchmarkStats::num_semantic_failures`. The rate
+    /// bounds the overhead of the checks.
+    pub fn with_verify_effects(mut self, sample_rate: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&sample_rate),
+            "Effects verification sample rate must be between 0.0 and 1.0"
+        );
+        self.verify_effects_rate = sample_rate;
+        self
+    }
+    /// Break the latency of successful transactions down by the region of the validators which
+    /// formed their effects quorum, see `BenchmarkStats::latency_ms_by_region`. Validators
+    /// missing from `validator_regions` are not attributed to any region. See
+    /// `load_validator_regions` for a file to read them from.
+    pub fn with_validator_regions(
+        mut self,
+        validator_regions: BTreeMap<AuthorityName, String>,
+    ) -> Self {
+        self.validator_regions = Arc::new(validator_regions);
+        self
+    }
+    /// End the run at the first transaction which fails, or invalid transaction which is
+    /// accepted, instead of retrying it. Workers stop sending and wait for the transactions in
+    /// flight as with `drain`, then `run` returns a `BenchmarkAborted` error.
+    pub fn with_fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = fail_fast;
+        self
+    }
     pub fn terminate(&self) {
         self.token.cancel()
     }
+    /// Stop sending new transactions and wait up to `drain_timeout` for the ones in flight, unlike
+    /// `terminate` the stats include everything that was sent
+    pub fn drain(&self) {
+        self.drain_token.cancel()
+    }
+    /// A bar of the successful transactions out of the target for `Interval::Count`, of the
+    /// elapsed seconds out of the run duration for `Interval::Time`, and a spinner counting
+    /// completed transactions for unbounded runs. Only completed transactions move it.
+    fn progress_bar(run_duration: Interval) -> ProgressBar {
+        match run_duration {
+            // Advanced by every completed transaction rather than a steady tick, which would need
+            // a thread of its own
+            Interval::Count(0) | Interval::Time(Duration::MAX) => ProgressBar::new_spinner()
+                .with_prefix("Running benchmark(unbounded)")
+                .with_style(
+                    ProgressStyle::with_template(
+                        "{prefix}: {spinner} {pos} transactions completed: {msg}",
+                    )
+                    .unwrap(),
+                ),
+            Interval::Count(count) => ProgressBar::new(count)
+                .with_prefix("Running benchmark(count)")
+                .with_style(
+                    ProgressStyle::with_template(
+                        "{prefix}: {wide_bar} {pos} of {len} transactions: {msg}",
+                    )
+                    .unwrap(),
+                ),
+            Interval::Time(duration) => ProgressBar::new(duration.as_secs())
+                .with_prefix("Running benchmark(duration)")
+                .with_style(
+                    ProgressStyle::with_template("{prefix}: {wide_bar} {pos}/{len}s: {msg}")
+                        .unwrap(),
+                ),
+        }
+    }
     pub fn update_progress(
         start_time: Instant,
         interval: Interval,
@@ -198,6 +703,7 @@ impl BenchDriver {
         progress_bar: Arc<ProgressBar>,
     ) {
         match interval {
+            Interval::Count(0) => progress_bar.inc(1),
             Interval::Count(count) => {
                 progress_bar.inc(1);
                 if progress_bar.position() >= count {
@@ -226,6 +732,7 @@ impl BenchDriver {
         if qps == 0 {
             return vec![];
         }
+        info!("Making workers for {}", workload_info.workload.describe());
         let mut payloads = workload_info
             .workload
             .make_test_payloads(proxy.clone(), system_state_observer.clone())
@@ -237,6 +744,7 @@ impl BenchDriver {
                 let chunk_size = payloads.len() / total_workers as usize;
                 let remaining = payloads.split_off(chunk_size);
                 workers.push(BenchWorker {
+                    workload: workload_info.workload.name(),
                     target_qps,
                     payload: payloads,
                     proxy: proxy.clone(),
@@ -295,34 +803,58 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
         let metrics = Arc::new(BenchMetrics::new(registry));
         let barrier = Arc::new(Barrier::new(num_workers as usize));
         info!("Setting up {:?} workers...", num_workers);
-        let progress = Arc::new(match run_duration {
-            Interval::Count(count) => ProgressBar::new(count)
-                .with_prefix("Running benchmark(count):")
-                .with_style(
-                    ProgressStyle::with_template("{prefix}: {wide_bar} {pos}/{len}: {msg}")
-                        .unwrap(),
-                ),
-            Interval::Time(Duration::MAX) => ProgressBar::hidden(),
-            Interval::Time(duration) => ProgressBar::new(duration.as_secs())
-                .with_prefix("Running benchmark(duration):")
-                .with_style(
-                    ProgressStyle::with_template("{prefix}: {wide_bar} {pos}/{len}").unwrap(),
-                ),
-        });
+        let progress = Arc::new(BenchDriver::progress_bar(run_duration));
+        let warmup = self.warmup;
+        let stagger = self.stagger;
+        let mut record_txs = vec![];
+        let mut record_tasks = vec![];
+        if let Some(path) = &self.record_path {
+            let (record_tx, record_rx) = tokio::sync::mpsc::unbounded_channel();
+            record_txs.push(record_tx);
+            record_tasks.push(record_writer(path.clone(), record_rx));
+        }
+        if let Some(endpoint) = &self.otel_endpoint {
+            let (record_tx, record_rx) = tokio::sync::mpsc::unbounded_channel();
+            record_txs.push(record_tx);
+            record_tasks.push(span_exporter(endpoint.clone(), record_rx)?);
+        }
+        let total_target_qps: u64 = bench_workers.iter().map(|w| w.target_qps).sum();
+        let fail_fast = self.fail_fast;
+        let first_error: Arc<OnceCell<String>> = Arc::new(OnceCell::new());
         for (i, worker) in bench_workers.into_iter().enumerate() {
+            let first_error = first_error.clone();
             let cloned_token = self.token.clone();
+            let cloned_drain_token = self.drain_token.clone();
+            let drain_timeout = self.drain_timeout;
+            let verify_effects_rate = self.verify_effects_rate;
+            let validator_regions = self.validator_regions.clone();
+            let workload_name = worker.workload;
             let request_delay_micros = 1_000_000 / worker.target_qps;
+            let ramp_cloned = self.ramp.clone();
+            let schedule_cloned = self.schedule.clone();
+            let backpressure = self.backpressure;
+            let track_finality = self.track_finality;
+            let qps_share = worker.target_qps as f64 / total_target_qps as f64;
+            let worker_max_in_flight = self
+                .max_in_flight
+                .map(|max| ((max as f64 * qps_share).round() as u64).max(1));
             let mut free_pool = worker.payload;
             let progress_cloned = progress.clone();
             let tx_cloned = tx.clone();
             let cloned_barrier = barrier.clone();
             let metrics_cloned = metrics.clone();
+            let record_tx_cloned = record_txs.clone();
 
             let runner = tokio::spawn(async move {
                 cloned_barrier.wait().await;
                 let start_time = print_and_start_benchmark().await;
                 let mut num_success_txes = 0;
                 let mut num_error_txes = 0;
+                let mut num_rejected_txes = 0;
+                let mut num_semantic_failures = 0;
+                let mut shared_object_conflicts = 0;
+                let mut num_abandoned_txes = 0;
+                let mut errors_by_category: BTreeMap<String, u64> = BTreeMap::new();
                 let mut num_success_cmds = 0;
                 let mut num_no_gas = 0;
                 let mut num_in_flight: u64 = 0;
@@ -330,27 +862,131 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
                 let mut total_gas_used = 0;
                 let mut latency_histogram =
                     hdrhistogram::Histogram::<u64>::new_with_max(120_000, 3).unwrap();
+                let mut gas_histogram = hdrhistogram::Histogram::<u64>::new(3).unwrap();
+                let mut txn_bytes_histogram = hdrhistogram::Histogram::<u64>::new(3).unwrap();
+                let mut num_success_reads = 0;
+                let mut num_error_reads = 0;
+                let mut read_latency_histogram =
+                    hdrhistogram::Histogram::<u64>::new_with_max(120_000, 3).unwrap();
+                let mut latency_ms_by_region: BTreeMap<String, HistogramWrapper> = BTreeMap::new();
+                let mut finality_histogram =
+                    hdrhistogram::Histogram::<u64>::new_with_max(120_000, 3).unwrap();
+                let mut num_finality_timeouts = 0;
+                let mut finality_polls: FuturesUnordered<BoxFuture<Option<Duration>>> =
+                    FuturesUnordered::new();
+                let first_request = *start_time + stagger.mul_f64(rand::random::<f64>());
                 let mut request_interval =
-                    time::interval(Duration::from_micros(request_delay_micros));
+                    time::interval_at(first_request, Duration::from_micros(request_delay_micros));
                 request_interval.set_missed_tick_behavior(time::MissedTickBehavior::Burst);
+                let mut current_qps = worker.target_qps;
+                let mut ramp_interval = time::interval(RAMP_UPDATE_INTERVAL);
+                // Set while the current phase of the schedule gives the workload no weight
+                let mut paused = schedule_cloned.as_ref().map_or(false, |schedule| {
+                    schedule.rate_scale_at(workload_name, Duration::ZERO) == 0.0
+                });
+                // The rate the worker would offer without backpressure
+                let mut ceiling_qps = worker.target_qps;
+                let mut rate_controller = backpressure.map(|target_latency| {
+                    AimdRateController::new(target_latency, worker.target_qps)
+                });
+                let mut backpressure_interval = time::interval(BACKPRESSURE_UPDATE_INTERVAL);
+                let mut backpressure_latency_histogram =
+                    hdrhistogram::Histogram::<u64>::new_with_max(120_000, 3).unwrap();
                 let mut stat_interval = time::interval(Duration::from_micros(stat_delay_micros));
                 let mut futures: FuturesUnordered<BoxFuture<NextOp>> = FuturesUnordered::new();
 
                 let mut retry_queue: VecDeque<RetryType> = VecDeque::new();
                 let mut stat_start_time: Instant = Instant::now();
+                let warmup_end = time::sleep_until(*start_time + warmup);
+                tokio::pin!(warmup_end);
+                let mut warmed_up = warmup.is_zero();
+                // Workers stop sending once the run has ended, either when `run_duration` is
+                // reached or `drain` is called, then wait until `drain_end` for the transactions
+                // in flight
+                let mut draining = false;
+                let drain_end = time::sleep(Duration::ZERO);
+                tokio::pin!(drain_end);
                 loop {
                     tokio::select! {
                         _ = cloned_token.cancelled() => {
                             break;
                         }
+                        _ = cloned_drain_token.cancelled(), if !draining => {
+                            draining = true;
+                            drain_end.as_mut().reset(Instant::now() + drain_timeout);
+                            if futures.is_empty() && finality_polls.is_empty() {
+                                break;
+                            }
+                        }
+                        _ = &mut drain_end, if draining => {
+                            num_abandoned_txes = futures.len() as u64;
+                            if num_abandoned_txes > 0 {
+                                info!("Abandoning {num_abandoned_txes} transactions still in flight after {drain_timeout:?}");
+                            }
+                            num_finality_timeouts += finality_polls.len() as u64;
+                            break;
+                        }
+                        _ = &mut warmup_end, if !warmed_up => {
+                            // Drop everything recorded during the warmup
+                            warmed_up = true;
+                            num_success_txes = 0;
+                            num_error_txes = 0;
+                            num_rejected_txes = 0;
+                            num_semantic_failures = 0;
+                            shared_object_conflicts = 0;
+                            errors_by_category.clear();
+                            num_success_cmds = 0;
+                            num_no_gas = 0;
+                            num_submitted = 0;
+                            total_gas_used = 0;
+                            num_success_reads = 0;
+                            num_error_reads = 0;
+                            stat_start_time = Instant::now();
+                            latency_histogram.reset();
+                            gas_histogram.reset();
+                            txn_bytes_histogram.reset();
+                            read_latency_histogram.reset();
+                            latency_ms_by_region.clear();
+                            finality_histogram.reset();
+                            num_finality_timeouts = 0;
+                        }
+                        _ = ramp_interval.tick(), if ramp_cloned.is_some() || schedule_cloned.is_some() => {
+                            let elapsed = start_time.elapsed();
+                            let open_loop_qps = match &ramp_cloned {
+                                Some(ramp) => ramp.qps_at(elapsed) as f64 * qps_share,
+                                None => worker.target_qps as f64,
+                            };
+                            let scale = schedule_cloned
+                                .as_ref()
+                                .map_or(1.0, |schedule| schedule.rate_scale_at(workload_name, elapsed));
+                            paused = scale == 0.0;
+                            ceiling_qps = ((open_loop_qps * scale).round() as u64).max(1);
+                            // With backpressure the new ceiling applies on the next update
+                            if rate_controller.is_none() && ceiling_qps != current_qps {
+                                current_qps = ceiling_qps;
+                                request_interval = request_interval_at(first_request, current_qps);
+                            }
+                        }
+                        _ = backpressure_interval.tick(), if rate_controller.is_some() => {
+                            let latency = (!backpressure_latency_histogram.is_empty())
+                                .then(|| Duration::from_millis(backpressure_latency_histogram.value_at_quantile(0.5)));
+                            backpressure_latency_histogram.reset();
+                            let qps = rate_controller.as_mut().unwrap().update(ceiling_qps, latency);
+                            if qps != current_qps {
+                                debug!("Worker {i} adjusting rate from {current_qps} to {qps} qps for latency {latency:?}");
+                                current_qps = qps;
+                                request_interval = request_interval_at(first_request, current_qps);
+                            }
+                        }
                         _ = stat_interval.tick() => {
-                            if tx_cloned
+                            if warmed_up && tx_cloned
                                 .try_send(Stats {
                                     id: i,
+                                    workload: workload_name,
                                     num_no_gas,
                                     num_in_flight,
                                     num_submitted,
-                                    bench_stats: BenchmarkStats {duration:stat_start_time.elapsed(),num_error_txes,num_success_txes,num_success_cmds,latency_ms:HistogramWrapper{histogram:latency_histogram.clone()}, total_gas_used },
+                                    bench_stats: BenchmarkStats {duration:stat_start_time.elapsed(),num_error_txes,num_success_txes,num_success_cmds,num_rejected_txes,num_semantic_failures,shared_object_conflicts,num_submitted_txes: num_submitted,latency_ms:HistogramWrapper{histogram:latency_histogram.clone()}, total_gas_used, gas_used_per_tx:HistogramWrapper{histogram:gas_histogram.clone()}, txn_bytes:HistogramWrapper{histogram:txn_bytes_histogram.clone()}, errors_by_category: errors_by_category.clone(), num_success_reads, num_error_reads, read_latency_ms:HistogramWrapper{histogram:read_latency_histogram.clone()}, finality_latency_ms:HistogramWrapper{histogram:finality_histogram.clone()}, num_finality_timeouts, latency_ms_by_region: latency_ms_by_region.clone(), ..BenchmarkStats::empty() },
                                 })
                                 .is_err()
                             {
@@ -358,23 +994,36 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
                             }
                             num_success_txes = 0;
                             num_error_txes = 0;
+                            num_rejected_txes = 0;
+                            num_semantic_failures = 0;
+                            shared_object_conflicts = 0;
+                            errors_by_category.clear();
                             num_success_cmds = 0;
                             num_no_gas = 0;
                             num_submitted = 0;
+                            num_success_reads = 0;
+                            num_error_reads = 0;
                             stat_start_time = Instant::now();
                             latency_histogram.reset();
+                            gas_histogram.reset();
+                            txn_bytes_histogram.reset();
+                            read_latency_histogram.reset();
+                            latency_ms_by_region.clear();
+                            finality_histogram.reset();
+                            num_finality_timeouts = 0;
                         }
-                        _ = request_interval.tick() => {
+                        _ = request_interval.tick(), if !draining && !paused => {
 
                             // If a retry is available send that
                             // (sending retries here subjects them to our rate limit)
                             if let Some(mut b) = retry_queue.pop_front() {
-                                num_error_txes += 1;
                                 num_submitted += 1;
+                                txn_bytes_histogram.saturating_record(serialized_size(&b.0));
                                 metrics_cloned.num_submitted.with_label_values(&[&b.1.to_string()]).inc();
                                 let metrics_cloned = metrics_cloned.clone();
                                 // TODO: clone committee for each request is not ideal.
                                 let committee_cloned = Arc::new(worker.proxy.clone_committee());
+                                let validator_regions = validator_regions.clone();
                                 let start = Arc::new(Instant::now());
                                 let res = worker.proxy
                                     .execute_transaction_block(b.0.clone().into())
@@ -400,13 +1049,16 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
                                                     sig_info.authorities(&committee_cloned).for_each(|name| metrics_cloned.validators_in_effects_cert.with_label_values(&[&name.unwrap().to_string()]).inc())
                                                 }
                                                 let num_commands = b.0.data().transaction_data().kind().num_commands() as u16;
+                                                let semantic_failure = verify_sampled_effects(verify_effects_rate, &*b.1, &effects);
+                                                let shared_object_conflict = b.1.is_shared_object_conflict(&effects);
+                                                let quorum_regions = quorum_regions(&validator_regions, &committee_cloned, &effects);
                                                 b.1.make_new_payload(&effects);
-                                                NextOp::Response {latency,num_commands,payload:b.1, gas_used: effects.gas_used() }
+                                                NextOp::Response {latency,num_commands,payload:b.1, gas_used: effects.gas_used(), semantic_failure, shared_object_conflict, quorum_regions, digest: *b.0.digest(), submitted: *start }
                                             }
                                             Err(err) => {
                                                 error!("{}", err);
                                                 metrics_cloned.num_error.with_label_values(&[&b.1.to_string()]).inc();
-                                                NextOp::Retry(b)
+                                                NextOp::Retry { category: error_category(&err), error: err.to_string(), retry: b, latency: start.elapsed() }
                                             }
                                         }
                                     });
@@ -415,7 +1067,9 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
                             }
 
                             // Otherwise send a fresh request
-                            if free_pool.is_empty() {
+                            let at_max_in_flight = worker_max_in_flight
+                                .map_or(false, |max| num_in_flight >= max);
+                            if free_pool.is_empty() || at_max_in_flight {
                                 num_no_gas += 1;
                             } else {
                                 let mut payload = free_pool.pop().unwrap();
@@ -423,11 +1077,35 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
                                 num_submitted += 1;
                                 metrics_cloned.num_in_flight.with_label_values(&[&payload.to_string()]).inc();
                                 metrics_cloned.num_submitted.with_label_values(&[&payload.to_string()]).inc();
+                                if let Some(query) = payload.make_query() {
+                                    let start = Instant::now();
+                                    let metrics_cloned = metrics_cloned.clone();
+                                    let res = query.map(move |res| {
+                                        metrics_cloned.num_in_flight.with_label_values(&[&payload.to_string()]).dec();
+                                        match res {
+                                            Ok(()) => {
+                                                let latency = start.elapsed();
+                                                metrics_cloned.read_latency_s.with_label_values(&[&payload.to_string()]).observe(latency.as_secs_f64());
+                                                metrics_cloned.num_success.with_label_values(&[&payload.to_string()]).inc();
+                                                NextOp::Query { latency, success: true, payload }
+                                            }
+                                            Err(err) => {
+                                                error!("Query failed: {}", err);
+                                                metrics_cloned.num_error.with_label_values(&[&payload.to_string()]).inc();
+                                                NextOp::Query { latency: start.elapsed(), success: false, payload }
+                                            }
+                                        }
+                                    });
+                                    futures.push(Box::pin(res));
+                                    continue
+                                }
                                 let tx = payload.make_transaction();
+                                txn_bytes_histogram.saturating_record(serialized_size(&tx));
                                 let start = Arc::new(Instant::now());
                                 let metrics_cloned = metrics_cloned.clone();
                                 // TODO: clone committee for each request is not ideal.
                                 let committee_cloned = Arc::new(worker.proxy.clone_committee());
+                                let validator_regions = validator_regions.clone();
                                 let res = worker.proxy
                                     .execute_transaction_block(tx.clone().into())
                                 .then(|res| async move {
@@ -449,14 +1127,26 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
                                             // let auth_sign_info = AuthorityStrongQuorumSignInfo::try_from(&cert.auth_sign_info).unwrap();
                                             // auth_sign_info.authorities(&committee_cloned).for_each(|name| metrics_cloned.validators_in_tx_cert.with_label_values(&[&name.unwrap().to_string()]).inc());
                                             if let Some(sig_info) = effects.quorum_sig() { sig_info.authorities(&committee_cloned).for_each(|name| metrics_cloned.validators_in_effects_cert.with_label_values(&[&name.unwrap().to_string()]).inc()) }
+                                            let semantic_failure = !payload.expects_rejection() && verify_sampled_effects(verify_effects_rate, &*payload, &effects);
+                                            let shared_object_conflict = payload.is_shared_object_conflict(&effects);
+                                            let quorum_regions = quorum_regions(&validator_regions, &committee_cloned, &effects);
                                             payload.make_new_payload(&effects);
+                                            if payload.expects_rejection() {
+                                                error!("Invalid transaction was accepted: {:?}", tx.digest());
+                                                return NextOp::Invalid { latency, rejected: false, payload };
+                                            }
                                             let num_commands = tx.data().transaction_data().kind().num_commands() as u16;
-                                            NextOp::Response {latency,num_commands,payload, gas_used: effects.gas_used() }
+                                            NextOp::Response {latency,num_commands,payload, gas_used: effects.gas_used(), semantic_failure, shared_object_conflict, quorum_regions, digest: *tx.digest(), submitted: *start }
                                         }
                                         Err(err) => {
+                                            if payload.expects_rejection() {
+                                                debug!("Invalid transaction rejected: {}", err);
+                                                metrics_cloned.num_in_flight.with_label_values(&[&payload.to_string()]).dec();
+                                                return NextOp::Invalid { latency: start.elapsed(), rejected: true, payload };
+                                            }
                                             error!("Retry due to error: {}", err);
                                             metrics_cloned.num_error.with_label_values(&[&payload.to_string()]).inc();
-                                            NextOp::Retry(Box::new((tx, payload)))
+                                            NextOp::Retry { retry: Box::new((tx, payload)), category: error_category(&err), error: err.to_string(), latency: start.elapsed() }
                                         }
                                     }
                                 });
@@ -465,48 +1155,149 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
                         }
                         Some(op) = futures.next() => {
                             match op {
-                                NextOp::Retry(b) => {
+                                NextOp::Retry { retry: b, category, error, latency } => {
+                                    if fail_fast {
+                                        let _ = first_error.set(format!("Transaction {} of {workload_name} failed: {error}", b.0.digest()));
+                                        cloned_drain_token.cancel();
+                                    }
+                                    TxRecord::send(&record_tx_cloned, start_time, latency, workload_name, &category);
+                                    num_error_txes += 1;
+                                    *errors_by_category.entry(category).or_default() += 1;
+                                    // Timeouts are the first sign of overload, so failures count
+                                    // towards the latency backpressure is measured on
+                                    backpressure_latency_histogram.saturating_record(latency.as_millis().try_into().unwrap());
                                     retry_queue.push_back(b);
-                                    BenchDriver::update_progress(*start_time, run_duration, total_gas_used, progress_cloned.clone());
-                                    if progress_cloned.is_finished() {
-                                        break;
+                                    // Only successful transactions count towards `Interval::Count`
+                                    if !matches!(run_duration, Interval::Count(_)) {
+                                        BenchDriver::update_progress(*start_time, run_duration, total_gas_used, progress_cloned.clone());
+                                    }
+                                }
+                                NextOp::Invalid { latency, rejected, payload } => {
+                                    num_in_flight -= 1;
+                                    free_pool.push(payload);
+                                    if rejected {
+                                        TxRecord::send(&record_tx_cloned, start_time, latency, workload_name, "rejected");
+                                        num_rejected_txes += 1;
+                                    } else {
+                                        if fail_fast {
+                                            let _ = first_error.set(format!("Invalid transaction of {workload_name} was accepted"));
+                                            cloned_drain_token.cancel();
+                                        }
+                                        TxRecord::send(&record_tx_cloned, start_time, latency, workload_name, INVALID_ACCEPTED);
+                                        num_error_txes += 1;
+                                        *errors_by_category.entry(INVALID_ACCEPTED.to_string()).or_default() += 1;
+                                    }
+                                    // Only successful transactions count towards `Interval::Count`
+                                    if !matches!(run_duration, Interval::Count(_)) {
+                                        BenchDriver::update_progress(*start_time, run_duration, total_gas_used, progress_cloned.clone());
+                                    }
+                                }
+                                NextOp::Query { latency, success, payload } => {
+                                    num_in_flight -= 1;
+                                    free_pool.push(payload);
+                                    if success {
+                                        TxRecord::send(&record_tx_cloned, start_time, latency, workload_name, "success");
+                                        num_success_reads += 1;
+                                        read_latency_histogram.saturating_record(latency.as_millis().try_into().unwrap());
+                                    } else {
+                                        TxRecord::send(&record_tx_cloned, start_time, latency, workload_name, "error");
+                                        num_error_reads += 1;
+                                    }
+                                    // Only successful transactions count towards `Interval::Count`
+                                    if !matches!(run_duration, Interval::Count(_)) {
+                                        BenchDriver::update_progress(*start_time, run_duration, total_gas_used, progress_cloned.clone());
                                     }
                                 }
-                                NextOp::Response { latency, num_commands, payload, gas_used } => {
+                                NextOp::Response { latency, num_commands, payload, gas_used, semantic_failure, shared_object_conflict, quorum_regions, digest, submitted } => {
+                                    TxRecord::send(&record_tx_cloned, start_time, latency, workload_name, "success");
                                     num_success_txes += 1;
+                                    if semantic_failure {
+                                        metrics_cloned.num_semantic_failures.with_label_values(&[&payload.to_string()]).inc();
+                                        num_semantic_failures += 1;
+                                    }
+                                    if shared_object_conflict {
+                                        metrics_cloned.shared_object_conflicts.with_label_values(&[&payload.to_string()]).inc();
+                                        shared_object_conflicts += 1;
+                                    }
                                     num_success_cmds += num_commands as u64;
                                     num_in_flight -= 1;
                                     total_gas_used += gas_used;
+                                    gas_histogram.saturating_record(gas_used);
                                     free_pool.push(payload);
                                     latency_histogram.saturating_record(latency.as_millis().try_into().unwrap());
-                                    BenchDriver::update_progress(*start_time, run_duration, total_gas_used, progress_cloned.clone());
-                                    if progress_cloned.is_finished() {
-                                        break;
+                                    for region in quorum_regions {
+                                        latency_ms_by_region.entry(region).or_insert_with(HistogramWrapper::latency_ms).histogram.saturating_record(latency.as_millis().try_into().unwrap());
+                                    }
+                                    backpressure_latency_histogram.saturating_record(latency.as_millis().try_into().unwrap());
+                                    if track_finality {
+                                        finality_polls.push(Box::pin(wait_for_finality(worker.proxy.clone(), digest, submitted)));
                                     }
+                                    BenchDriver::update_progress(*start_time, run_duration, total_gas_used, progress_cloned.clone());
                                 }
                             }
+                            if !draining && progress_cloned.is_finished() {
+                                draining = true;
+                                drain_end.as_mut().reset(Instant::now() + drain_timeout);
+                            }
+                            if draining && futures.is_empty() && finality_polls.is_empty() {
+                                break;
+                            }
+                        }
+                        Some(finality) = finality_polls.next() => {
+                            match finality {
+                                Some(latency) => finality_histogram.saturating_record(latency.as_millis().try_into().unwrap()),
+                                None => num_finality_timeouts += 1,
+                            }
+                            if draining && futures.is_empty() && finality_polls.is_empty() {
+                                break;
+                            }
                         }
                     }
                 }
                 // send stats one last time
-                if tx_cloned
-                    .try_send(Stats {
-                        id: i,
-                        num_no_gas,
-                        num_in_flight,
-                        num_submitted,
-                        bench_stats: BenchmarkStats {
-                            duration: stat_start_time.elapsed(),
-                            num_error_txes,
-                            num_success_txes,
-                            num_success_cmds,
-                            total_gas_used,
-                            latency_ms: HistogramWrapper {
-                                histogram: latency_histogram,
+                if warmed_up
+                    && tx_cloned
+                        .try_send(Stats {
+                            id: i,
+                            workload: workload_name,
+                            num_no_gas,
+                            num_in_flight,
+                            num_submitted,
+                            bench_stats: BenchmarkStats {
+                                duration: stat_start_time.elapsed(),
+                                num_error_txes,
+                                num_success_txes,
+                                num_success_cmds,
+                                num_rejected_txes,
+                                num_semantic_failures,
+                                shared_object_conflicts,
+                                num_abandoned_txes,
+                                num_submitted_txes: num_submitted,
+                                total_gas_used,
+                                latency_ms: HistogramWrapper {
+                                    histogram: latency_histogram,
+                                },
+                                gas_used_per_tx: HistogramWrapper {
+                                    histogram: gas_histogram,
+                                },
+                                txn_bytes: HistogramWrapper {
+                                    histogram: txn_bytes_histogram,
+                                },
+                                errors_by_category,
+                                num_success_reads,
+                                num_error_reads,
+                                read_latency_ms: HistogramWrapper {
+                                    histogram: read_latency_histogram,
+                                },
+                                finality_latency_ms: HistogramWrapper {
+                                    histogram: finality_histogram,
+                                },
+                                num_finality_timeouts,
+                                latency_ms_by_region,
+                                ..BenchmarkStats::empty()
                             },
-                        },
-                    })
-                    .is_err()
+                        })
+                        .is_err()
                 {
                     debug!("Failed to update stat!");
                 }
@@ -514,17 +1305,13 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
             tasks.push(runner);
         }
 
+        let ramp = self.ramp.clone();
+        let schedule = self.schedule.clone();
+        let stat_progress = progress.clone();
+        // Reconfiguration is observed by every proxy, any of them tells the current epoch
+        let epoch_proxy = proxies[0].clone();
         let benchmark_stat_task = tokio::spawn(async move {
-            let mut benchmark_stat = BenchmarkStats {
-                duration: Duration::ZERO,
-                num_error_txes: 0,
-                num_success_txes: 0,
-                num_success_cmds: 0,
-                total_gas_used: 0,
-                latency_ms: HistogramWrapper {
-                    histogram: hdrhistogram::Histogram::<u64>::new_with_max(120_000, 3).unwrap(),
-                },
-            };
+            let mut benchmark_stat = BenchmarkStats::empty();
             let mut stat_collection: BTreeMap<usize, Stats> = BTreeMap::new();
             let mut counter = 0;
             let mut stat;
@@ -532,6 +1319,7 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
             while let Some(
                 sample_stat @ Stats {
                     id,
+                    workload,
                     num_no_gas: _,
                     num_in_flight: _,
                     num_submitted: _,
@@ -539,15 +1327,46 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
                 },
             ) = rx.recv().await
             {
-                benchmark_stat.update(start.elapsed(), &sample_stat.bench_stats);
+                benchmark_stat.update(
+                    start.elapsed().saturating_sub(warmup),
+                    &sample_stat.bench_stats,
+                );
+                benchmark_stat.update_workload(workload, &sample_stat.bench_stats);
+                benchmark_stat.update_epoch(
+                    epoch_proxy.get_current_epoch(),
+                    start.elapsed().saturating_sub(warmup),
+                    &sample_stat.bench_stats,
+                );
+                if let Some(schedule) = &schedule {
+                    let (cycle, phase) = schedule.phase_at(start.elapsed());
+                    benchmark_stat.update_phase(
+                        cycle,
+                        phase,
+                        start.elapsed().saturating_sub(warmup),
+                        &sample_stat.bench_stats,
+                    );
+                }
                 stat_collection.insert(id, sample_stat);
                 let mut total_qps: f32 = 0.0;
+                let mut total_offered_qps: f32 = 0.0;
                 let mut total_cps: f32 = 0.0;
                 let mut num_success_txes: u64 = 0;
                 let mut num_error_txes: u64 = 0;
+                let mut num_rejected_txes: u64 = 0;
+                let mut num_semantic_failures: u64 = 0;
+                let mut shared_object_conflicts: u64 = 0;
+                let mut errors_by_category: BTreeMap<String, u64> = BTreeMap::new();
                 let mut num_success_cmds = 0;
                 let mut latency_histogram =
                     hdrhistogram::Histogram::<u64>::new_with_max(120_000, 3).unwrap();
+                let mut gas_histogram = hdrhistogram::Histogram::<u64>::new(3).unwrap();
+                let mut num_success_reads: u64 = 0;
+                let mut num_error_reads: u64 = 0;
+                let mut read_latency_histogram =
+                    hdrhistogram::Histogram::<u64>::new_with_max(120_000, 3).unwrap();
+                let mut finality_histogram =
+                    hdrhistogram::Histogram::<u64>::new_with_max(120_000, 3).unwrap();
+                let mut num_finality_timeouts: u64 = 0;
 
                 let mut num_in_flight: u64 = 0;
                 let mut num_submitted: u64 = 0;
@@ -555,9 +1374,16 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
                 for (_, v) in stat_collection.iter() {
                     let duration = v.bench_stats.duration.as_secs() as f32;
                     total_qps += v.bench_stats.num_success_txes as f32 / duration;
+                    total_offered_qps += v.bench_stats.num_submitted_txes as f32 / duration;
                     total_cps += v.bench_stats.num_success_cmds as f32 / duration;
                     num_success_txes += v.bench_stats.num_success_txes;
                     num_error_txes += v.bench_stats.num_error_txes;
+                    num_rejected_txes += v.bench_stats.num_rejected_txes;
+                    num_semantic_failures += v.bench_stats.num_semantic_failures;
+                    shared_object_conflicts += v.bench_stats.shared_object_conflicts;
+                    for (category, count) in &v.bench_stats.errors_by_category {
+                        *errors_by_category.entry(category.clone()).or_default() += count;
+                    }
                     num_success_cmds += v.bench_stats.num_success_cmds;
                     num_no_gas += v.num_no_gas;
                     num_submitted += v.num_submitted;
@@ -565,6 +1391,18 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
                     latency_histogram
                         .add(&v.bench_stats.latency_ms.histogram)
                         .unwrap();
+                    gas_histogram
+                        .add(&v.bench_stats.gas_used_per_tx.histogram)
+                        .unwrap();
+                    num_success_reads += v.bench_stats.num_success_reads;
+                    num_error_reads += v.bench_stats.num_error_reads;
+                    read_latency_histogram
+                        .add(&v.bench_stats.read_latency_ms.histogram)
+                        .unwrap();
+                    finality_histogram
+                        .add(&v.bench_stats.finality_latency_ms.histogram)
+                        .unwrap();
+                    num_finality_timeouts += v.bench_stats.num_finality_timeouts;
                 }
                 let denom = num_success_txes + num_error_txes;
                 let _error_rate = if denom > 0 {
@@ -574,12 +1412,66 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
                 };
                 counter += 1;
                 if counter % num_workers == 0 {
-                    stat = format!("TPS = {}, CPS = {}, latency_ms(min/p50/p99/max) = {}/{}/{}/{}, num_success_tx = {}, num_error_tx = {}, num_success_cmds = {}, no_gas = {}, submitted = {}, in_flight = {}", total_qps, total_cps, latency_histogram.min(), latency_histogram.value_at_quantile(0.5), latency_histogram.value_at_quantile(0.99), latency_histogram.max(), num_success_txes, num_error_txes, num_success_cmds, num_no_gas, num_submitted, num_in_flight);
+                    if let Some(ramp) = &ramp {
+                        let elapsed = start.elapsed();
+                        benchmark_stat.qps_samples.push(QpsLatencySample {
+                            elapsed,
+                            target_qps: ramp.qps_at(elapsed),
+                            tps: total_qps,
+                            latency_p50_ms: latency_histogram.value_at_quantile(0.5),
+                            latency_p99_ms: latency_histogram.value_at_quantile(0.99),
+                        });
+                    }
+                    stat = format!("Offered QPS = {}, TPS = {}, CPS = {}, latency_ms(min/p50/p95/p99/max) = {}/{}/{}/{}/{}, num_success_tx = {}, num_error_tx = {}, num_success_cmds = {}, no_gas = {}, submitted = {}, in_flight = {}", total_offered_qps, total_qps, total_cps, latency_histogram.min(), latency_histogram.value_at_quantile(0.5), latency_histogram.value_at_quantile(0.95), latency_histogram.value_at_quantile(0.99), latency_histogram.max(), num_success_txes, num_error_txes, num_success_cmds, num_no_gas, num_submitted, num_in_flight);
+                    if !errors_by_category.is_empty() {
+                        stat = format!("{stat}, errors = {errors_by_category:?}");
+                    }
+                    if !gas_histogram.is_empty() {
+                        stat = format!(
+                            "{stat}, gas_per_tx(mean/p50/p99) = {:.0}/{}/{}",
+                            gas_histogram.mean(),
+                            gas_histogram.value_at_quantile(0.5),
+                            gas_histogram.value_at_quantile(0.99)
+                        );
+                    }
+                    if num_rejected_txes > 0 {
+                        stat = format!("{stat}, num_rejected_tx = {num_rejected_txes}");
+                    }
+                    if num_semantic_failures > 0 {
+                        stat = format!("{stat}, num_semantic_failures = {num_semantic_failures}");
+                    }
+                    if shared_object_conflicts > 0 {
+                        stat =
+                            format!("{stat}, shared_object_conflicts = {shared_object_conflicts}");
+                    }
+                    if num_success_reads + num_error_reads > 0 {
+                        stat = format!("{stat}, num_success_reads = {}, num_error_reads = {}, read_latency_ms(p50/p99) = {}/{}", num_success_reads, num_error_reads, read_latency_histogram.value_at_quantile(0.5), read_latency_histogram.value_at_quantile(0.99));
+                    }
+                    if !finality_histogram.is_empty() || num_finality_timeouts > 0 {
+                        stat = format!(
+                            "{stat}, finality_latency_ms(p50/p99) = {}/{}, finality_timeouts = {}",
+                            finality_histogram.value_at_quantile(0.5),
+                            finality_histogram.value_at_quantile(0.99),
+                            num_finality_timeouts
+                        );
+                    }
                     if show_progress {
-                        eprintln!("{}", stat);
+                        // Keep the stats from being drawn over by the spinner
+                        stat_progress.suspend(|| eprintln!("{}", stat));
                     }
                 }
             }
+            // Every worker has returned, stop the spinner of an unbounded run
+            stat_progress.finish_and_clear();
+            if show_progress {
+                eprintln!(
+                    "Overall latency_ms(p50/p95/p99/max) = {}/{}/{}/{}",
+                    benchmark_stat.p50(),
+                    benchmark_stat.p95(),
+                    benchmark_stat.p99(),
+                    benchmark_stat.max()
+                );
+            }
             benchmark_stat
         });
         drop(tx);
@@ -627,15 +1519,34 @@ impl Driver<(BenchmarkStats, StressStats)> for BenchDriver {
         });
 
         let all_tasks = try_join_all(tasks);
+        tokio::pin!(all_tasks);
         let _res = tokio::select! {
             _ = ctrl_c() => {
-                self.terminate();
-                vec![]
+                info!("Received ctrl-c, waiting for in flight transactions. Press ctrl-c again to exit immediately");
+                self.drain();
+                tokio::select! {
+                    _ = ctrl_c() => {
+                        // Conventional exit status of a process interrupted by SIGINT
+                        std::process::exit(130);
+                    }
+                    res = &mut all_tasks => res.unwrap().into_iter().collect()
+                }
             }
-            res = all_tasks => res.unwrap().into_iter().collect()
+            res = &mut all_tasks => res.unwrap().into_iter().collect()
         };
+        drop(record_txs);
+        for record_task in record_tasks {
+            record_task.await?;
+        }
         let benchmark_stat = benchmark_stat_task.await.unwrap();
         let stress_stat = stress_stat_task.await.unwrap();
+        if let Some(error) = first_error.get() {
+            return Err(BenchmarkAborted {
+                error: error.clone(),
+                stats: benchmark_stat,
+            }
+            .into());
+        }
         Ok((benchmark_stat, stress_stat))
     }
 }
@@ -2,7 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use duration_str::parse;
-use std::{str::FromStr, time::Duration};
+use std::{
+    collections::BTreeMap,
+    fs::OpenOptions,
+    io::Write,
+    path::Path,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 pub mod bench_driver;
 pub mod driver;
@@ -11,13 +18,18 @@ use hdrhistogram::{serialization::Serializer, Histogram};
 
 #[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub enum Interval {
+    /// Run until this many transactions have been executed successfully, 0 for unbounded
     Count(u64),
+    /// Run for this long, `Duration::MAX` for unbounded
     Time(tokio::time::Duration),
 }
 
 impl Interval {
     pub fn is_unbounded(&self) -> bool {
-        matches!(self, Interval::Time(tokio::time::Duration::MAX))
+        matches!(
+            self,
+            Interval::Count(0) | Interval::Time(tokio::time::Duration::MAX)
+        )
     }
 }
 
@@ -37,6 +49,118 @@ impl FromStr for Interval {
     }
 }
 
+/// Offered load over the course of a benchmark, as `(time since start, total QPS)` points. The QPS
+/// is interpolated linearly between points, and held at the first and last points outside them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RampSchedule {
+    points: Vec<(Duration, u32)>,
+}
+
+impl RampSchedule {
+    pub fn new(mut points: Vec<(Duration, u32)>) -> Result<Self, String> {
+        if points.is_empty() {
+            return Err("Ramp schedule needs at least one point".to_string());
+        }
+        points.sort_by_key(|(time, _)| *time);
+        if points.windows(2).any(|w| w[0].0 == w[1].0) {
+            return Err("Ramp schedule has more than one point at the same time".to_string());
+        }
+        Ok(Self { points })
+    }
+
+    /// The QPS the benchmark should offer `elapsed` after it started
+    pub fn qps_at(&self, elapsed: Duration) -> u32 {
+        let next = self.points.partition_point(|(time, _)| *time <= elapsed);
+        if next == 0 {
+            return self.points[0].1;
+        }
+        if next == self.points.len() {
+            return self.points[next - 1].1;
+        }
+        let (start, from) = self.points[next - 1];
+        let (end, to) = self.points[next];
+        let progress = (elapsed - start).as_secs_f64() / (end - start).as_secs_f64();
+        (from as f64 + (to as f64 - from as f64) * progress).round() as u32
+    }
+}
+
+impl FromStr for RampSchedule {
+    type Err = String;
+
+    /// Parses a comma separated list of `<duration>:<qps>` points, e.g. "0s:100,1m:1000"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let points = s
+            .split(',')
+            .map(|point| {
+                let (time, qps) = point
+                    .split_once(':')
+                    .ok_or_else(|| format!("Expected <duration>:<qps>, got {point:?}"))?;
+                let time = parse(time.trim()).map_err(|e| e.to_string())?;
+                let qps = qps
+                    .trim()
+                    .parse()
+                    .map_err(|e| format!("Invalid qps {qps:?}: {e}"))?;
+                Ok((time, qps))
+            })
+            .collect::<Result<_, String>>()?;
+        RampSchedule::new(points)
+    }
+}
+
+/// Fraction of the rate kept when the latency is above target
+const BACKPRESSURE_DECREASE_FACTOR: f64 = 0.5;
+/// Fraction of the ceiling added to the rate when the latency is within target
+const BACKPRESSURE_INCREASE_FRACTION: f64 = 0.05;
+
+/// Adapts the rate a worker offers to keep the latency of its transactions near a target, so the
+/// benchmark runs close to the knee of the cluster instead of overrunning it. The rate is cut
+/// multiplicatively while the latency is above target and grows additively while it is within,
+/// never above the ceiling the worker would offer open-loop.
+#[derive(Debug, Clone)]
+pub struct AimdRateController {
+    target_latency: Duration,
+    qps: f64,
+}
+
+impl AimdRateController {
+    pub fn new(target_latency: Duration, initial_qps: u64) -> Self {
+        Self {
+            target_latency,
+            qps: initial_qps.max(1) as f64,
+        }
+    }
+
+    /// The QPS to offer next, given the `latency` observed since the last update and the
+    /// `ceiling` on the rate. The rate is held when no latency was observed.
+    pub fn update(&mut self, ceiling: u64, latency: Option<Duration>) -> u64 {
+        let ceiling = ceiling.max(1) as f64;
+        match latency {
+            Some(latency) if latency > self.target_latency => {
+                self.qps *= BACKPRESSURE_DECREASE_FACTOR;
+            }
+            Some(_) => {
+                self.qps += (ceiling * BACKPRESSURE_INCREASE_FRACTION).max(1.0);
+            }
+            None => {}
+        }
+        self.qps = self.qps.clamp(1.0, ceiling);
+        self.qps.round() as u64
+    }
+}
+
+/// Throughput and latency of the benchmark at one point of a `RampSchedule`.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct QpsLatencySample {
+    /// Time since the start of the benchmark
+    pub elapsed: Duration,
+    /// QPS the schedule offered at `elapsed`
+    pub target_qps: u32,
+    /// Transactions per second that were executed successfully over the last stat interval
+    pub tps: f32,
+    pub latency_p50_ms: u64,
+    pub latency_p99_ms: u64,
+}
+
 // wrapper which implements serde
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -44,6 +168,27 @@ pub struct HistogramWrapper {
     histogram: Histogram<u64>,
 }
 
+impl HistogramWrapper {
+    /// An empty histogram of latencies in milliseconds
+    fn latency_ms() -> Self {
+        HistogramWrapper {
+            histogram: Histogram::new_with_max(120_000, 3).unwrap(),
+        }
+    }
+    /// An empty histogram of gas used per transaction in MIST, which grows to fit the values
+    fn gas() -> Self {
+        HistogramWrapper {
+            histogram: Histogram::new(3).unwrap(),
+        }
+    }
+    /// An empty histogram of serialized transaction sizes in bytes, which grows to fit the values
+    fn txn_bytes() -> Self {
+        HistogramWrapper {
+            histogram: Histogram::new(3).unwrap(),
+        }
+    }
+}
+
 impl serde::Serialize for HistogramWrapper {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         let mut vec = Vec::new();
@@ -94,6 +239,121 @@ impl StressStats {
     }
 }
 
+/// Transaction stats of a single workload of the benchmark.
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct WorkloadStats {
+    pub num_error_txes: u64,
+    pub num_success_txes: u64,
+    /// See `BenchmarkStats::shared_object_conflicts`
+    #[serde(default)]
+    pub shared_object_conflicts: u64,
+    pub latency_ms: HistogramWrapper,
+    /// See `BenchmarkStats::txn_bytes`
+    #[serde(default = "HistogramWrapper::txn_bytes")]
+    pub txn_bytes: HistogramWrapper,
+}
+
+impl WorkloadStats {
+    fn new() -> Self {
+        WorkloadStats {
+            num_error_txes: 0,
+            num_success_txes: 0,
+            shared_object_conflicts: 0,
+            latency_ms: HistogramWrapper::latency_ms(),
+            txn_bytes: HistogramWrapper::txn_bytes(),
+        }
+    }
+
+    fn add(
+        &mut self,
+        num_error_txes: u64,
+        num_success_txes: u64,
+        shared_object_conflicts: u64,
+        latency_ms: &Histogram<u64>,
+        txn_bytes: &Histogram<u64>,
+    ) {
+        self.num_error_txes += num_error_txes;
+        self.num_success_txes += num_success_txes;
+        self.shared_object_conflicts += shared_object_conflicts;
+        self.latency_ms.histogram.add(latency_ms).unwrap();
+        self.txn_bytes.histogram.add(txn_bytes).unwrap();
+    }
+}
+
+/// Transaction stats of the part of the benchmark run in a single epoch.
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct EpochStats {
+    pub epoch: u64,
+    /// Time from the start of the benchmark to the first stats collected in the epoch
+    pub start: Duration,
+    /// Time from `start` to the last stats collected in the epoch
+    pub duration: Duration,
+    pub num_error_txes: u64,
+    pub num_success_txes: u64,
+    pub latency_ms: HistogramWrapper,
+}
+
+impl EpochStats {
+    fn new(epoch: u64, start: Duration) -> Self {
+        EpochStats {
+            epoch,
+            start,
+            duration: Duration::ZERO,
+            num_error_txes: 0,
+            num_success_txes: 0,
+            latency_ms: HistogramWrapper::latency_ms(),
+        }
+    }
+}
+
+/// Transaction stats of one phase of a `WorkloadSchedule`, over a single pass of the schedule.
+///
+/// [`WorkloadSchedule`]: crate::workloads::workload_configuration::WorkloadSchedule
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+pub struct PhaseStats {
+    /// Number of passes over the whole schedule before this one
+    pub cycle: u64,
+    /// Index of the phase in the schedule
+    pub phase: usize,
+    /// Time from the start of the benchmark to the first stats collected in the phase
+    pub start: Duration,
+    /// Time from `start` to the last stats collected in the phase
+    pub duration: Duration,
+    pub num_error_txes: u64,
+    pub num_success_txes: u64,
+    pub latency_ms: HistogramWrapper,
+}
+
+impl PhaseStats {
+    fn new(cycle: u64, phase: usize, start: Duration) -> Self {
+        PhaseStats {
+            cycle,
+            phase,
+            start,
+            duration: Duration::ZERO,
+            num_error_txes: 0,
+            num_success_txes: 0,
+            latency_ms: HistogramWrapper::latency_ms(),
+        }
+    }
+}
+
+/// Version of the format written by `BenchmarkStats::to_json`. Bump it on any incompatible
+/// change to the serialized fields of `BenchmarkStats`.
+pub const BENCHMARK_STATS_JSON_VERSION: u32 = 1;
+
+/// Columns of the rows written by `BenchmarkStats::append_summary_csv`. Only append new columns
+/// at the end, so that files written by older versions keep lining up.
+pub const BENCHMARK_SUMMARY_CSV_HEADER: &str = "timestamp,duration_s,offered_qps,tps,num_txn,\
+    num_success,num_error,num_rejected,num_abandoned,p50_ms,p95_ms,p99_ms,max_ms,mean_gas,\
+    mean_txn_bytes";
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct VersionedBenchmarkStats<S> {
+    version: u32,
+    stats: S,
+}
+
 /// Stores the final statistics of the test run.
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 pub struct BenchmarkStats {
@@ -104,22 +364,420 @@ pub struct BenchmarkStats {
     pub num_success_txes: u64,
     /// Total number of commands in transactions that executed successfully
     pub num_success_cmds: u64,
+    /// Number of transactions made invalid on purpose that were rejected as expected, these are
+    /// not errors
+    #[serde(default)]
+    pub num_rejected_txes: u64,
+    /// Number of successful transactions, out of those sampled for effects verification, whose
+    /// effects did not match what they should have done. These are still counted as successes
+    #[serde(default)]
+    pub num_semantic_failures: u64,
+    /// Number of successful transactions which found a shared object written by another
+    /// transaction since their payload last wrote it, i.e. whose read-modify-write was ordered
+    /// behind a competing one. Measures the cost of contention on shared objects
+    #[serde(default)]
+    pub shared_object_conflicts: u64,
+    /// Number of transactions and queries sent, retries included. Differs from the target load
+    /// when the workers run out of payloads or back off with `BenchDriver::with_backpressure`
+    #[serde(default)]
+    pub num_submitted_txes: u64,
+    /// Number of transactions and queries still in flight when the drain timeout at the end of
+    /// the run passed. Their outcome is unknown so they are neither successes nor errors
+    #[serde(default)]
+    pub num_abandoned_txes: u64,
     /// Total gas used
     pub total_gas_used: u64,
     pub latency_ms: HistogramWrapper,
+    /// Gas used by each transaction that executed successfully, in MIST
+    #[serde(default = "HistogramWrapper::gas")]
+    pub gas_used_per_tx: HistogramWrapper,
+    /// Size in bytes of the BCS serialization of every transaction sent, signatures and retries
+    /// included
+    #[serde(default = "HistogramWrapper::txn_bytes")]
+    pub txn_bytes: HistogramWrapper,
+    /// Number of read-only queries that succeeded
+    #[serde(default)]
+    pub num_success_reads: u64,
+    /// Number of read-only queries that failed
+    #[serde(default)]
+    pub num_error_reads: u64,
+    /// Latency of successful read-only queries, kept apart from the latency of transactions
+    #[serde(default = "HistogramWrapper::latency_ms")]
+    pub read_latency_ms: HistogramWrapper,
+    /// Time from submitting a successful transaction to finding it in a checkpoint, only
+    /// collected with `BenchDriver::with_finality_tracking`. Unlike `latency_ms`, which ends at
+    /// the effects certificate, it includes the time taken to build a checkpoint
+    #[serde(default = "HistogramWrapper::latency_ms")]
+    pub finality_latency_ms: HistogramWrapper,
+    /// Number of successful transactions tracked for finality which were not found in a
+    /// checkpoint in time, or whose checkpoint could not be looked up
+    #[serde(default)]
+    pub num_finality_timeouts: u64,
+    /// Number of errors in `num_error_txes` by category, see `crate::error_category`
+    #[serde(default)]
+    pub errors_by_category: BTreeMap<String, u64>,
+    /// Samples taken while following a `RampSchedule`, empty for a constant QPS run
+    #[serde(default)]
+    pub qps_samples: Vec<QpsLatencySample>,
+    /// Transaction stats broken down by the name of the workload which generated them
+    #[serde(default)]
+    pub stats_by_workload: BTreeMap<String, WorkloadStats>,
+    /// Transaction stats of each epoch the benchmark ran in, in epoch order
+    #[serde(default)]
+    pub stats_by_epoch: Vec<EpochStats>,
+    /// Transaction stats of each phase run when following a `WorkloadSchedule`, in the order
+    /// they ran, empty otherwise
+    #[serde(default)]
+    pub stats_by_phase: Vec<PhaseStats>,
+    /// Latency of successful transactions by the region of the validators in their effects
+    /// quorum, see `BenchDriver::with_validator_regions`. A transaction counts towards every
+    /// region with a validator in its quorum
+    #[serde(default)]
+    pub latency_ms_by_region: BTreeMap<String, HistogramWrapper>,
 }
 
 impl BenchmarkStats {
+    /// Stats of a run which has not done anything yet
+    pub fn empty() -> Self {
+        BenchmarkStats {
+            duration: Duration::ZERO,
+            num_error_txes: 0,
+            num_success_txes: 0,
+            num_success_cmds: 0,
+            num_rejected_txes: 0,
+            num_semantic_failures: 0,
+            shared_object_conflicts: 0,
+            num_submitted_txes: 0,
+            num_abandoned_txes: 0,
+            total_gas_used: 0,
+            latency_ms: HistogramWrapper::latency_ms(),
+            gas_used_per_tx: HistogramWrapper::gas(),
+            txn_bytes: HistogramWrapper::txn_bytes(),
+            num_success_reads: 0,
+            num_error_reads: 0,
+            read_latency_ms: HistogramWrapper::latency_ms(),
+            finality_latency_ms: HistogramWrapper::latency_ms(),
+            num_finality_timeouts: 0,
+            errors_by_category: BTreeMap::new(),
+            qps_samples: vec![],
+            stats_by_workload: BTreeMap::new(),
+            stats_by_epoch: vec![],
+            stats_by_phase: vec![],
+            latency_ms_by_region: BTreeMap::new(),
+        }
+    }
     pub fn update(&mut self, duration: Duration, sample_stat: &BenchmarkStats) {
         self.duration = duration;
         self.num_error_txes += sample_stat.num_error_txes;
         self.num_success_txes += sample_stat.num_success_txes;
         self.num_success_cmds += sample_stat.num_success_cmds;
+        self.num_rejected_txes += sample_stat.num_rejected_txes;
+        self.num_semantic_failures += sample_stat.num_semantic_failures;
+        self.shared_object_conflicts += sample_stat.shared_object_conflicts;
+        self.num_abandoned_txes += sample_stat.num_abandoned_txes;
+        self.num_submitted_txes += sample_stat.num_submitted_txes;
         self.total_gas_used += sample_stat.total_gas_used;
         self.latency_ms
             .histogram
             .add(&sample_stat.latency_ms.histogram)
             .unwrap();
+        self.gas_used_per_tx
+            .histogram
+            .add(&sample_stat.gas_used_per_tx.histogram)
+            .unwrap();
+        self.txn_bytes
+            .histogram
+            .add(&sample_stat.txn_bytes.histogram)
+            .unwrap();
+        for (category, count) in &sample_stat.errors_by_category {
+            *self.errors_by_category.entry(category.clone()).or_default() += count;
+        }
+        self.num_success_reads += sample_stat.num_success_reads;
+        self.num_error_reads += sample_stat.num_error_reads;
+        self.read_latency_ms
+            .histogram
+            .add(&sample_stat.read_latency_ms.histogram)
+            .unwrap();
+        self.finality_latency_ms
+            .histogram
+            .add(&sample_stat.finality_latency_ms.histogram)
+            .unwrap();
+        self.num_finality_timeouts += sample_stat.num_finality_timeouts;
+        for (workload, stats) in &sample_stat.stats_by_workload {
+            self.stats_by_workload
+                .entry(workload.clone())
+                .or_insert_with(WorkloadStats::new)
+                .add(
+                    stats.num_error_txes,
+                    stats.num_success_txes,
+                    stats.shared_object_conflicts,
+                    &stats.latency_ms.histogram,
+                    &stats.txn_bytes.histogram,
+                );
+        }
+        for (region, latency_ms) in &sample_stat.latency_ms_by_region {
+            self.latency_ms_by_region
+                .entry(region.clone())
+                .or_insert_with(HistogramWrapper::latency_ms)
+                .histogram
+                .add(&latency_ms.histogram)
+                .unwrap();
+        }
+    }
+    /// Attribute the transactions in `sample_stat` to `workload`, on top of `update`
+    pub fn update_workload(&mut self, workload: &str, sample_stat: &BenchmarkStats) {
+        self.stats_by_workload
+            .entry(workload.to_string())
+            .or_insert_with(WorkloadStats::new)
+            .add(
+                sample_stat.num_error_txes,
+                sample_stat.num_success_txes,
+                sample_stat.shared_object_conflicts,
+                &sample_stat.latency_ms.histogram,
+                &sample_stat.txn_bytes.histogram,
+            );
+    }
+    /// Attribute the transactions in `sample_stat`, collected `elapsed` into the benchmark, to
+    /// `epoch`, on top of `update`. Stats collected in the same epoch must be added consecutively.
+    pub fn update_epoch(&mut self, epoch: u64, elapsed: Duration, sample_stat: &BenchmarkStats) {
+        if self
+            .stats_by_epoch
+            .last()
+            .map_or(true, |stats| stats.epoch != epoch)
+        {
+            self.stats_by_epoch.push(EpochStats::new(epoch, elapsed));
+        }
+        let stats = self.stats_by_epoch.last_mut().unwrap();
+        stats.duration = elapsed.saturating_sub(stats.start);
+        stats.num_error_txes += sample_stat.num_error_txes;
+        stats.num_success_txes += sample_stat.num_success_txes;
+        stats
+            .latency_ms
+            .histogram
+            .add(&sample_stat.latency_ms.histogram)
+            .unwrap();
+    }
+    /// Attribute the transactions in `sample_stat`, collected `elapsed` into the benchmark, to
+    /// `phase` of the `cycle`-th pass over a `WorkloadSchedule`, on top of `update`. Stats
+    /// collected in the same phase must be added consecutively.
+    pub fn update_phase(
+        &mut self,
+        cycle: u64,
+        phase: usize,
+        elapsed: Duration,
+        sample_stat: &BenchmarkStats,
+    ) {
+        if self
+            .stats_by_phase
+            .last()
+            .map_or(true, |stats| (stats.cycle, stats.phase) != (cycle, phase))
+        {
+            self.stats_by_phase
+                .push(PhaseStats::new(cycle, phase, elapsed));
+        }
+        let stats = self.stats_by_phase.last_mut().unwrap();
+        stats.duration = elapsed.saturating_sub(stats.start);
+        stats.num_error_txes += sample_stat.num_error_txes;
+        stats.num_success_txes += sample_stat.num_success_txes;
+        stats
+            .latency_ms
+            .histogram
+            .add(&sample_stat.latency_ms.histogram)
+            .unwrap();
+    }
+    /// Combine the final stats of a benchmark run alongside this one, e.g. by another process
+    /// driving the same network, into stats of the load they offered together. Latency
+    /// histograms are merged rather than their percentiles averaged, the runs overlap so the
+    /// duration is the longest of the two, and the ramp samples of both are kept as they are.
+    pub fn merge(&mut self, other: &BenchmarkStats) {
+        self.update(self.duration.max(other.duration), other);
+        for other_epoch in &other.stats_by_epoch {
+            let index = match self
+                .stats_by_epoch
+                .binary_search_by_key(&other_epoch.epoch, |stats| stats.epoch)
+            {
+                Ok(index) => index,
+                Err(index) => {
+                    self.stats_by_epoch
+                        .insert(index, EpochStats::new(other_epoch.epoch, other_epoch.start));
+                    index
+                }
+            };
+            let stats = &mut self.stats_by_epoch[index];
+            let end = (stats.start + stats.duration).max(other_epoch.start + other_epoch.duration);
+            stats.start = stats.start.min(other_epoch.start);
+            stats.duration = end - stats.start;
+            stats.num_error_txes += other_epoch.num_error_txes;
+            stats.num_success_txes += other_epoch.num_success_txes;
+            stats
+                .latency_ms
+                .histogram
+                .add(&other_epoch.latency_ms.histogram)
+                .unwrap();
+        }
+        for other_phase in &other.stats_by_phase {
+            let key = (other_phase.cycle, other_phase.phase);
+            let index = match self
+                .stats_by_phase
+                .binary_search_by_key(&key, |stats| (stats.cycle, stats.phase))
+            {
+                Ok(index) => index,
+                Err(index) => {
+                    self.stats_by_phase.insert(
+                        index,
+                        PhaseStats::new(other_phase.cycle, other_phase.phase, other_phase.start),
+                    );
+                    index
+                }
+            };
+            let stats = &mut self.stats_by_phase[index];
+            let end = (stats.start + stats.duration).max(other_phase.start + other_phase.duration);
+            stats.start = stats.start.min(other_phase.start);
+            stats.duration = end - stats.start;
+            stats.num_error_txes += other_phase.num_error_txes;
+            stats.num_success_txes += other_phase.num_success_txes;
+            stats
+                .latency_ms
+                .histogram
+                .add(&other_phase.latency_ms.histogram)
+                .unwrap();
+        }
+        self.qps_samples.extend_from_slice(&other.qps_samples);
+        self.qps_samples.sort_by_key(|sample| sample.elapsed);
+    }
+    /// Serialize to JSON, tagged with `BENCHMARK_STATS_JSON_VERSION`
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string(&VersionedBenchmarkStats {
+            version: BENCHMARK_STATS_JSON_VERSION,
+            stats: self,
+        })
+    }
+    /// Deserialize stats written by `to_json`, or by older versions of the benchmark which
+    /// serialized `BenchmarkStats` without a version
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        match serde_json::from_str::<VersionedBenchmarkStats<BenchmarkStats>>(json) {
+            Ok(VersionedBenchmarkStats { version, stats }) => {
+                if version != BENCHMARK_STATS_JSON_VERSION {
+                    anyhow::bail!(
+                        "Unsupported benchmark stats version {version}, expected {BENCHMARK_STATS_JSON_VERSION}"
+                    );
+                }
+                Ok(stats)
+            }
+            Err(_) => Ok(serde_json::from_str(json)?),
+        }
+    }
+    /// Append a single row summarizing the run to the CSV file at `path`, writing
+    /// `BENCHMARK_SUMMARY_CSV_HEADER` first if the file is new or empty, so that the summaries of
+    /// many runs accumulate in one file. The timestamp is the time of the call, in seconds since
+    /// the Unix epoch.
+    pub fn append_summary_csv(&self, path: &Path) -> std::io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        let mut csv = String::new();
+        if file.metadata()?.len() == 0 {
+            csv.push_str(BENCHMARK_SUMMARY_CSV_HEADER);
+            csv.push('\n');
+        }
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        csv.push_str(&format!(
+            "{},{},{:.2},{:.2},{},{},{},{},{},{},{},{},{},{:.0},{:.0}\n",
+            timestamp,
+            self.duration.as_secs_f64(),
+            self.offered_qps(),
+            self.tps(),
+            self.num_success_txes + self.num_error_txes,
+            self.num_success_txes,
+            self.num_error_txes,
+            self.num_rejected_txes,
+            self.num_abandoned_txes,
+            self.p50(),
+            self.p95(),
+            self.p99(),
+            self.max(),
+            self.mean_gas(),
+            self.mean_txn_bytes(),
+        ));
+        // A single write, so that a row is not interleaved with one appended concurrently
+        file.write_all(csv.as_bytes())
+    }
+    /// Median latency of successful transactions, in milliseconds
+    pub fn p50(&self) -> u64 {
+        self.latency_ms.histogram.value_at_quantile(0.5)
+    }
+    /// 95th percentile latency of successful transactions, in milliseconds
+    pub fn p95(&self) -> u64 {
+        self.latency_ms.histogram.value_at_quantile(0.95)
+    }
+    /// 99th percentile latency of successful transactions, in milliseconds
+    pub fn p99(&self) -> u64 {
+        self.latency_ms.histogram.value_at_quantile(0.99)
+    }
+    /// Highest latency of successful transactions, in milliseconds
+    pub fn max(&self) -> u64 {
+        self.latency_ms.histogram.max()
+    }
+    /// Successful transactions per second, over the measured window which excludes any warmup
+    pub fn tps(&self) -> f64 {
+        if self.duration.is_zero() {
+            return 0.0;
+        }
+        self.num_success_txes as f64 / self.duration.as_secs_f64()
+    }
+    /// Transactions and queries sent per second, i.e. the offered load, to compare with the
+    /// achieved `tps`
+    pub fn offered_qps(&self) -> f64 {
+        if self.duration.is_zero() {
+            return 0.0;
+        }
+        self.num_submitted_txes as f64 / self.duration.as_secs_f64()
+    }
+    /// Commands of successful transactions per second
+    pub fn cps(&self) -> f64 {
+        if self.duration.is_zero() {
+            return 0.0;
+        }
+        self.num_success_cmds as f64 / self.duration.as_secs_f64()
+    }
+    /// Gas used by successful transactions per hour at the rate of the run, in MIST
+    pub fn gas_per_hour(&self) -> u64 {
+        if self.duration.is_zero() {
+            return 0;
+        }
+        (self.total_gas_used as f64 * 3600.0 / self.duration.as_secs_f64()) as u64
+    }
+    /// Panics if fewer than `tps` successful transactions per second were measured, so tests can
+    /// catch throughput regressions. The floor is checked over the post-warmup window only.
+    pub fn assert_min_throughput(&self, tps: f64) {
+        assert!(
+            self.tps() >= tps,
+            "Throughput of {:.2} tps over {:?} is below the floor of {:.2} tps",
+            self.tps(),
+            self.duration,
+            tps
+        );
+    }
+    /// Number of successful transactions found in a checkpoint, see `finality_latency_ms`
+    pub fn num_finalized_txes(&self) -> u64 {
+        self.finality_latency_ms.histogram.len()
+    }
+    /// Mean gas used by successful transactions, in MIST
+    pub fn mean_gas(&self) -> f64 {
+        self.gas_used_per_tx.histogram.mean()
+    }
+    /// Total gas used by successful transactions, in MIST
+    pub fn total_gas(&self) -> u64 {
+        self.total_gas_used
+    }
+    /// Mean size of the transactions sent, in bytes
+    pub fn mean_txn_bytes(&self) -> f64 {
+        self.txn_bytes.histogram.mean()
+    }
+    /// Size in bytes under which a `quantile` of the transactions sent are, e.g. 0.99 for the p99
+    pub fn txn_bytes_at_quantile(&self, quantile: f64) -> u64 {
+        self.txn_bytes.histogram.value_at_quantile(quantile)
     }
     pub fn to_table(&self) -> Table {
         let mut table = Table::new();
@@ -128,39 +786,212 @@ impl BenchmarkStats {
             .set_width(200)
             .set_header(vec![
                 "duration(s)",
+                "offered qps",
                 "tps",
                 "cps",
                 "error%",
                 "latency (min)",
                 "latency (p50)",
+                "latency (p95)",
                 "latency (p99)",
+                "latency (max)",
                 "gas used (MIST total)",
                 "gas used/hr (MIST approx.)",
             ]);
         let mut row = Row::new();
         row.add_cell(Cell::new(self.duration.as_secs()));
-        row.add_cell(Cell::new(self.num_success_txes / self.duration.as_secs()));
-        row.add_cell(Cell::new(self.num_success_cmds / self.duration.as_secs()));
+        row.add_cell(Cell::new(format!("{:.2}", self.offered_qps())));
+        row.add_cell(Cell::new(format!("{:.2}", self.tps())));
+        row.add_cell(Cell::new(format!("{:.2}", self.cps())));
         row.add_cell(Cell::new(
             (100 * self.num_error_txes) as f32
                 / (self.num_error_txes + self.num_success_txes) as f32,
         ));
         row.add_cell(Cell::new(self.latency_ms.histogram.min()));
-        row.add_cell(Cell::new(self.latency_ms.histogram.value_at_quantile(0.5)));
-        row.add_cell(Cell::new(self.latency_ms.histogram.value_at_quantile(0.99)));
+        row.add_cell(Cell::new(self.p50()));
+        row.add_cell(Cell::new(self.p95()));
+        row.add_cell(Cell::new(self.p99()));
+        row.add_cell(Cell::new(self.max()));
         row.add_cell(Cell::new(format_num_with_separators(
             self.total_gas_used,
             3,
             ",",
         )));
         row.add_cell(Cell::new(format_num_with_separators(
-            self.total_gas_used * 60 * 60 / self.duration.as_secs(),
+            self.gas_per_hour(),
             3,
             ",",
         )));
         table.add_row(row);
         table
     }
+    /// One row per workload, for runs mixing several workloads
+    pub fn workloads_to_table(&self) -> Table {
+        let mut table = Table::new();
+        table
+            .set_content_arrangement(ContentArrangement::Dynamic)
+            .set_width(200)
+            .set_header(vec![
+                "workload",
+                "tps",
+                "num success",
+                "num error",
+                "error%",
+                "shared obj conflicts",
+                "latency (p50)",
+                "latency (p99)",
+                "tx size (mean bytes)",
+                "tx size (p99 bytes)",
+            ]);
+        for (workload, stats) in &self.stats_by_workload {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(workload));
+            row.add_cell(Cell::new(
+                stats.num_success_txes / self.duration.as_secs().max(1),
+            ));
+            row.add_cell(Cell::new(stats.num_success_txes));
+            row.add_cell(Cell::new(stats.num_error_txes));
+            row.add_cell(Cell::new(
+                (100 * stats.num_error_txes) as f32
+                    / (stats.num_error_txes + stats.num_success_txes).max(1) as f32,
+            ));
+            row.add_cell(Cell::new(stats.shared_object_conflicts));
+            row.add_cell(Cell::new(stats.latency_ms.histogram.value_at_quantile(0.5)));
+            row.add_cell(Cell::new(
+                stats.latency_ms.histogram.value_at_quantile(0.99),
+            ));
+            row.add_cell(Cell::new(format!(
+                "{:.0}",
+                stats.txn_bytes.histogram.mean()
+            )));
+            row.add_cell(Cell::new(stats.txn_bytes.histogram.value_at_quantile(0.99)));
+            table.add_row(row);
+        }
+        table
+    }
+    /// One row per epoch, to show how throughput and latency change around reconfiguration
+    pub fn epochs_to_table(&self) -> Table {
+        let mut table = Table::new();
+        table
+            .set_content_arrangement(ContentArrangement::Dynamic)
+            .set_width(200)
+            .set_header(vec![
+                "epoch",
+                "start(s)",
+                "duration(s)",
+                "tps",
+                "num success",
+                "num error",
+                "latency (p50)",
+                "latency (p99)",
+            ]);
+        for stats in &self.stats_by_epoch {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(stats.epoch));
+            row.add_cell(Cell::new(stats.start.as_secs()));
+            row.add_cell(Cell::new(stats.duration.as_secs()));
+            row.add_cell(Cell::new(
+                stats.num_success_txes / stats.duration.as_secs().max(1),
+            ));
+            row.add_cell(Cell::new(stats.num_success_txes));
+            row.add_cell(Cell::new(stats.num_error_txes));
+            row.add_cell(Cell::new(stats.latency_ms.histogram.value_at_quantile(0.5)));
+            row.add_cell(Cell::new(
+                stats.latency_ms.histogram.value_at_quantile(0.99),
+            ));
+            table.add_row(row);
+        }
+        table
+    }
+    /// One row per phase run of a `WorkloadSchedule`, to compare the traffic mixes of a soak test
+    pub fn phases_to_table(&self) -> Table {
+        let mut table = Table::new();
+        table
+            .set_content_arrangement(ContentArrangement::Dynamic)
+            .set_width(200)
+            .set_header(vec![
+                "cycle",
+                "phase",
+                "start(s)",
+                "duration(s)",
+                "tps",
+                "num success",
+                "num error",
+                "latency (p50)",
+                "latency (p99)",
+            ]);
+        for stats in &self.stats_by_phase {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(stats.cycle));
+            row.add_cell(Cell::new(stats.phase));
+            row.add_cell(Cell::new(stats.start.as_secs()));
+            row.add_cell(Cell::new(stats.duration.as_secs()));
+            row.add_cell(Cell::new(
+                stats.num_success_txes / stats.duration.as_secs().max(1),
+            ));
+            row.add_cell(Cell::new(stats.num_success_txes));
+            row.add_cell(Cell::new(stats.num_error_txes));
+            row.add_cell(Cell::new(stats.latency_ms.histogram.value_at_quantile(0.5)));
+            row.add_cell(Cell::new(
+                stats.latency_ms.histogram.value_at_quantile(0.99),
+            ));
+            table.add_row(row);
+        }
+        table
+    }
+    /// One row per validator region, to show how much the latency of each region contributes to
+    /// the end-to-end latency
+    pub fn regions_to_table(&self) -> Table {
+        let mut table = Table::new();
+        table
+            .set_content_arrangement(ContentArrangement::Dynamic)
+            .set_width(200)
+            .set_header(vec![
+                "region",
+                "num success",
+                "latency (p50)",
+                "latency (p99)",
+            ]);
+        for (region, latency_ms) in &self.latency_ms_by_region {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(region));
+            row.add_cell(Cell::new(latency_ms.histogram.len()));
+            row.add_cell(Cell::new(latency_ms.histogram.value_at_quantile(0.5)));
+            row.add_cell(Cell::new(latency_ms.histogram.value_at_quantile(0.99)));
+            table.add_row(row);
+        }
+        table
+    }
+    /// The latency of successful transactions up to their effects certificate next to their
+    /// latency up to their checkpoint, see `BenchDriver::with_finality_tracking`
+    pub fn finality_to_table(&self) -> Table {
+        let mut table = Table::new();
+        table
+            .set_content_arrangement(ContentArrangement::Dynamic)
+            .set_width(200)
+            .set_header(vec![
+                "latency to",
+                "num txes",
+                "latency (p50)",
+                "latency (p95)",
+                "latency (p99)",
+                "latency (max)",
+            ]);
+        for (name, latency_ms) in [
+            ("certified", &self.latency_ms),
+            ("finalized", &self.finality_latency_ms),
+        ] {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(name));
+            row.add_cell(Cell::new(latency_ms.histogram.len()));
+            row.add_cell(Cell::new(latency_ms.histogram.value_at_quantile(0.5)));
+            row.add_cell(Cell::new(latency_ms.histogram.value_at_quantile(0.95)));
+            row.add_cell(Cell::new(latency_ms.histogram.value_at_quantile(0.99)));
+            row.add_cell(Cell::new(latency_ms.histogram.max()));
+            table.add_row(row);
+        }
+        table
+    }
 }
 
 /// A comparison between an old and a new benchmark.
@@ -393,3 +1224,359 @@ fn format_num_with_separators<T: Into<u128> + std::fmt::Display>(
         .unwrap()
         .join(delim)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Stats with every field set, to check that none of them is lost
+    fn benchmark_stats() -> BenchmarkStats {
+        let mut histogram = Histogram::<u64>::new_with_max(120_000, 3).unwrap();
+        for latency in [5, 20, 20, 150, 1_000] {
+            histogram.record(latency).unwrap();
+        }
+        let region_histogram = histogram.clone();
+        let mut finality_histogram = Histogram::<u64>::new_with_max(120_000, 3).unwrap();
+        for latency in [600, 2_500] {
+            finality_histogram.record(latency).unwrap();
+        }
+        let mut gas_histogram = Histogram::<u64>::new(3).unwrap();
+        for gas_used in [1_000_000, 2_000_000, 50_000_000_000] {
+            gas_histogram.record(gas_used).unwrap();
+        }
+        let mut txn_bytes_histogram = Histogram::<u64>::new(3).unwrap();
+        for txn_bytes in [500, 500, 100_000] {
+            txn_bytes_histogram.record(txn_bytes).unwrap();
+        }
+        BenchmarkStats {
+            duration: Duration::from_secs(30),
+            num_error_txes: 2,
+            num_success_txes: 5,
+            num_success_cmds: 7,
+            num_rejected_txes: 4,
+            num_semantic_failures: 1,
+            shared_object_conflicts: 2,
+            num_abandoned_txes: 3,
+            num_submitted_txes: 9,
+            total_gas_used: 1_000_000,
+            latency_ms: HistogramWrapper { histogram },
+            gas_used_per_tx: HistogramWrapper {
+                histogram: gas_histogram,
+            },
+            txn_bytes: HistogramWrapper {
+                histogram: txn_bytes_histogram,
+            },
+            errors_by_category: BTreeMap::from([
+                ("Timeout".to_string(), 1),
+                ("ObjectLocked".to_string(), 1),
+            ]),
+            num_success_reads: 3,
+            num_error_reads: 1,
+            read_latency_ms: HistogramWrapper::latency_ms(),
+            finality_latency_ms: HistogramWrapper {
+                histogram: finality_histogram,
+            },
+            num_finality_timeouts: 1,
+            qps_samples: vec![QpsLatencySample {
+                elapsed: Duration::from_secs(10),
+                target_qps: 100,
+                tps: 98.5,
+                latency_p50_ms: 20,
+                latency_p99_ms: 1_000,
+            }],
+            stats_by_workload: BTreeMap::from([(
+                "shared_counter".to_string(),
+                WorkloadStats {
+                    num_error_txes: 2,
+                    num_success_txes: 5,
+                    shared_object_conflicts: 2,
+                    latency_ms: HistogramWrapper::latency_ms(),
+                    txn_bytes: HistogramWrapper::txn_bytes(),
+                },
+            )]),
+            stats_by_epoch: vec![],
+            stats_by_phase: vec![],
+            latency_ms_by_region: BTreeMap::from([(
+                "us-east".to_string(),
+                HistogramWrapper {
+                    histogram: region_histogram,
+                },
+            )]),
+        }
+    }
+
+    #[test]
+    fn benchmark_stats_json_round_trip() {
+        let mut stats = benchmark_stats();
+        let sample = benchmark_stats();
+        stats.update_epoch(0, Duration::from_secs(10), &sample);
+        stats.update_epoch(1, Duration::from_secs(20), &sample);
+        stats.update_phase(0, 0, Duration::from_secs(10), &sample);
+
+        let json = stats.to_json().unwrap();
+        let deserialized = BenchmarkStats::from_json(&json).unwrap();
+        assert_eq!(deserialized.duration, stats.duration);
+        assert_eq!(deserialized.num_error_txes, stats.num_error_txes);
+        assert_eq!(deserialized.num_success_txes, stats.num_success_txes);
+        assert_eq!(deserialized.num_success_cmds, stats.num_success_cmds);
+        assert_eq!(deserialized.num_rejected_txes, stats.num_rejected_txes);
+        assert_eq!(
+            deserialized.num_semantic_failures,
+            stats.num_semantic_failures
+        );
+        assert_eq!(
+            deserialized.shared_object_conflicts,
+            stats.shared_object_conflicts
+        );
+        assert_eq!(deserialized.num_abandoned_txes, stats.num_abandoned_txes);
+        assert_eq!(deserialized.num_submitted_txes, stats.num_submitted_txes);
+        assert_eq!(deserialized.offered_qps(), 9.0 / 30.0);
+        assert_eq!(deserialized.total_gas_used, stats.total_gas_used);
+        assert_eq!(
+            deserialized.latency_ms.histogram,
+            stats.latency_ms.histogram
+        );
+        assert_eq!(
+            deserialized.gas_used_per_tx.histogram,
+            stats.gas_used_per_tx.histogram
+        );
+        assert_eq!(deserialized.mean_gas(), stats.mean_gas());
+        assert_eq!(deserialized.txn_bytes.histogram, stats.txn_bytes.histogram);
+        assert_eq!(deserialized.txn_bytes_at_quantile(0.5), 500);
+        assert_eq!(deserialized.errors_by_category, stats.errors_by_category);
+        assert_eq!(deserialized.num_success_reads, stats.num_success_reads);
+        assert_eq!(deserialized.num_error_reads, stats.num_error_reads);
+        assert_eq!(
+            deserialized.finality_latency_ms.histogram,
+            stats.finality_latency_ms.histogram
+        );
+        assert_eq!(
+            deserialized.num_finality_timeouts,
+            stats.num_finality_timeouts
+        );
+        assert_eq!(deserialized.qps_samples, stats.qps_samples);
+        assert_eq!(
+            deserialized.stats_by_workload["shared_counter"].num_success_txes,
+            5
+        );
+        assert_eq!(
+            deserialized.stats_by_workload["shared_counter"].shared_object_conflicts,
+            2
+        );
+        assert_eq!(deserialized.stats_by_epoch.len(), 2);
+        assert_eq!(
+            deserialized.stats_by_epoch[0].latency_ms.histogram,
+            stats.stats_by_epoch[0].latency_ms.histogram
+        );
+        assert_eq!(deserialized.stats_by_phase.len(), 1);
+        assert_eq!(
+            deserialized.latency_ms_by_region["us-east"].histogram,
+            stats.latency_ms_by_region["us-east"].histogram
+        );
+
+        // Stats written before versioning are still readable
+        let legacy = serde_json::to_string(&stats).unwrap();
+        let deserialized = BenchmarkStats::from_json(&legacy).unwrap();
+        assert_eq!(
+            deserialized.latency_ms.histogram,
+            stats.latency_ms.histogram
+        );
+
+        let unsupported = json.replacen(
+            &format!("\"version\":{BENCHMARK_STATS_JSON_VERSION}"),
+            "\"version\":0",
+            1,
+        );
+        assert!(BenchmarkStats::from_json(&unsupported).is_err());
+    }
+
+    #[test]
+    fn benchmark_stats_update_epoch() {
+        let mut stats = benchmark_stats();
+        let sample = benchmark_stats();
+        stats.update_epoch(0, Duration::from_secs(10), &sample);
+        stats.update_epoch(0, Duration::from_secs(20), &sample);
+        stats.update_epoch(1, Duration::from_secs(30), &sample);
+        assert_eq!(stats.stats_by_epoch.len(), 2);
+        assert_eq!(stats.stats_by_epoch[0].start, Duration::from_secs(10));
+        assert_eq!(stats.stats_by_epoch[0].duration, Duration::from_secs(10));
+        assert_eq!(stats.stats_by_epoch[0].num_success_txes, 10);
+        assert_eq!(stats.stats_by_epoch[1].epoch, 1);
+        assert_eq!(stats.stats_by_epoch[1].num_error_txes, 2);
+    }
+
+    #[test]
+    fn benchmark_stats_update_phase() {
+        let mut stats = benchmark_stats();
+        let sample = benchmark_stats();
+        stats.update_phase(0, 0, Duration::from_secs(10), &sample);
+        stats.update_phase(0, 1, Duration::from_secs(20), &sample);
+        stats.update_phase(1, 0, Duration::from_secs(30), &sample);
+        stats.update_phase(1, 0, Duration::from_secs(40), &sample);
+        assert_eq!(stats.stats_by_phase.len(), 3);
+        assert_eq!(stats.stats_by_phase[1].phase, 1);
+        assert_eq!(stats.stats_by_phase[2].cycle, 1);
+        assert_eq!(stats.stats_by_phase[2].start, Duration::from_secs(30));
+        assert_eq!(stats.stats_by_phase[2].duration, Duration::from_secs(10));
+        assert_eq!(stats.stats_by_phase[2].num_success_txes, 10);
+    }
+
+    #[test]
+    fn benchmark_stats_assert_min_throughput() {
+        // 5 successful transactions over 30s
+        let stats = benchmark_stats();
+        stats.assert_min_throughput(0.0);
+        stats.assert_min_throughput(5.0 / 30.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "is below the floor")]
+    fn benchmark_stats_assert_min_throughput_below_floor() {
+        benchmark_stats().assert_min_throughput(6.0 / 30.0);
+    }
+
+    #[test]
+    fn benchmark_stats_to_table_under_a_second() {
+        // A fail fast run can abort on its first error, well within its first second
+        let mut stats = benchmark_stats();
+        stats.duration = Duration::from_millis(500);
+        let table = stats.to_table().to_string();
+        assert!(table.contains("10.00"), "{table}");
+        stats.duration = Duration::ZERO;
+        stats.to_table();
+    }
+
+    fn stats_with_latencies(duration: Duration, latencies_ms: &[u64]) -> BenchmarkStats {
+        let mut latency_ms = HistogramWrapper::latency_ms();
+        for latency in latencies_ms {
+            latency_ms.histogram.record(*latency).unwrap();
+        }
+        let sample = BenchmarkStats {
+            duration,
+            num_error_txes: 1,
+            num_success_txes: latencies_ms.len() as u64,
+            num_success_cmds: latencies_ms.len() as u64,
+            num_submitted_txes: latencies_ms.len() as u64 + 1,
+            total_gas_used: 1_000 * latencies_ms.len() as u64,
+            latency_ms,
+            errors_by_category: BTreeMap::from([("Timeout".to_string(), 1)]),
+            ..BenchmarkStats::empty()
+        };
+        let mut stats = BenchmarkStats::from_json(&sample.to_json().unwrap()).unwrap();
+        stats.update_workload("transfer_object", &sample);
+        stats.update_epoch(0, duration, &sample);
+        stats
+    }
+
+    #[test]
+    fn benchmark_stats_merge() {
+        let mut stats = stats_with_latencies(Duration::from_secs(30), &[10; 99]);
+        let other = stats_with_latencies(Duration::from_secs(40), &[1_000; 99]);
+        stats.merge(&other);
+
+        assert_eq!(stats.duration, Duration::from_secs(40));
+        assert_eq!(stats.num_success_txes, 198);
+        assert_eq!(stats.num_error_txes, 2);
+        assert_eq!(stats.num_submitted_txes, 200);
+        assert_eq!(stats.total_gas_used, 198_000);
+        assert_eq!(stats.errors_by_category["Timeout"], 2);
+        // Half the transactions took 10ms and half 1s, so the median is 10ms and the p99 1s,
+        // where averaging the percentiles of each run would give about 505ms for both
+        assert_eq!(stats.p50(), 10);
+        assert_eq!(stats.p99(), 1_000);
+        let workload = &stats.stats_by_workload["transfer_object"];
+        assert_eq!(workload.num_success_txes, 198);
+        assert_eq!(workload.latency_ms.histogram.value_at_quantile(0.99), 1_000);
+        assert_eq!(stats.stats_by_epoch.len(), 1);
+        assert_eq!(stats.stats_by_epoch[0].num_success_txes, 198);
+        assert_eq!(stats.stats_by_epoch[0].start, Duration::from_secs(30));
+        assert_eq!(stats.stats_by_epoch[0].duration, Duration::from_secs(10));
+    }
+
+    #[test]
+    fn benchmark_stats_append_summary_csv() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("summary.csv");
+        let stats = stats_with_latencies(Duration::from_secs(10), &[10; 99]);
+        stats.append_summary_csv(&path).unwrap();
+        stats.append_summary_csv(&path).unwrap();
+
+        let csv = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], BENCHMARK_SUMMARY_CSV_HEADER);
+        let columns = BENCHMARK_SUMMARY_CSV_HEADER.split(',').count();
+        for row in &lines[1..] {
+            let row: Vec<_> = row.split(',').collect();
+            assert_eq!(row.len(), columns);
+            assert_eq!(row[1..5], ["10", "10.00", "9.90", "100"]);
+            assert_eq!(row[9], "10");
+        }
+    }
+
+    #[test]
+    fn load_validator_regions_from_toml() {
+        use crate::drivers::bench_driver::load_validator_regions;
+        use sui_types::base_types::AuthorityName;
+
+        let (east, west) = (AuthorityName::ZERO, AuthorityName::new([1; 96]));
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("regions.toml");
+        // With and without the `k#` prefix of displayed public keys
+        let west_key = west.to_string();
+        std::fs::write(
+            &path,
+            format!(
+                "\"{east}\" = \"us-east\"\n\"{}\" = \"eu-west\"\n",
+                &west_key[2..]
+            ),
+        )
+        .unwrap();
+        let regions = load_validator_regions(&path).unwrap();
+        assert_eq!(
+            regions,
+            BTreeMap::from([(east, "us-east".to_string()), (west, "eu-west".to_string())])
+        );
+
+        std::fs::write(&path, "\"k#00\" = \"us-east\"\n").unwrap();
+        assert!(load_validator_regions(&path).is_err());
+    }
+
+    #[test]
+    fn ramp_schedule_interpolates() {
+        let ramp: RampSchedule = "10s:100, 0s:0,30s:500".parse().unwrap();
+        assert_eq!(ramp.qps_at(Duration::ZERO), 0);
+        assert_eq!(ramp.qps_at(Duration::from_secs(5)), 50);
+        assert_eq!(ramp.qps_at(Duration::from_secs(10)), 100);
+        assert_eq!(ramp.qps_at(Duration::from_secs(20)), 300);
+        assert_eq!(ramp.qps_at(Duration::from_secs(30)), 500);
+        assert_eq!(ramp.qps_at(Duration::from_secs(600)), 500);
+
+        let constant: RampSchedule = "1m:200".parse().unwrap();
+        assert_eq!(constant.qps_at(Duration::ZERO), 200);
+        assert_eq!(constant.qps_at(Duration::from_secs(120)), 200);
+
+        assert!("".parse::<RampSchedule>().is_err());
+        assert!("10s".parse::<RampSchedule>().is_err());
+        assert!("10s:100,10s:200".parse::<RampSchedule>().is_err());
+    }
+
+    #[test]
+    fn aimd_rate_controller_backs_off() {
+        let target = Duration::from_millis(500);
+        let mut controller = AimdRateController::new(target, 100);
+        assert_eq!(controller.update(100, None), 100);
+        assert_eq!(controller.update(100, Some(Duration::from_secs(2))), 50);
+        assert_eq!(controller.update(100, Some(Duration::from_secs(2))), 25);
+        assert_eq!(controller.update(100, Some(target)), 30);
+        assert_eq!(controller.update(100, Some(Duration::from_millis(100))), 35);
+        // Never above the ceiling, which can move with a ramp
+        assert_eq!(controller.update(20, Some(Duration::from_millis(100))), 20);
+        assert_eq!(controller.update(100, Some(Duration::from_millis(100))), 25);
+        // Nor below one request per second
+        for _ in 0..10 {
+            controller.update(100, Some(Duration::from_secs(2)));
+        }
+        assert_eq!(controller.update(100, Some(Duration::from_secs(2))), 1);
+    }
+}
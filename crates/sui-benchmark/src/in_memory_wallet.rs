@@ -215,6 +215,32 @@ pub fn move_call_pt_impl(
     gas_budget: u64,
     gas_price: u64,
 ) -> VerifiedTransaction {
+    let data = move_call_pt_data(
+        sender,
+        package,
+        module,
+        function,
+        type_arguments,
+        arguments,
+        gas_ref,
+        gas_budget,
+        gas_price,
+    );
+    to_sender_signed_transaction(data, keypair)
+}
+
+/// The data of the transaction `move_call_pt_impl` signs, for callers which set more of it
+pub fn move_call_pt_data(
+    sender: SuiAddress,
+    package: ObjectID,
+    module: &str,
+    function: &str,
+    type_arguments: Vec<TypeTag>,
+    arguments: Vec<BenchMoveCallArg>,
+    gas_ref: &ObjectRef,
+    gas_budget: u64,
+    gas_price: u64,
+) -> TransactionData {
     let mut builder = ProgrammableTransactionBuilder::new();
     let args = convert_move_call_args(&arguments, &mut builder);
 
@@ -225,12 +251,11 @@ pub fn move_call_pt_impl(
         type_arguments,
         args,
     ));
-    let data = TransactionData::new_programmable(
+    TransactionData::new_programmable(
         sender,
         vec![*gas_ref],
         builder.finish(),
         gas_budget,
         gas_price,
-    );
-    to_sender_signed_transaction(data, keypair)
+    )
 }
@@ -4,13 +4,22 @@ use anyhow::bail;
 use async_trait::async_trait;
 use embedded_reconfig_observer::EmbeddedReconfigObserver;
 use fullnode_reconfig_observer::FullNodeReconfigObserver;
-use futures::{stream::FuturesUnordered, StreamExt};
-use prometheus::Registry;
+use futures::{future::join_all, stream::FuturesUnordered, StreamExt};
+use prometheus::{register_int_counter_vec_with_registry, IntCounterVec, Registry};
+use rand::seq::SliceRandom;
 use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::BTreeMap,
-    sync::{Arc, Mutex},
-    time::Duration,
+    collections::{BTreeMap, BTreeSet},
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
 };
 use sui_config::genesis::Genesis;
 use sui_core::{
@@ -36,7 +45,7 @@ use sui_types::programmable_transaction_builder::ProgrammableTransactionBuilder;
 use sui_types::sui_system_state::sui_system_state_summary::SuiSystemStateSummary;
 use sui_types::{
     base_types::ObjectID,
-    committee::{Committee, EpochId},
+    committee::{Committee, EpochId, StakeUnit},
     crypto::{
         AggregateAuthenticator, AggregateAuthoritySignature, AuthorityQuorumSignInfo,
         AuthoritySignature,
@@ -46,17 +55,20 @@ use sui_types::{
         CertifiedTransaction, CertifiedTransactionEffects, HandleCertificateResponse,
         QuorumDriverResponse, Transaction, TransactionEffectsAPI, TransactionStatus,
     },
+    messages_checkpoint::CheckpointSequenceNumber,
     object::Object,
+    quorum_driver_types::QuorumDriverError,
 };
 use sui_types::{base_types::ObjectRef, crypto::AuthorityStrongQuorumSignInfo, object::Owner};
 use sui_types::{base_types::SequenceNumber, gas_coin::GasCoin};
 use sui_types::{
-    base_types::{AuthorityName, SuiAddress},
+    base_types::{AuthorityName, SuiAddress, TransactionDigest},
     sui_system_state::SuiSystemStateTrait,
 };
 use sui_types::{error::SuiError, gas::GasCostSummary};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
 use tokio::{task::JoinSet, time::timeout};
-use tracing::{error, info};
+use tracing::{debug, error, info};
 
 pub mod bank;
 pub mod benchmark_setup;
@@ -106,6 +118,19 @@ impl ExecutionEffects {
         }
     }
 
+    pub fn unwrapped(&self) -> Vec<(ObjectRef, Owner)> {
+        match self {
+            ExecutionEffects::CertifiedTransactionEffects(certified_effects, ..) => {
+                certified_effects.data().unwrapped().to_vec()
+            }
+            ExecutionEffects::SuiTransactionBlockEffects(sui_tx_effects) => sui_tx_effects
+                .unwrapped()
+                .iter()
+                .map(|refe| (refe.reference.to_object_ref(), refe.owner))
+                .collect(),
+        }
+    }
+
     pub fn deleted(&self) -> Vec<ObjectRef> {
         match self {
             ExecutionEffects::CertifiedTransactionEffects(certified_effects, ..) => {
@@ -119,6 +144,21 @@ impl ExecutionEffects {
         }
     }
 
+    /// References to the shared objects the transaction took as input, at the versions consensus
+    /// assigned to it
+    pub fn shared_objects(&self) -> Vec<ObjectRef> {
+        match self {
+            ExecutionEffects::CertifiedTransactionEffects(certified_effects, ..) => {
+                certified_effects.data().shared_objects().to_vec()
+            }
+            ExecutionEffects::SuiTransactionBlockEffects(sui_tx_effects) => sui_tx_effects
+                .shared_objects()
+                .iter()
+                .map(|refe| refe.to_object_ref())
+                .collect(),
+        }
+    }
+
     pub fn quorum_sig(&self) -> Option<&AuthorityStrongQuorumSignInfo> {
         match self {
             ExecutionEffects::CertifiedTransactionEffects(certified_effects, ..) => {
@@ -221,10 +261,23 @@ pub trait ValidatorProxy {
 
     async fn execute_transaction_block(&self, tx: Transaction) -> anyhow::Result<ExecutionEffects>;
 
+    /// Execute a batch of transactions, returning the result of each in the order they were given.
+    /// By default they are executed one at a time, proxies which can submit them concurrently
+    /// should override this.
+    async fn submit_batch(&self, txs: Vec<Transaction>) -> Vec<anyhow::Result<ExecutionEffects>> {
+        let mut results = Vec::with_capacity(txs.len());
+        for tx in txs {
+            results.push(self.execute_transaction_block(tx).await);
+        }
+        results
+    }
+
     /// This function is similar to `execute_transaction` but does not check any validator's
     /// signature. It should only be used for benchmarks.
     async fn execute_bench_transaction(&self, tx: Transaction) -> anyhow::Result<ExecutionEffects>;
 
+    /// The committee of the current epoch, which changes with reconfiguration. Workloads and
+    /// stats use it to tell which validators signed a certificate, and with how much stake.
     fn clone_committee(&self) -> Committee;
 
     fn get_current_epoch(&self) -> EpochId;
@@ -232,6 +285,189 @@ pub trait ValidatorProxy {
     fn clone_new(&self) -> Box<dyn ValidatorProxy + Send + Sync>;
 
     async fn get_validators(&self) -> Result<Vec<SuiAddress>, anyhow::Error>;
+
+    /// The checkpoint which includes transaction `digest`, or `None` if it is not known to be in
+    /// one yet. Fails only if the proxy has no way of telling, which is the default: validators
+    /// do not index checkpoints by transaction, only full nodes do.
+    async fn get_transaction_checkpoint(
+        &self,
+        _digest: TransactionDigest,
+    ) -> anyhow::Result<Option<CheckpointSequenceNumber>> {
+        bail!("Checkpoint lookups are not supported by this proxy")
+    }
+}
+
+/// The order in which `LocalValidatorAggregatorProxy` contacts validators to assemble a quorum of
+/// votes and then of effects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoutingPolicy {
+    /// Send to every validator at once.
+    #[default]
+    Broadcast,
+    /// Validators with the lowest observed response latency first.
+    LowestLatencyFirst,
+    /// Start from the next validator on each request.
+    RoundRobin,
+    /// A new random order on each request.
+    Random,
+}
+
+impl RoutingPolicy {
+    /// Sort `validators` in the order this policy contacts them, given the observed `latencies`
+    /// of the validators and the number of requests routed before this one
+    fn order(
+        self,
+        validators: &mut [AuthorityName],
+        latencies: &BTreeMap<AuthorityName, Duration>,
+        requests: usize,
+    ) {
+        match self {
+            RoutingPolicy::Broadcast => {}
+            RoutingPolicy::LowestLatencyFirst => {
+                // Validators without any sample yet go first so that they get measured
+                validators.sort_by_key(|name| latencies.get(name).copied().unwrap_or_default());
+            }
+            RoutingPolicy::RoundRobin => {
+                let len = validators.len();
+                validators.rotate_left(requests % len);
+            }
+            RoutingPolicy::Random => validators.shuffle(&mut rand::thread_rng()),
+        }
+    }
+}
+
+impl FromStr for RoutingPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "broadcast" => Ok(RoutingPolicy::Broadcast),
+            "lowest-latency-first" => Ok(RoutingPolicy::LowestLatencyFirst),
+            "round-robin" => Ok(RoutingPolicy::RoundRobin),
+            "random" => Ok(RoutingPolicy::Random),
+            _ => bail!(
+                "Unknown routing policy {s:?}, expected one of broadcast, lowest-latency-first, round-robin or random"
+            ),
+        }
+    }
+}
+
+/// How much stake `LocalValidatorAggregatorProxy` waits for before it stops waiting for the other
+/// validators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuorumThreshold {
+    /// f+1, enough for at least one honest validator to have replied
+    Validity,
+    /// 2f+1, the threshold of certificates and of final effects
+    #[default]
+    Quorum,
+    /// Every validator, or all of those that reply if some fail
+    All,
+}
+
+impl QuorumThreshold {
+    pub fn stake(self, committee: &Committee) -> StakeUnit {
+        match self {
+            QuorumThreshold::Validity => committee.validity_threshold(),
+            QuorumThreshold::Quorum => committee.quorum_threshold(),
+            QuorumThreshold::All => committee.total_votes(),
+        }
+    }
+}
+
+impl FromStr for QuorumThreshold {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "validity" => Ok(QuorumThreshold::Validity),
+            "quorum" => Ok(QuorumThreshold::Quorum),
+            "all" => Ok(QuorumThreshold::All),
+            _ => bail!("Unknown quorum threshold {s:?}, expected one of validity, quorum or all"),
+        }
+    }
+}
+
+/// Weight of the newest sample in the moving average of each validator's latency.
+const LATENCY_EWMA_WEIGHT: f64 = 0.2;
+
+/// Latency recorded for a validator which failed a request, unless it took longer to fail. As
+/// slow as a request that timed out, so that `RoutingPolicy::LowestLatencyFirst` does not keep
+/// preferring a validator which fails fast.
+const FAILED_REQUEST_LATENCY: Duration = DEFAULT_REQUEST_TIMEOUT_SEC;
+
+/// Add a `latency` sample of validator `name` to the moving average of its `latencies`
+fn record_latency(
+    latencies: &mut BTreeMap<AuthorityName, Duration>,
+    name: AuthorityName,
+    latency: Duration,
+) {
+    let average = latencies.entry(name).or_insert(latency);
+    *average = average.mul_f64(1.0 - LATENCY_EWMA_WEIGHT) + latency.mul_f64(LATENCY_EWMA_WEIGHT);
+}
+
+/// Record a request to validator `name` which failed after `elapsed` as a slow reply, see
+/// `FAILED_REQUEST_LATENCY`
+fn record_failure(
+    latencies: &mut BTreeMap<AuthorityName, Duration>,
+    name: AuthorityName,
+    elapsed: Duration,
+) {
+    record_latency(latencies, name, elapsed.max(FAILED_REQUEST_LATENCY));
+}
+
+/// How many of `validators`, taken in order, it takes to reach `threshold` of the stake of
+/// `committee`, or all of them if they do not hold that much
+fn quorum_prefix(
+    committee: &Committee,
+    validators: &[AuthorityName],
+    threshold: StakeUnit,
+) -> usize {
+    let mut stake = 0;
+    for (i, name) in validators.iter().enumerate() {
+        stake += committee.weight(name);
+        if stake >= threshold {
+            return i + 1;
+        }
+    }
+    validators.len()
+}
+
+/// How often `LocalValidatorAggregatorProxy` had to wait for a reply from the slow tail of the
+/// committee, the validators outside the fastest quorum by observed latency, to reach its
+/// `QuorumThreshold`. Labeled by phase, "transaction" for votes and "certificate" for effects.
+#[derive(Clone)]
+struct QuorumMetrics {
+    requests: IntCounterVec,
+    tail_needed: IntCounterVec,
+}
+
+impl QuorumMetrics {
+    fn new(registry: &Registry) -> Self {
+        Self {
+            requests: register_int_counter_vec_with_registry!(
+                "quorum_requests",
+                "Total number of requests which reached their quorum threshold",
+                &["phase"],
+                registry,
+            )
+            .unwrap(),
+            tail_needed: register_int_counter_vec_with_registry!(
+                "quorum_tail_needed",
+                "Total number of requests which needed a reply from the slow tail of validators",
+                &["phase"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    fn record(&self, phase: &str, tail_needed: bool) {
+        self.requests.with_label_values(&[phase]).inc();
+        if tail_needed {
+            self.tail_needed.with_label_values(&[phase]).inc();
+        }
+    }
 }
 
 // TODO: Eventually remove this proxy because we shouldn't rely on validators to read objects.
@@ -239,9 +475,14 @@ pub struct LocalValidatorAggregatorProxy {
     _qd_handler: QuorumDriverHandler<NetworkAuthorityClient>,
     // Stress client does not verify individual validator signatures since this is very expensive
     qd: Arc<QuorumDriver<NetworkAuthorityClient>>,
-    committee: Committee,
     clients: BTreeMap<AuthorityName, NetworkAuthorityClient>,
     requests: Mutex<JoinSet<()>>,
+    routing_policy: RoutingPolicy,
+    quorum_threshold: QuorumThreshold,
+    // Shared with the clones of this proxy, so they all route based on the same observations
+    next_validator: Arc<AtomicUsize>,
+    latencies: Arc<Mutex<BTreeMap<AuthorityName, Duration>>>,
+    quorum_metrics: QuorumMetrics,
 }
 
 impl LocalValidatorAggregatorProxy {
@@ -263,14 +504,7 @@ impl LocalValidatorAggregatorProxy {
         )
         .unwrap();
 
-        Self::new_impl(
-            aggregator,
-            registry,
-            reconfig_fullnode_rpc_url,
-            clients,
-            committee.committee,
-        )
-        .await
+        Self::new_impl(aggregator, registry, reconfig_fullnode_rpc_url, clients).await
     }
 
     async fn new_impl(
@@ -278,7 +512,6 @@ impl LocalValidatorAggregatorProxy {
         registry: &Registry,
         reconfig_fullnode_rpc_url: Option<&str>,
         clients: BTreeMap<AuthorityName, NetworkAuthorityClient>,
-        committee: Committee,
     ) -> Self {
         let quorum_driver_metrics = Arc::new(QuorumDriverMetrics::new(registry));
         let qd_handler = (if let Some(reconfig_fullnode_rpc_url) = reconfig_fullnode_rpc_url {
@@ -320,9 +553,84 @@ impl LocalValidatorAggregatorProxy {
             _qd_handler: qd_handler,
             qd,
             clients,
-            committee,
             requests: Mutex::new(JoinSet::new()),
+            routing_policy: RoutingPolicy::default(),
+            quorum_threshold: QuorumThreshold::default(),
+            next_validator: Arc::new(AtomicUsize::new(0)),
+            latencies: Arc::new(Mutex::new(BTreeMap::new())),
+            quorum_metrics: QuorumMetrics::new(registry),
+        }
+    }
+
+    /// Contact validators in the order given by `policy`. Unless broadcasting, a request is only
+    /// sent to as many validators as a quorum needs, and to the next one each time a validator
+    /// fails. The quorum driver has its own routing, so transactions always go through the bench
+    /// path of `execute_bench_transaction` with any other policy than `RoutingPolicy::Broadcast`.
+    pub fn with_routing_policy(mut self, policy: RoutingPolicy) -> Self {
+        self.routing_policy = policy;
+        self
+    }
+
+    /// Stop waiting for validators once `threshold` of the stake replied. A certificate always
+    /// needs a quorum of votes, so a lower threshold only applies to effects: a validity threshold
+    /// returns effects once f+1 validators executed the certificate, before they are final. As
+    /// with routing policies, any other threshold than `QuorumThreshold::Quorum` sends
+    /// transactions through the bench path of `execute_bench_transaction`.
+    ///
+    /// Whether each request needed a reply from the slow tail of the committee to reach the
+    /// threshold is recorded in the `quorum_tail_needed` metric, to measure the latency cost of
+    /// straggler validators.
+    pub fn with_quorum_threshold(mut self, threshold: QuorumThreshold) -> Self {
+        self.quorum_threshold = threshold;
+        self
+    }
+
+    /// The validators in the order they should be contacted, and how many of them to contact
+    /// up front to reach `threshold` of the stake of `committee`.
+    fn route(&self, committee: &Committee, threshold: StakeUnit) -> (Vec<AuthorityName>, usize) {
+        let mut validators: Vec<AuthorityName> = self.clients.keys().cloned().collect();
+        if self.routing_policy == RoutingPolicy::Broadcast {
+            let len = validators.len();
+            return (validators, len);
         }
+        let requests = self.next_validator.fetch_add(1, Ordering::Relaxed);
+        self.routing_policy
+            .order(&mut validators, &self.latencies.lock().unwrap(), requests);
+        let initial = quorum_prefix(committee, &validators, threshold);
+        (validators, initial)
+    }
+
+    /// The validators outside the fastest quorum of `committee` by observed latency, counting
+    /// validators without any sample yet as the fastest
+    fn slow_tail(&self, committee: &Committee) -> BTreeSet<AuthorityName> {
+        let mut validators: Vec<AuthorityName> = self.clients.keys().cloned().collect();
+        RoutingPolicy::LowestLatencyFirst.order(
+            &mut validators,
+            &self.latencies.lock().unwrap(),
+            0,
+        );
+        let mut stake = 0;
+        validators
+            .into_iter()
+            .skip_while(|name| {
+                let fast = stake < committee.quorum_threshold();
+                stake += committee.weight(name);
+                fast
+            })
+            .collect()
+    }
+
+    fn record_latency(&self, name: AuthorityName, latency: Duration) {
+        record_latency(&mut self.latencies.lock().unwrap(), name, latency);
+    }
+
+    fn record_failure(&self, name: AuthorityName, elapsed: Duration) {
+        record_failure(&mut self.latencies.lock().unwrap(), name, elapsed);
+    }
+
+    /// Resubmit transactions that fail with a retryable error, see `RetryProxy`.
+    pub fn with_retry(self, max_attempts: u32, base_backoff: Duration) -> RetryProxy {
+        RetryProxy::new(Box::new(self), max_attempts, base_backoff)
     }
 }
 
@@ -351,13 +659,16 @@ impl ValidatorProxy for LocalValidatorAggregatorProxy {
     }
 
     async fn execute_transaction_block(&self, tx: Transaction) -> anyhow::Result<ExecutionEffects> {
-        if std::env::var("BENCH_MODE").is_ok() {
+        if std::env::var("BENCH_MODE").is_ok()
+            || self.routing_policy != RoutingPolicy::Broadcast
+            || self.quorum_threshold != QuorumThreshold::Quorum
+        {
             return self.execute_bench_transaction(tx).await;
         }
         let tx_digest = *tx.digest();
         let tx = tx.verify()?;
         let mut retry_cnt = 0;
-        while retry_cnt < 3 {
+        loop {
             let ticket = self.qd.submit_transaction(tx.clone()).await?;
             // The ticket only times out when QuorumDriver exceeds the retry times
             match ticket.await {
@@ -377,36 +688,68 @@ impl ValidatorProxy for LocalValidatorAggregatorProxy {
                         retry_cnt, "Transaction failed with err: {:?}", err
                     );
                     retry_cnt += 1;
+                    if retry_cnt >= 3 || !is_retryable_quorum_driver_error(&err) {
+                        return Err(anyhow::Error::new(err).context(format!(
+                            "Transaction {:?} failed for {retry_cnt} times",
+                            tx_digest
+                        )));
+                    }
                 }
             }
         }
-        bail!("Transaction {:?} failed for {retry_cnt} times", tx_digest);
+    }
+
+    async fn submit_batch(&self, txs: Vec<Transaction>) -> Vec<anyhow::Result<ExecutionEffects>> {
+        // Each transaction goes through the quorum driver independently, so fan them all out
+        join_all(txs.into_iter().map(|tx| self.execute_transaction_block(tx))).await
     }
 
     async fn execute_bench_transaction(&self, tx: Transaction) -> anyhow::Result<ExecutionEffects> {
         // Store the epoch number; we read it from the votes and use it later to create the certificate.
         let mut epoch = 0;
-
-        // Send the transaction to all validators.
-        let mut futures = FuturesUnordered::new();
-        for (name, client) in self.clients.iter() {
-            let fut = client.handle_transaction(tx.clone()).map(|r| (r, *name));
-            futures.push(fut);
-        }
+        // Stake is counted against the committee of the epoch at submission, which follows
+        // reconfiguration unlike the committee of the genesis the proxy was created from
+        let committee = self.clone_committee();
+        // A certificate needs a quorum of votes whatever the threshold
+        let vote_threshold = self
+            .quorum_threshold
+            .stake(&committee)
+            .max(committee.quorum_threshold());
+        let effects_threshold = self.quorum_threshold.stake(&committee);
+        let slow_tail = self.slow_tail(&committee);
+
+        // Send the transaction to the validators picked by the routing policy, all of them when
+        // broadcasting.
+        let (validators, initial) = self.route(&committee, vote_threshold);
+        let mut validators = validators.into_iter();
+        let send_transaction = |name: AuthorityName| {
+            let start = Instant::now();
+            self.clients[&name]
+                .handle_transaction(tx.clone())
+                .map(move |r| (r, name, start.elapsed()))
+        };
+        let mut futures: FuturesUnordered<_> = validators
+            .by_ref()
+            .take(initial)
+            .map(send_transaction)
+            .collect();
 
         // TODO: This following aggregation will not work well at epoch boundary.
 
-        // Listen to the replies from the first 2f+1 votes.
+        // Listen to the replies from the first votes reaching the threshold.
         let mut total_stake = 0;
+        let mut tail_needed = false;
         let mut votes = Vec::new();
         let mut certificate = None;
-        while let Some((response, name)) = futures.next().await {
+        while let Some((response, name, latency)) = futures.next().await {
             match response {
                 Ok(response) => match response.status {
                     // If all goes well, the authority returns a vote.
                     TransactionStatus::Signed(signature) => {
+                        self.record_latency(name, latency);
+                        tail_needed |= slow_tail.contains(&name);
                         epoch = signature.epoch;
-                        total_stake += self.committee.weight(&signature.authority);
+                        total_stake += committee.weight(&signature.authority);
                         votes.push(signature);
                     }
                     // The transaction may be submitted again in case the certificate's submission failed.
@@ -430,11 +773,15 @@ impl ValidatorProxy for LocalValidatorAggregatorProxy {
                         .process_tx_errors
                         .with_label_values(&[&name.concise().to_string(), e.as_ref()])
                         .inc();
-                    tracing::warn!("Failed to submit transaction: {e}")
+                    self.record_failure(name, latency);
+                    tracing::warn!("Failed to submit transaction: {e}");
+                    // Make up for the failed validator with the next one, if any is left
+                    futures.extend(validators.next().map(send_transaction));
                 }
             }
 
-            if total_stake >= self.committee.quorum_threshold() {
+            if total_stake >= vote_threshold {
+                self.quorum_metrics.record("transaction", tail_needed);
                 break;
             }
 
@@ -454,12 +801,12 @@ impl ValidatorProxy for LocalValidatorAggregatorProxy {
                 let mut signers_map = RoaringBitmap::new();
                 for pk in signatures.keys() {
                     signers_map.insert(
-                        self.committee
+                        committee
                             .authority_index(pk)
                             .ok_or(SuiError::UnknownSigner {
                                 signer: Some(pk.concise().to_string()),
                                 index: None,
-                                committee: Box::new(self.committee.clone()),
+                                committee: Box::new(committee.clone()),
                             })
                             .expect("Received signature from unknown validator"),
                     );
@@ -482,25 +829,32 @@ impl ValidatorProxy for LocalValidatorAggregatorProxy {
             }
         };
 
-        // Send the certificate to all validators.
-        let mut futures = FuturesUnordered::new();
-        total_stake = 0;
-        let mut transaction_effects = None;
-        let mut transaction_events = None;
-        for (name, client) in self.clients.iter() {
-            let client = client.clone();
+        // Send the certificate to the validators picked by the routing policy.
+        let (validators, initial) = self.route(&committee, effects_threshold);
+        let mut validators = validators.into_iter();
+        let send_certificate = |name: AuthorityName| {
+            let client = self.clients[&name].clone();
             let certificate = certified_transaction.clone();
-            let name = *name;
-            futures.push(async move {
+            async move {
+                let start = Instant::now();
                 client
                     .handle_certificate(certificate)
-                    .map(move |r| (r, name))
+                    .map(move |r| (r, name, start.elapsed()))
                     .await
-            });
-        }
+            }
+        };
+        let mut futures: FuturesUnordered<_> = validators
+            .by_ref()
+            .take(initial)
+            .map(send_certificate)
+            .collect();
+        total_stake = 0;
+        tail_needed = false;
+        let mut transaction_effects = None;
+        let mut transaction_events = None;
 
-        // Wait for the replies from a quorum of validators.
-        while let Some((response, name)) = futures.next().await {
+        // Wait for the replies from validators holding the threshold of the stake.
+        while let Some((response, name, latency)) = futures.next().await {
             match response {
                 // If all goes well, the validators reply with signed effects.
                 Ok(HandleCertificateResponse {
@@ -508,9 +862,11 @@ impl ValidatorProxy for LocalValidatorAggregatorProxy {
                     events,
                 }) => {
                     let author = signed_effects.auth_sig().authority;
+                    self.record_latency(name, latency);
+                    tail_needed |= slow_tail.contains(&name);
                     transaction_effects = Some(signed_effects.data().clone());
                     transaction_events = Some(events);
-                    total_stake += self.committee.weight(&author);
+                    total_stake += committee.weight(&author);
                 }
 
                 // This typically happens when the validators are overloaded and the certificate is
@@ -523,18 +879,24 @@ impl ValidatorProxy for LocalValidatorAggregatorProxy {
                         .process_cert_errors
                         .with_label_values(&[&name.concise().to_string(), e.as_ref()])
                         .inc();
-                    tracing::warn!("Failed to submit certificate: {e}")
+                    self.record_failure(name, latency);
+                    tracing::warn!("Failed to submit certificate: {e}");
+                    futures.extend(validators.next().map(send_certificate));
                 }
             }
 
-            if total_stake >= self.committee.quorum_threshold() {
+            if total_stake >= effects_threshold {
+                self.quorum_metrics.record("certificate", tail_needed);
                 break;
             }
         }
 
         // Abort if we failed to submit the certificate to enough validators. This typically
-        // happens when the validators are overloaded and the requests timed out.
-        if transaction_effects.is_none() || total_stake < self.committee.quorum_threshold() {
+        // happens when the validators are overloaded and the requests timed out. Waiting for all
+        // validators settles for a quorum of them when some fail.
+        if transaction_effects.is_none()
+            || total_stake < effects_threshold.min(committee.quorum_threshold())
+        {
             bail!("Failed to submit certificate to quorum of validators");
         }
 
@@ -570,8 +932,12 @@ impl ValidatorProxy for LocalValidatorAggregatorProxy {
             _qd_handler: qdh,
             qd,
             clients: self.clients.clone(),
-            committee: self.committee.clone(),
             requests: Mutex::new(JoinSet::new()),
+            routing_policy: self.routing_policy,
+            quorum_threshold: self.quorum_threshold,
+            next_validator: self.next_validator.clone(),
+            latencies: self.latencies.clone(),
+            quorum_metrics: self.quorum_metrics.clone(),
         })
     }
 
@@ -585,6 +951,338 @@ impl ValidatorProxy for LocalValidatorAggregatorProxy {
     }
 }
 
+/// Whether an error returned by a `ValidatorProxy` is transient, such that submitting the same
+/// transaction again may succeed.
+pub fn is_retryable_error(err: &anyhow::Error) -> bool {
+    if let Some(err) = err.downcast_ref::<QuorumDriverError>() {
+        is_retryable_quorum_driver_error(err)
+    } else if let Some(err) = err.downcast_ref::<SuiError>() {
+        err.is_retryable().0
+    } else {
+        false
+    }
+}
+
+/// A short, stable name for the kind of failure in `err`, used to break errors down in stats.
+pub fn error_category(err: &anyhow::Error) -> String {
+    if let Some(err) = err.downcast_ref::<QuorumDriverError>() {
+        match err {
+            QuorumDriverError::TimeoutBeforeFinality => "Timeout".to_string(),
+            QuorumDriverError::ObjectsDoubleUsed { .. } => "ObjectLocked".to_string(),
+            QuorumDriverError::SystemOverload { .. } => "Overload".to_string(),
+            QuorumDriverError::FailedWithTransientErrorAfterMaximumAttempts { .. } => {
+                "QuorumFailure".to_string()
+            }
+            QuorumDriverError::QuorumDriverInternalError(err) => sui_error_category(err),
+            _ => err.as_ref().to_string(),
+        }
+    } else if let Some(err) = err.downcast_ref::<SuiError>() {
+        sui_error_category(err)
+    } else {
+        "Other".to_string()
+    }
+}
+
+fn sui_error_category(err: &SuiError) -> String {
+    match err {
+        SuiError::ObjectLockConflict { .. } => "ObjectLocked".to_string(),
+        SuiError::RpcError(..) => "Rpc".to_string(),
+        _ if err.is_overload() => "Overload".to_string(),
+        _ => err.as_ref().to_string(),
+    }
+}
+
+fn is_retryable_quorum_driver_error(err: &QuorumDriverError) -> bool {
+    match err {
+        QuorumDriverError::TimeoutBeforeFinality
+        | QuorumDriverError::FailedWithTransientErrorAfterMaximumAttempts { .. }
+        | QuorumDriverError::SystemOverload { .. } => true,
+        QuorumDriverError::QuorumDriverInternalError(err) => err.is_retryable().0,
+        QuorumDriverError::InvalidUserSignature(_)
+        | QuorumDriverError::ObjectsDoubleUsed { .. }
+        | QuorumDriverError::NonRecoverableTransactionError { .. } => false,
+    }
+}
+
+/// Wraps a `ValidatorProxy` to resubmit transactions that fail with a retryable error, backing off
+/// exponentially between attempts. Any other error is returned immediately.
+pub struct RetryProxy {
+    inner: Box<dyn ValidatorProxy + Send + Sync>,
+    max_attempts: u32,
+    base_backoff: Duration,
+}
+
+impl RetryProxy {
+    /// Make up to `max_attempts` attempts at each transaction, waiting `base_backoff` before the
+    /// first retry and doubling the wait before each one after that.
+    pub fn new(
+        inner: Box<dyn ValidatorProxy + Send + Sync>,
+        max_attempts: u32,
+        base_backoff: Duration,
+    ) -> Self {
+        assert!(
+            max_attempts > 0,
+            "RetryProxy must make at least one attempt"
+        );
+        Self {
+            inner,
+            max_attempts,
+            base_backoff,
+        }
+    }
+
+    async fn execute_with_retry(
+        &self,
+        tx: Transaction,
+        bench: bool,
+    ) -> anyhow::Result<ExecutionEffects> {
+        let tx_digest = *tx.digest();
+        let mut attempt = 1;
+        loop {
+            let result = if bench {
+                self.inner.execute_bench_transaction(tx.clone()).await
+            } else {
+                self.inner.execute_transaction_block(tx.clone()).await
+            };
+            match result {
+                Err(err) if attempt < self.max_attempts && is_retryable_error(&err) => {
+                    let backoff = self.base_backoff.saturating_mul(1 << (attempt - 1).min(31));
+                    tracing::warn!(
+                        ?tx_digest,
+                        attempt,
+                        "Retrying transaction in {backoff:?} after err: {err}"
+                    );
+                    tokio::time::sleep(backoff).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl ValidatorProxy for RetryProxy {
+    async fn get_object(&self, object_id: ObjectID) -> Result<Object, anyhow::Error> {
+        self.inner.get_object(object_id).await
+    }
+
+    async fn get_owned_objects(
+        &self,
+        account_address: SuiAddress,
+    ) -> Result<Vec<(u64, Object)>, anyhow::Error> {
+        self.inner.get_owned_objects(account_address).await
+    }
+
+    async fn get_latest_system_state_object(&self) -> Result<SuiSystemStateSummary, anyhow::Error> {
+        self.inner.get_latest_system_state_object().await
+    }
+
+    async fn execute_transaction_block(&self, tx: Transaction) -> anyhow::Result<ExecutionEffects> {
+        self.execute_with_retry(tx, false).await
+    }
+
+    async fn execute_bench_transaction(&self, tx: Transaction) -> anyhow::Result<ExecutionEffects> {
+        self.execute_with_retry(tx, true).await
+    }
+
+    fn clone_committee(&self) -> Committee {
+        self.inner.clone_committee()
+    }
+
+    fn get_current_epoch(&self) -> EpochId {
+        self.inner.get_current_epoch()
+    }
+
+    fn clone_new(&self) -> Box<dyn ValidatorProxy + Send + Sync> {
+        Box::new(Self {
+            inner: self.inner.clone_new(),
+            max_attempts: self.max_attempts,
+            base_backoff: self.base_backoff,
+        })
+    }
+
+    async fn get_validators(&self) -> Result<Vec<SuiAddress>, anyhow::Error> {
+        self.inner.get_validators().await
+    }
+
+    async fn get_transaction_checkpoint(
+        &self,
+        digest: TransactionDigest,
+    ) -> anyhow::Result<Option<CheckpointSequenceNumber>> {
+        self.inner.get_transaction_checkpoint(digest).await
+    }
+}
+
+/// One transaction submitted through a `RecordingValidatorProxy`, along with what came of it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionLogEntry {
+    pub tx: Transaction,
+    /// Whether the transaction was submitted with `execute_bench_transaction`
+    pub bench: bool,
+    /// The execution status of the transaction, or the error it failed with
+    pub outcome: Result<String, String>,
+}
+
+impl TransactionLogEntry {
+    fn outcome_of(result: &anyhow::Result<ExecutionEffects>) -> Result<String, String> {
+        match result {
+            Ok(effects) => Ok(effects.status()),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+}
+
+/// Wraps a `ValidatorProxy` to append every transaction it executes, along with its outcome, to a
+/// log which `replay_transaction_log` can submit again later. Entries are in the order the
+/// transactions completed, so each comes after those whose outputs it uses. Clones made with
+/// `clone_new` append to the same log.
+pub struct RecordingValidatorProxy {
+    inner: Box<dyn ValidatorProxy + Send + Sync>,
+    log_tx: UnboundedSender<TransactionLogEntry>,
+}
+
+impl RecordingValidatorProxy {
+    /// Create the log at `path`, replacing any file there. Entries are written on a blocking
+    /// thread, each of them flushed so that the log is complete up to the last transaction even
+    /// if the process dies.
+    pub fn new(inner: Box<dyn ValidatorProxy + Send + Sync>, path: &Path) -> anyhow::Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        let path = path.to_owned();
+        let (log_tx, mut log_rx) = unbounded_channel::<TransactionLogEntry>();
+        tokio::task::spawn_blocking(move || {
+            let mut write_all = || -> anyhow::Result<()> {
+                while let Some(entry) = log_rx.blocking_recv() {
+                    let bytes = bcs::to_bytes(&entry)?;
+                    writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+                    writer.write_all(&bytes)?;
+                    writer.flush()?;
+                }
+                Ok(())
+            };
+            if let Err(e) = write_all() {
+                error!("Failed to write transaction log to {:?}: {}", path, e);
+            }
+        });
+        Ok(Self { inner, log_tx })
+    }
+
+    fn record(&self, tx: Transaction, bench: bool, result: &anyhow::Result<ExecutionEffects>) {
+        let entry = TransactionLogEntry {
+            tx,
+            bench,
+            outcome: TransactionLogEntry::outcome_of(result),
+        };
+        // The writer only goes away if it failed to write, which it has logged
+        let _ = self.log_tx.send(entry);
+    }
+}
+
+#[async_trait]
+impl ValidatorProxy for RecordingValidatorProxy {
+    async fn get_object(&self, object_id: ObjectID) -> Result<Object, anyhow::Error> {
+        self.inner.get_object(object_id).await
+    }
+
+    async fn get_owned_objects(
+        &self,
+        account_address: SuiAddress,
+    ) -> Result<Vec<(u64, Object)>, anyhow::Error> {
+        self.inner.get_owned_objects(account_address).await
+    }
+
+    async fn get_latest_system_state_object(&self) -> Result<SuiSystemStateSummary, anyhow::Error> {
+        self.inner.get_latest_system_state_object().await
+    }
+
+    async fn execute_transaction_block(&self, tx: Transaction) -> anyhow::Result<ExecutionEffects> {
+        let result = self.inner.execute_transaction_block(tx.clone()).await;
+        self.record(tx, false, &result);
+        result
+    }
+
+    async fn execute_bench_transaction(&self, tx: Transaction) -> anyhow::Result<ExecutionEffects> {
+        let result = self.inner.execute_bench_transaction(tx.clone()).await;
+        self.record(tx, true, &result);
+        result
+    }
+
+    fn clone_committee(&self) -> Committee {
+        self.inner.clone_committee()
+    }
+
+    fn get_current_epoch(&self) -> EpochId {
+        self.inner.get_current_epoch()
+    }
+
+    fn clone_new(&self) -> Box<dyn ValidatorProxy + Send + Sync> {
+        Box::new(Self {
+            inner: self.inner.clone_new(),
+            log_tx: self.log_tx.clone(),
+        })
+    }
+
+    async fn get_validators(&self) -> Result<Vec<SuiAddress>, anyhow::Error> {
+        self.inner.get_validators().await
+    }
+
+    async fn get_transaction_checkpoint(
+        &self,
+        digest: TransactionDigest,
+    ) -> anyhow::Result<Option<CheckpointSequenceNumber>> {
+        self.inner.get_transaction_checkpoint(digest).await
+    }
+}
+
+/// Read every entry of a log written by `RecordingValidatorProxy`.
+pub fn read_transaction_log(path: &Path) -> anyhow::Result<Vec<TransactionLogEntry>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut entries = vec![];
+    let mut len = [0u8; 4];
+    loop {
+        match reader.read_exact(&mut len) {
+            Ok(()) => (),
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(entries),
+            Err(e) => return Err(e.into()),
+        }
+        let mut bytes = vec![0u8; u32::from_le_bytes(len) as usize];
+        reader.read_exact(&mut bytes)?;
+        entries.push(bcs::from_bytes(&bytes)?);
+    }
+}
+
+/// Submit the transactions of the log at `path` through `proxy` again, one at a time in the order
+/// they were recorded, and return the entries whose outcome differs this time along with that
+/// outcome. Transactions refer to objects at the versions they had when they were recorded, so
+/// the log must be replayed against a cluster started from the same genesis, such as a simtest
+/// run with the same seed, and before anything else is executed on it.
+pub async fn replay_transaction_log(
+    proxy: &(dyn ValidatorProxy + Send + Sync),
+    path: &Path,
+) -> anyhow::Result<Vec<(TransactionLogEntry, Result<String, String>)>> {
+    let entries = read_transaction_log(path)?;
+    info!("Replaying {} transactions from {:?}", entries.len(), path);
+    let mut mismatches = vec![];
+    for entry in entries {
+        let result = if entry.bench {
+            proxy.execute_bench_transaction(entry.tx.clone()).await
+        } else {
+            proxy.execute_transaction_block(entry.tx.clone()).await
+        };
+        let outcome = TransactionLogEntry::outcome_of(&result);
+        if outcome != entry.outcome {
+            error!(
+                tx_digest = ?entry.tx.digest(),
+                "Replayed transaction had outcome {:?}, recorded {:?}",
+                outcome,
+                entry.outcome
+            );
+            mismatches.push((entry, outcome));
+        }
+    }
+    Ok(mismatches)
+}
+
 pub struct FullNodeProxy {
     sui_client: SuiClient,
     committee: Committee,
@@ -745,6 +1443,26 @@ impl ValidatorProxy for FullNodeProxy {
             .active_validators;
         Ok(validators.into_iter().map(|v| v.sui_address).collect())
     }
+
+    async fn get_transaction_checkpoint(
+        &self,
+        digest: TransactionDigest,
+    ) -> anyhow::Result<Option<CheckpointSequenceNumber>> {
+        // A transaction executed through validators is unknown to the full node until it syncs
+        // the checkpoint including it, so a failed lookup only means it is not there yet
+        match self
+            .sui_client
+            .read_api()
+            .get_transaction_with_options(digest, SuiTransactionBlockResponseOptions::new())
+            .await
+        {
+            Ok(response) => Ok(response.checkpoint),
+            Err(err) => {
+                debug!(?digest, "Transaction not found on the full node yet: {err}");
+                Ok(None)
+            }
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
@@ -866,3 +1584,283 @@ pub fn convert_move_call_args(
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use sui_types::crypto::AggregateAuthoritySignature;
+    use sui_types::messages::TransactionEffects;
+    use sui_types::utils::create_fake_transaction;
+
+    /// Fails the first `failures` transactions with the error made by `make_error`
+    struct FaultyProxy {
+        failures: u32,
+        make_error: fn() -> anyhow::Error,
+        attempts: Arc<AtomicU32>,
+    }
+
+    impl FaultyProxy {
+        fn execute(&self) -> anyhow::Result<ExecutionEffects> {
+            if self.attempts.fetch_add(1, Ordering::SeqCst) < self.failures {
+                return Err((self.make_error)());
+            }
+            let sig = AuthorityStrongQuorumSignInfo {
+                epoch: 0,
+                signature: AggregateAuthoritySignature::default(),
+                signers_map: RoaringBitmap::new(),
+            };
+            Ok(ExecutionEffects::CertifiedTransactionEffects(
+                Envelope::new_from_data_and_sig(TransactionEffects::default(), sig),
+                TransactionEvents::default(),
+            ))
+        }
+    }
+
+    #[async_trait]
+    impl ValidatorProxy for FaultyProxy {
+        async fn get_object(&self, _object_id: ObjectID) -> Result<Object, anyhow::Error> {
+            bail!("FaultyProxy has no objects")
+        }
+
+        async fn get_owned_objects(
+            &self,
+            _account_address: SuiAddress,
+        ) -> Result<Vec<(u64, Object)>, anyhow::Error> {
+            bail!("FaultyProxy has no objects")
+        }
+
+        async fn get_latest_system_state_object(
+            &self,
+        ) -> Result<SuiSystemStateSummary, anyhow::Error> {
+            bail!("FaultyProxy has no system state")
+        }
+
+        async fn execute_transaction_block(
+            &self,
+            _tx: Transaction,
+        ) -> anyhow::Result<ExecutionEffects> {
+            self.execute()
+        }
+
+        async fn execute_bench_transaction(
+            &self,
+            _tx: Transaction,
+        ) -> anyhow::Result<ExecutionEffects> {
+            self.execute()
+        }
+
+        fn clone_committee(&self) -> Committee {
+            Committee::new_simple_test_committee().0
+        }
+
+        fn get_current_epoch(&self) -> EpochId {
+            0
+        }
+
+        /// A proxy counting its attempts along with this one, so that failures are not reset
+        fn clone_new(&self) -> Box<dyn ValidatorProxy + Send + Sync> {
+            Box::new(FaultyProxy {
+                failures: self.failures,
+                make_error: self.make_error,
+                attempts: self.attempts.clone(),
+            })
+        }
+
+        async fn get_validators(&self) -> Result<Vec<SuiAddress>, anyhow::Error> {
+            bail!("FaultyProxy has no validators")
+        }
+    }
+
+    fn retry_proxy(
+        failures: u32,
+        make_error: fn() -> anyhow::Error,
+        max_attempts: u32,
+    ) -> (RetryProxy, Arc<AtomicU32>) {
+        let attempts = Arc::new(AtomicU32::new(0));
+        let proxy = RetryProxy::new(
+            Box::new(FaultyProxy {
+                failures,
+                make_error,
+                attempts: attempts.clone(),
+            }),
+            max_attempts,
+            Duration::from_millis(10),
+        );
+        (proxy, attempts)
+    }
+
+    #[tokio::test]
+    async fn test_retry_proxy_retries_transient_errors() {
+        let (proxy, attempts) =
+            retry_proxy(2, || QuorumDriverError::TimeoutBeforeFinality.into(), 3);
+        let tx = create_fake_transaction().into_inner();
+        assert!(proxy.execute_transaction_block(tx).await.is_ok());
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+        // Gives up once out of attempts
+        let (proxy, attempts) =
+            retry_proxy(2, || QuorumDriverError::TimeoutBeforeFinality.into(), 2);
+        let tx = create_fake_transaction().into_inner();
+        assert!(proxy.execute_bench_transaction(tx).await.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_proxy_fails_fast_on_permanent_errors() {
+        let (proxy, attempts) = retry_proxy(
+            2,
+            || {
+                QuorumDriverError::InvalidUserSignature(SuiError::InvalidSignature {
+                    error: "bad signature".to_string(),
+                })
+                .into()
+            },
+            3,
+        );
+        let tx = create_fake_transaction().into_inner();
+        let err = proxy.execute_transaction_block(tx).await.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<QuorumDriverError>(),
+            Some(QuorumDriverError::InvalidUserSignature(_))
+        ));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_submit_batch_returns_results_in_order() {
+        let proxy = FaultyProxy {
+            failures: 1,
+            make_error: || QuorumDriverError::TimeoutBeforeFinality.into(),
+            attempts: Arc::new(AtomicU32::new(0)),
+        };
+        let txs = (0..3)
+            .map(|_| create_fake_transaction().into_inner())
+            .collect();
+        let results = proxy.submit_batch(txs).await;
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_err());
+        assert!(results[1..].iter().all(|result| result.is_ok()));
+    }
+
+    #[tokio::test]
+    async fn test_recording_proxy_log_replays() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("transactions.log");
+        let proxy = RecordingValidatorProxy::new(
+            Box::new(FaultyProxy {
+                failures: 1,
+                make_error: || QuorumDriverError::TimeoutBeforeFinality.into(),
+                attempts: Arc::new(AtomicU32::new(0)),
+            }),
+            &path,
+        )
+        .unwrap();
+        let txs: Vec<_> = (0..2)
+            .map(|_| create_fake_transaction().into_inner())
+            .collect();
+        assert!(proxy
+            .execute_transaction_block(txs[0].clone())
+            .await
+            .is_err());
+        assert!(proxy
+            .execute_bench_transaction(txs[1].clone())
+            .await
+            .is_ok());
+        drop(proxy);
+
+        // Entries are written on another thread
+        let mut entries = vec![];
+        for _ in 0..100 {
+            entries = read_transaction_log(&path).unwrap();
+            if entries.len() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].tx, txs[0]);
+        assert!(!entries[0].bench && entries[0].outcome.is_err());
+        assert_eq!(entries[1].tx, txs[1]);
+        assert!(entries[1].bench && entries[1].outcome.is_ok());
+
+        // Only the transaction that timed out when recorded has a different outcome
+        let proxy = FaultyProxy {
+            failures: 0,
+            make_error: || QuorumDriverError::TimeoutBeforeFinality.into(),
+            attempts: Arc::new(AtomicU32::new(0)),
+        };
+        let mismatches = replay_transaction_log(&proxy, &path).await.unwrap();
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].0, entries[0]);
+        assert!(mismatches[0].1.is_ok());
+    }
+
+    #[test]
+    fn test_quorum_threshold_stake() {
+        let (committee, _) = Committee::new_simple_test_committee();
+        let stake = |threshold: &str| {
+            threshold
+                .parse::<QuorumThreshold>()
+                .unwrap()
+                .stake(&committee)
+        };
+        assert_eq!(stake("validity"), committee.validity_threshold());
+        assert_eq!(stake("quorum"), committee.quorum_threshold());
+        assert_eq!(stake("all"), committee.total_votes());
+        assert!(stake("validity") < stake("quorum") && stake("quorum") < stake("all"));
+        assert_eq!(QuorumThreshold::default(), QuorumThreshold::Quorum);
+        assert!("majority".parse::<QuorumThreshold>().is_err());
+    }
+
+    #[test]
+    fn test_routing_policy_order() {
+        let (committee, _) = Committee::new_simple_test_committee();
+        let names: Vec<AuthorityName> = committee.names().cloned().collect();
+        let ordered = |policy: RoutingPolicy,
+                       latencies: &BTreeMap<AuthorityName, Duration>,
+                       requests: usize| {
+            let mut validators = names.clone();
+            policy.order(&mut validators, latencies, requests);
+            validators
+        };
+        let mut latencies = BTreeMap::new();
+        record_latency(&mut latencies, names[0], Duration::from_millis(20));
+        record_latency(&mut latencies, names[1], Duration::from_millis(10));
+        // A validator which fails fast is still slower than any which replies
+        record_failure(&mut latencies, names[2], Duration::from_millis(1));
+        // names[3] has no sample yet, so it goes first to get measured
+        assert_eq!(
+            ordered(RoutingPolicy::LowestLatencyFirst, &latencies, 0),
+            vec![names[3], names[1], names[0], names[2]]
+        );
+        assert_eq!(ordered(RoutingPolicy::Broadcast, &latencies, 0), names);
+        assert_eq!(
+            ordered(RoutingPolicy::RoundRobin, &latencies, 5),
+            vec![names[1], names[2], names[3], names[0]]
+        );
+        let mut random = ordered(RoutingPolicy::Random, &latencies, 0);
+        random.sort();
+        let mut sorted = names.clone();
+        sorted.sort();
+        assert_eq!(random, sorted);
+    }
+
+    #[test]
+    fn test_quorum_prefix() {
+        let (committee, _) = Committee::new_simple_test_committee();
+        let names: Vec<AuthorityName> = committee.names().cloned().collect();
+        let prefix = |threshold: QuorumThreshold| {
+            quorum_prefix(&committee, &names, threshold.stake(&committee))
+        };
+        // 4 validators of equal stake, f = 1
+        assert_eq!(prefix(QuorumThreshold::Validity), 2);
+        assert_eq!(prefix(QuorumThreshold::Quorum), 3);
+        assert_eq!(prefix(QuorumThreshold::All), 4);
+        // Not enough stake left after the first validators: contact all of them
+        assert_eq!(
+            quorum_prefix(&committee, &names[..2], committee.quorum_threshold()),
+            2
+        );
+    }
+}
@@ -2,10 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use clap::*;
+use std::path::PathBuf;
 
 use strum_macros::EnumString;
 
-use crate::drivers::Interval;
+use crate::drivers::{Interval, RampSchedule};
+use crate::system_state_observer::GasPriceRange;
+use crate::{QuorumThreshold, RoutingPolicy};
 
 #[derive(Parser)]
 #[clap(name = "Stress Testing Framework")]
@@ -68,6 +71,19 @@ pub struct Opts {
     /// Otherwise use EmbeddedReconfigObserver,
     #[clap(long, parse(try_from_str), default_value = "false", global = true)]
     pub use_fullnode_for_reconfig: bool,
+    /// Order in which LocalValidatorAggregatorProxy
+    /// contacts validators, one of "broadcast",
+    /// "lowest-latency-first", "round-robin" or
+    /// "random". Anything but "broadcast" only
+    /// contacts as many validators as a quorum needs
+    #[clap(long, default_value = "broadcast", global = true)]
+    pub routing_policy: RoutingPolicy,
+    /// Stake LocalValidatorAggregatorProxy waits
+    /// for before returning, one of "validity",
+    /// "quorum" or "all". Votes always wait for a
+    /// quorum, which the certificate needs
+    #[clap(long, default_value = "quorum", global = true)]
+    pub quorum_threshold: QuorumThreshold,
     /// Default workload is 100% transfer object
     #[clap(subcommand)]
     pub run_spec: RunSpec,
@@ -86,14 +102,87 @@ pub struct Opts {
     /// until terminated with a ctrl-c. However,
     /// if we wanted to run the test for
     /// 60 seconds, this could be set as "60s".
-    /// And if we wanted to run the test for
-    /// 10,000 transactions we could set it to
-    /// "10000"
+    /// And if we wanted to run the test until
+    /// 10,000 transactions succeed we could set
+    /// it to "10000"
     #[clap(long, global = true, default_value = "unbounded")]
     pub run_duration: Interval,
+    /// Vary the total offered load over the run instead
+    /// of holding it at the target qps, given as
+    /// comma separated "<duration>:<qps>" points, e.g.
+    /// "0s:100,5m:2000". The qps is interpolated
+    /// linearly between points and latency is sampled
+    /// against it every stat collection interval.
+    #[clap(long, global = true)]
+    pub ramp: Option<RampSchedule>,
+    /// Gas price to send workload transactions with
+    /// instead of the reference gas price, either a
+    /// single price, e.g. "1000", or a range to pick
+    /// from at random, e.g. "1000-5000"
+    #[clap(long, global = true)]
+    pub gas_price: Option<GasPriceRange>,
+    /// Make workload transactions expire this many
+    /// epochs after the one they are created in,
+    /// instead of never
+    #[clap(long, global = true)]
+    pub expiration_epochs: Option<u64>,
+    /// Cap on the number of transactions in flight, which
+    /// is otherwise target_qps * in_flight_ratio. Lets
+    /// concurrency be held fixed while sweeping the qps.
+    /// Payloads are still pre-generated for the full
+    /// target_qps * in_flight_ratio
+    #[clap(long, global = true)]
+    pub max_in_flight: Option<usize>,
+    /// Target median latency in milliseconds. When set,
+    /// each worker lowers its request rate while latency
+    /// is above the target and raises it back to the
+    /// target qps while below, instead of sending at a
+    /// fixed rate however slow the cluster gets
+    #[clap(long, global = true)]
+    pub backpressure_latency_ms: Option<u64>,
+    /// Seconds to wait for transactions still in flight
+    /// when the run ends. The ones that have not completed
+    /// by then are reported as abandoned, not as errors
+    #[clap(long, default_value = "30", global = true)]
+    pub drain_timeout_secs: u64,
+    /// Fraction of successful transactions, from 0.0
+    /// to 1.0, whose effects are checked against what
+    /// the transaction should have done. Mismatches are
+    /// reported as semantic failures
+    #[clap(long, default_value = "0.0", global = true)]
+    pub verify_effects_rate: f64,
+    /// End the run at the first transaction error instead
+    /// of retrying, after waiting for the transactions in
+    /// flight, and report the stats up to then
+    #[clap(long, global = true)]
+    pub fail_fast: bool,
     /// Path where benchmark stats is stored
     #[clap(long, default_value = "/tmp/bench_result", global = true)]
     pub benchmark_stats_path: String,
+    /// Path of a CSV file to write the submit and confirm
+    /// times, latency, workload and outcome of every
+    /// completed transaction to
+    #[clap(long, global = true)]
+    pub record_path: Option<PathBuf>,
+    /// Endpoint of an OpenTelemetry collector, e.g.
+    /// http://localhost:4317, to export a span of every
+    /// completed transaction to over OTLP
+    #[clap(long, global = true)]
+    pub otel_endpoint: Option<String>,
+    /// Look every successful transaction up until it is in
+    /// a checkpoint, to report the time to finality next to
+    /// the latency to the effects certificate. Only full
+    /// nodes can look checkpoints up, so this needs
+    /// --use-fullnode-for-execution, and adds load on them
+    #[clap(long, global = true)]
+    pub track_finality: bool,
+    /// Path of a TOML, or JSON with a .json extension,
+    /// table from the hex public key of each validator
+    /// to its region, e.g. "k#8e1f..." = "us-east-1".
+    /// Latency is then also reported per region of the
+    /// validators which formed each effects quorum
+    #[clap(long, global = true)]
+    pub validator_regions: Option<PathBuf>,
     /// Path where previous benchmark stats is stored to use for comparison
     #[clap(long, default_value = "", global = true)]
     pub compare_with: String,
@@ -146,6 +235,60 @@ pub enum RunSpec {
         // relative weight of adversarial transactions in the benchmark workload
         #[clap(long, default_value = "0")]
         adversarial: u32,
+        // relative weight of nft mint and transfer transactions in the benchmark workload
+        #[clap(long, default_value = "0")]
+        nft: u32,
+        // relative weight of package publish transactions in the benchmark workload
+        #[clap(long, default_value = "0")]
+        publish: u32,
+        // relative weight of read-only queries to a fullnode in the benchmark workload,
+        // which are sent to the first of `fullnode_rpc_addresses`
+        #[clap(long, default_value = "0")]
+        fullnode_query: u32,
+        // relative weight of coin split and merge transactions in the benchmark workload
+        #[clap(long, default_value = "0")]
+        coin_ops: u32,
+        // relative weight of transactions from the workload sending deliberately invalid
+        // transactions, which validators are expected to reject
+        #[clap(long, default_value = "0")]
+        invalid_transaction: u32,
+        // relative weight of transactions wrapping an object into another object and
+        // unwrapping it back out in the benchmark workload
+        #[clap(long, default_value = "0")]
+        wrap_unwrap: u32,
+        // relative weight of transactions splitting a coin into many new coins and merging
+        // them back, which deletes them, in the benchmark workload
+        #[clap(long, default_value = "0")]
+        gas_fanout: u32,
+        // relative weight of transactions merging many coins and splitting them back, which
+        // take many owned objects as inputs, in the benchmark workload
+        #[clap(long, default_value = "0")]
+        multi_input: u32,
+        // relative weight of transactions reading the Clock, a shared object written by every
+        // consensus commit, in the benchmark workload
+        #[clap(long, default_value = "0")]
+        clock_read: u32,
+        // relative weight of transactions upgrading a package, each payload publishing a package
+        // of its own first, in the benchmark workload
+        #[clap(long, default_value = "0")]
+        upgrade: u32,
+        // relative weight of transactions adding dynamic fields to an object and removing them,
+        // which creates and loads as many child objects, in the benchmark workload
+        #[clap(long, default_value = "0")]
+        dynamic_field: u32,
+        // relative weight of transactions publishing a package, each followed once confirmed by
+        // a transaction minting an NFT from that package, in the benchmark workload
+        #[clap(long, default_value = "0")]
+        publish_mint: u32,
+        // relative weight of transactions spending a version of their gas coin which the
+        // previous transaction already spent, which validators must reject, in the benchmark
+        // workload
+        #[clap(long, default_value = "0")]
+        equivocation: u32,
+        // relative weight of transactions of sizes drawn from `size_mix_distribution` in the
+        // benchmark workload
+        #[clap(long, default_value = "0")]
+        size_mix: u32,
 
         // --- workload-specific options --- (TODO: use subcommands or similar)
         // 100 for max hotness i.e all requests target
@@ -156,15 +299,67 @@ pub enum RunSpec {
         // total_shared_counters = max(1, qps * (1.0 - hotness/100.0))
         #[clap(long, default_value = "50")]
         shared_counter_hotness_factor: u32,
+        // percentage of shared counter transactions which all target the same hot counter,
+        // the rest are spread over all counters. 0 for no contention, 100 for all requests
+        // targeting a single counter
+        #[clap(long, default_value = "0")]
+        shared_counter_contention: u32,
         // batch size use for batch payment workload
         #[clap(long, default_value = "15")]
         batch_payment_size: u32,
         // type and load % of adversarial transactions in the benchmark workload.
-        // Format is "{adversarial_type}-{load_factor}".
+        // Format is "{adversarial_type}-{load_factor}", optionally followed by "-{expired_fraction}".
         // `load_factor` is a number between 0.0 and 1.0 which dictates how much load per tx
+        // `expired_fraction` is the fraction of txs sent already expired, which must be rejected
         // Default is (0-0.5) implying random load at 50% load. See `AdversarialPayloadType` enum for `adversarial_type`
         #[clap(long, default_value = "0-1.0")]
         adversarial_cfg: String,
+        // directory of compiled modules (`.mv` files) published by the publish workload,
+        // in file name order. A small bundled package is published if not set
+        #[clap(long)]
+        publish_package_dir: Option<PathBuf>,
+        // max number of parts the coin ops workload splits a coin into, each split picks
+        // a random number of parts between 2 and this
+        #[clap(long, default_value = "4")]
+        coin_ops_split_count: u64,
+        // percentage of the invalid transaction workload's transactions which are made
+        // invalid, the rest are valid transfers
+        #[clap(long, default_value = "50")]
+        invalid_transaction_fraction: u32,
+        // number of coins each split of the gas fanout workload creates, and the next
+        // transaction deletes
+        #[clap(long, default_value = "64")]
+        gas_fanout_width: u64,
+        // number of owned coins, besides the gas coin, each transaction of the multi input
+        // workload takes as inputs
+        #[clap(long, default_value = "16")]
+        multi_input_inputs_per_tx: u64,
+        // number of dynamic fields each transaction of the dynamic field workload adds to its
+        // parent object, or removes from it
+        #[clap(long, default_value = "16")]
+        dynamic_field_count: u64,
+        // percentage of the equivocation workload's transactions which conflict with the
+        // previous transaction of their sender, the rest are valid transfers
+        #[clap(long, default_value = "50")]
+        equivocation_conflict_fraction: u32,
+        // sizes in bytes of the argument of the size mix workload's transactions, each with a
+        // relative weight. Format is "{bytes}:{weight},...", a size of 0 sends a plain transfer
+        #[clap(long, default_value = "0:80,4096:20")]
+        size_mix_distribution: String,
+        // TOML or JSON file of workload weights and parameters, see `WorkloadMix`. When set
+        // it replaces the workload weight and workload-specific options above
+        #[clap(long)]
+        workload_mix: Option<PathBuf>,
+        // TOML or JSON file of phases, each a duration in seconds and a workload mix, which are
+        // run one after the other and repeated until the end of the run, see `WorkloadSchedule`.
+        // Phases may only differ in their weights
+        #[clap(long, conflicts_with = "workload_mix")]
+        workload_schedule: Option<PathBuf>,
+        // seed of the random choices the workloads make, e.g. which shared counter or
+        // recipient a payload uses, so that they can be repeated. A random seed is used and
+        // logged if unset. Overrides the seed of a workload mix file
+        #[clap(long)]
+        workload_rng_seed: Option<u64>,
 
         // --- generic options ---
         // Target qps
@@ -173,7 +368,11 @@ pub enum RunSpec {
         // Number of workers
         #[clap(long, default_value = "12", global = true)]
         num_workers: u64,
-        // Max in-flight ratio
+        // Max in-flight ratio. Every workload creates its gas
+        // coins and payloads up front for target_qps * in_flight_ratio
+        // transactions, so at high qps lowering this trades headroom
+        // for slow transactions (which otherwise stall on a drained
+        // payload pool) for less setup time and memory
         #[clap(long, default_value = "5", global = true)]
         in_flight_ratio: u64,
     },
@@ -2,9 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::ValidatorProxy;
+use rand::Rng;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
+use sui_types::committee::EpochId;
+use sui_types::messages::TransactionExpiration;
 use tokio::sync::oneshot::Sender;
 use tokio::sync::watch;
 use tokio::sync::watch::Receiver;
@@ -14,14 +18,59 @@ use tracing::{error, info};
 
 #[derive(Debug, Clone)]
 pub struct SystemState {
+    pub epoch: EpochId,
     pub reference_gas_price: u64,
     pub protocol_config: Option<ProtocolConfig>,
 }
 
+/// Gas prices benchmark transactions are sent with instead of the reference gas price, each
+/// transaction is priced uniformly at random in `min..=max`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GasPriceRange {
+    pub min: u64,
+    pub max: u64,
+}
+
+impl GasPriceRange {
+    pub fn new(min: u64, max: u64) -> Result<Self, String> {
+        if min == 0 || min > max {
+            return Err(format!("Invalid gas price range {min}-{max}"));
+        }
+        Ok(Self { min, max })
+    }
+
+    pub fn sample(&self) -> u64 {
+        rand::thread_rng().gen_range(self.min..=self.max)
+    }
+}
+
+impl FromStr for GasPriceRange {
+    type Err = String;
+
+    /// Parses a single price, e.g. "1000", or an inclusive range, e.g. "1000-2000"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |price: &str| {
+            price
+                .trim()
+                .parse::<u64>()
+                .map_err(|e| format!("Invalid gas price {price:?}: {e}"))
+        };
+        match s.split_once('-') {
+            Some((min, max)) => GasPriceRange::new(parse(min)?, parse(max)?),
+            None => {
+                let price = parse(s)?;
+                GasPriceRange::new(price, price)
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct SystemStateObserver {
     pub state: Receiver<SystemState>,
     pub _sender: Sender<()>,
+    gas_price: Option<GasPriceRange>,
+    expiration_epochs_ahead: Option<u64>,
 }
 
 impl SystemStateObserver {
@@ -30,6 +79,7 @@ impl SystemStateObserver {
         let mut interval = tokio::time::interval_at(Instant::now(), Duration::from_secs(60));
         interval.set_missed_tick_behavior(time::MissedTickBehavior::Skip);
         let (tx, rx) = watch::channel(SystemState {
+            epoch: 0,
             reference_gas_price: 1u64,
             protocol_config: None,
         });
@@ -40,7 +90,7 @@ impl SystemStateObserver {
                         match proxy.get_latest_system_state_object().await {
                             Ok(result) => {
                                 let p = ProtocolConfig::get_for_version(ProtocolVersion::new(result.protocol_version));
-                                if tx.send(SystemState {reference_gas_price: result.reference_gas_price,protocol_config: Some(p)}).is_ok() {
+                                if tx.send(SystemState {epoch: result.epoch, reference_gas_price: result.reference_gas_price,protocol_config: Some(p)}).is_ok() {
                                     info!("Reference gas price = {:?}", result.reference_gas_price    );
                                 }
                             }
@@ -56,6 +106,43 @@ impl SystemStateObserver {
         Self {
             state: rx,
             _sender: sender,
+            gas_price: None,
+            expiration_epochs_ahead: None,
+        }
+    }
+
+    /// Price workload transactions within `gas_price` rather than at the reference gas price.
+    /// Prices below the reference gas price are rejected by validators.
+    pub fn with_gas_price(mut self, gas_price: GasPriceRange) -> Self {
+        self.gas_price = Some(gas_price);
+        self
+    }
+
+    /// Make workload transactions expire `epochs_ahead` epochs after the one they are created in,
+    /// rather than never: validators sign them up to and including that epoch. The epoch is the
+    /// one last observed, which lags the network's for up to a minute after reconfiguration, so
+    /// zero epochs ahead may see transactions rejected at epoch boundaries.
+    pub fn with_expiration(mut self, epochs_ahead: u64) -> Self {
+        self.expiration_epochs_ahead = Some(epochs_ahead);
+        self
+    }
+
+    /// Expiration to set on the next workload transaction. Only workloads which build their
+    /// transaction data in this crate set it, not those signed by `test_utils` helpers.
+    pub fn expiration(&self) -> TransactionExpiration {
+        match self.expiration_epochs_ahead {
+            Some(epochs_ahead) => {
+                TransactionExpiration::Epoch(self.state.borrow().epoch + epochs_ahead)
+            }
+            None => TransactionExpiration::None,
+        }
+    }
+
+    /// Gas price to set on the next workload transaction
+    pub fn gas_price(&self) -> u64 {
+        match &self.gas_price {
+            Some(range) => range.sample(),
+            None => self.state.borrow().reference_gas_price,
         }
     }
 }
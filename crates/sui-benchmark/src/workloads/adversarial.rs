@@ -5,11 +5,11 @@ use super::{
     workload::{Workload, WorkloadBuilder, MAX_GAS_FOR_TESTING},
     WorkloadBuilderInfo, WorkloadParams,
 };
-use crate::in_memory_wallet::move_call_pt_impl;
 use crate::in_memory_wallet::InMemoryWallet;
+use crate::in_memory_wallet::{move_call_pt_data, move_call_pt_impl};
 use crate::system_state_observer::{SystemState, SystemStateObserver};
 use crate::workloads::payload::Payload;
-use crate::workloads::{Gas, GasCoinConfig};
+use crate::workloads::{workload_target_qps, Gas, GasCoinConfig};
 use crate::ProgrammableTransactionBuilder;
 use crate::{convert_move_call_args, BenchMoveCallArg, ExecutionEffects, ValidatorProxy};
 use anyhow::anyhow;
@@ -26,7 +26,9 @@ use strum::{EnumCount, IntoEnumIterator};
 use strum_macros::{EnumCount as EnumCountMacro, EnumIter};
 use sui_protocol_config::ProtocolConfig;
 use sui_types::messages::Command;
-use sui_types::messages::{CallArg, ObjectArg, TransactionEffectsAPI};
+use sui_types::messages::{
+    CallArg, ObjectArg, TransactionDataAPI, TransactionEffectsAPI, TransactionExpiration,
+};
 use sui_types::{base_types::ObjectID, object::Owner};
 use sui_types::{base_types::SuiAddress, crypto::get_key_pair, messages::VerifiedTransaction};
 use sui_types::{
@@ -36,7 +38,7 @@ use sui_types::{
 use sui_types::{messages::TransactionData, utils::to_sender_signed_transaction};
 use tracing::debug;
 
-use test_utils::messages::create_publish_move_package_transaction;
+use test_utils::messages::{compile_move_package, create_publish_move_package_transaction};
 /// Number of vectors to create in LargeTransientRuntimeVectors workload
 const NUM_VECTORS: u64 = 1_000;
 
@@ -118,6 +120,8 @@ pub struct AdversarialTestPayload {
     state: InMemoryWallet,
     system_state_observer: Arc<SystemStateObserver>,
     adversarial_payload_cfg: AdversarialPayloadCfg,
+    /// Whether the last transaction made had already expired
+    expired: bool,
 }
 
 impl std::fmt::Display for AdversarialTestPayload {
@@ -130,12 +134,30 @@ impl std::fmt::Display for AdversarialTestPayload {
 pub struct AdversarialPayloadCfg {
     pub payload_type: AdversarialPayloadType,
     pub load_factor: f32,
+    /// Fraction of the transactions sent with an expiration epoch which has already passed, for
+    /// validators to reject, between 0.0 and 1.0
+    pub expired_fraction: f32,
 }
 
 impl FromStr for AdversarialPayloadCfg {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // An optional third number is the fraction of expired transactions
+        if let [payload_type, load_factor, expired_fraction] = s.split('-').collect_vec()[..] {
+            let expired_fraction = expired_fraction
+                .parse::<f32>()
+                .map_err(|_| anyhow!("invalid expired fraction"))?;
+            if !(0.0..=1.0).contains(&expired_fraction) {
+                return Err(anyhow!(
+                    "invalid expired fraction. Valid range is [0.0, 1.0]"
+                ));
+            }
+            return Ok(AdversarialPayloadCfg {
+                expired_fraction,
+                ..AdversarialPayloadCfg::from_str(&format!("{payload_type}-{load_factor}"))?
+            });
+        }
         // Matches regex for two numbers delimited by a hyphen, where the left number must be positive
         // and the right number must be a float between 0.0 inclusive and 1.0 inclusive
         let re = Regex::new(
@@ -156,6 +178,7 @@ impl FromStr for AdversarialPayloadCfg {
         Ok(AdversarialPayloadCfg {
             payload_type,
             load_factor,
+            expired_fraction: 0.0,
         })
     }
 }
@@ -180,16 +203,31 @@ impl Payload for AdversarialTestPayload {
 
     fn make_transaction(&mut self) -> VerifiedTransaction {
         let payload_type = self.adversarial_payload_cfg.payload_type;
-
-        self.create_transaction(
-            &payload_type,
-            self.system_state_observer
-                .state
-                .borrow()
-                .protocol_config
-                .as_ref()
-                .expect("Protocol config not in system state"),
-        )
+        let (epoch, mut data) = {
+            let state = self.system_state_observer.state.borrow();
+            let data = self.create_transaction_data(
+                &payload_type,
+                state
+                    .protocol_config
+                    .as_ref()
+                    .expect("Protocol config not in system state"),
+            );
+            (state.epoch, data)
+        };
+        // Nothing can have expired before the first epoch ends
+        let expired_epoch = epoch.checked_sub(1).filter(|_| {
+            rand::thread_rng().gen_bool(self.adversarial_payload_cfg.expired_fraction as f64)
+        });
+        self.expired = expired_epoch.is_some();
+        *data.expiration_mut_for_testing() = match expired_epoch {
+            Some(expired_epoch) => TransactionExpiration::Epoch(expired_epoch),
+            None => self.system_state_observer.expiration(),
+        };
+        let account = self.state.account(&self.sender).unwrap();
+        to_sender_signed_transaction(data, account.key())
+    }
+    fn expects_rejection(&self) -> bool {
+        self.expired
     }
 }
 
@@ -201,20 +239,16 @@ impl AdversarialTestPayload {
         x as u64
     }
 
-    fn create_transaction(
+    fn create_transaction_data(
         &self,
         payload_type: &AdversarialPayloadType,
         protocol_config: &ProtocolConfig,
-    ) -> VerifiedTransaction {
+    ) -> TransactionData {
         let args = self.get_payload_args(payload_type, protocol_config);
         let module_name = "adversarial";
         let account = self.state.account(&self.sender).unwrap();
         let gas_budget = protocol_config.max_tx_gas();
-        let gas_price = self
-            .system_state_observer
-            .state
-            .borrow()
-            .reference_gas_price;
+        let gas_price = self.system_state_observer.gas_price();
         match payload_type {
             AdversarialPayloadType::MaxReads => {
                 let mut builder = ProgrammableTransactionBuilder::new();
@@ -231,35 +265,36 @@ impl AdversarialTestPayload {
                     vec![],
                     vec![],
                 ));
-                let data = TransactionData::new_programmable(
+                TransactionData::new_programmable(
                     self.sender,
                     vec![account.gas],
                     builder.finish(),
                     gas_budget,
                     gas_price,
-                );
-                to_sender_signed_transaction(data, account.key())
+                )
             }
             AdversarialPayloadType::MaxPackagePublish => {
                 let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
                 path.push("src/workloads/data/max_package");
+                let (all_module_bytes, dependencies) = compile_move_package(&path);
 
-                create_publish_move_package_transaction(
-                    account.gas,
-                    path,
+                TransactionData::new_module(
                     self.sender,
-                    account.key(),
+                    account.gas,
+                    all_module_bytes,
+                    dependencies,
                     gas_price * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
                     gas_price,
                 )
             }
-            _ => self.state.move_call_pt(
+            _ => move_call_pt_data(
                 self.sender,
                 self.package_id,
                 module_name,
                 &args.fn_name,
                 vec![],
                 args.args,
+                &account.gas,
                 gas_budget,
                 gas_price,
             ),
@@ -386,9 +421,10 @@ impl WorkloadBuilder<dyn Payload> for AdversarialWorkloadBuilder {
         payload_gas: Vec<Gas>,
     ) -> Box<dyn Workload<dyn Payload>> {
         debug!(
-            "Using `{:?}` adversarial workloads at {}% load factor",
+            "Using `{:?}` adversarial workloads at {}% load factor, {}% expired",
             self.adversarial_payload_cfg.payload_type,
-            self.adversarial_payload_cfg.load_factor * 100.0
+            self.adversarial_payload_cfg.load_factor * 100.0,
+            self.adversarial_payload_cfg.expired_fraction * 100.0
         );
 
         Box::<dyn Workload<dyn Payload>>::from(Box::new(AdversarialWorkload {
@@ -414,7 +450,7 @@ impl AdversarialWorkloadBuilder {
         in_flight_ratio: u64,
         adversarial_payload_cfg: AdversarialPayloadCfg,
     ) -> Option<WorkloadBuilderInfo> {
-        let target_qps = (workload_weight * target_qps as f32) as u64;
+        let target_qps = workload_target_qps(workload_weight, target_qps);
         let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
         let max_ops = target_qps * in_flight_ratio;
         if max_ops == 0 || num_workers == 0 {
@@ -455,6 +491,12 @@ pub struct AdversarialWorkload {
 
 #[async_trait]
 impl Workload<dyn Payload> for AdversarialWorkload {
+    fn name(&self) -> &'static str {
+        "adversarial"
+    }
+    fn senders(&self) -> Vec<SuiAddress> {
+        self.payload_gas.iter().map(|gas| gas.1).collect()
+    }
     async fn init(
         &mut self,
         proxy: Arc<dyn ValidatorProxy + Sync + Send>,
@@ -466,6 +508,7 @@ impl Workload<dyn Payload> for AdversarialWorkload {
         let SystemState {
             reference_gas_price,
             protocol_config,
+            ..
         } = system_state_observer.state.borrow().clone();
         let protocol_config = protocol_config.unwrap();
         let gas_budget = protocol_config.max_tx_gas();
@@ -558,6 +601,7 @@ impl Workload<dyn Payload> for AdversarialWorkload {
                 state: InMemoryWallet::new(gas),
                 system_state_observer: system_state_observer.clone(),
                 adversarial_payload_cfg: self.adversarial_payload_cfg,
+                expired: false,
             })
         }
         payloads
@@ -6,7 +6,9 @@ use crate::system_state_observer::SystemStateObserver;
 use crate::workloads::payload::Payload;
 use crate::workloads::workload::{Workload, STORAGE_COST_PER_COIN};
 use crate::workloads::workload::{WorkloadBuilder, ESTIMATED_COMPUTATION_COST};
-use crate::workloads::{Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams};
+use crate::workloads::{
+    workload_target_qps, Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams,
+};
 use crate::{ExecutionEffects, ValidatorProxy};
 use async_trait::async_trait;
 use std::collections::HashMap;
@@ -107,10 +109,7 @@ impl Payload for BatchPaymentTestPayload {
             vec![amount; num_recipients],
             sender,
             &self.state.keypair(&sender).unwrap(),
-            self.system_state_observer
-                .state
-                .borrow()
-                .reference_gas_price,
+            self.system_state_observer.gas_price(),
             gas_budget,
         )
     }
@@ -130,7 +129,7 @@ impl BatchPaymentWorkloadBuilder {
         in_flight_ratio: u64,
         batch_size: u32,
     ) -> Option<WorkloadBuilderInfo> {
-        let target_qps = (workload_weight * target_qps as f32) as u64;
+        let target_qps = workload_target_qps(workload_weight, target_qps);
         let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
         let max_ops = target_qps * in_flight_ratio;
         if max_ops == 0 || num_workers == 0 {
@@ -202,6 +201,12 @@ pub struct BatchPaymentWorkload {
 
 #[async_trait]
 impl Workload<dyn Payload> for BatchPaymentWorkload {
+    fn name(&self) -> &'static str {
+        "batch_payment"
+    }
+    fn senders(&self) -> Vec<SuiAddress> {
+        self.payload_gas.iter().map(|gas| gas.1).collect()
+    }
     async fn init(
         &mut self,
         _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
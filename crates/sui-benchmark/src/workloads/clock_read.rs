@@ -0,0 +1,177 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::in_memory_wallet::move_call_pt_data;
+use crate::system_state_observer::SystemStateObserver;
+use crate::workloads::payload::Payload;
+use crate::workloads::workload::{
+    Workload, WorkloadBuilder, ESTIMATED_COMPUTATION_COST, MAX_GAS_FOR_TESTING,
+};
+use crate::workloads::{
+    workload_target_qps, Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams,
+};
+use crate::{BenchMoveCallArg, ExecutionEffects, ValidatorProxy};
+use async_trait::async_trait;
+use std::sync::Arc;
+use sui_types::{
+    base_types::SuiAddress,
+    clock::CLOCK_MODULE_NAME,
+    crypto::{get_key_pair, AccountKeyPair},
+    messages::{TransactionDataAPI, VerifiedTransaction, TEST_ONLY_GAS_UNIT_FOR_GENERIC},
+    utils::to_sender_signed_transaction,
+    SUI_CLOCK_OBJECT_ID, SUI_CLOCK_OBJECT_SHARED_VERSION, SUI_FRAMEWORK_OBJECT_ID,
+};
+use tracing::{error, info};
+
+/// Reads the `0x6` Clock, which every consensus commit writes, so that every transaction goes
+/// through consensus and is sequenced after the commit's clock update. The Clock is only taken by
+/// immutable reference, so these transactions do not contend with each other for it, unlike
+/// shared counter increments: their latency is that of ordering alone, however many are in flight.
+#[derive(Debug)]
+pub struct ClockReadTestPayload {
+    gas: Gas,
+    system_state_observer: Arc<SystemStateObserver>,
+}
+
+impl std::fmt::Display for ClockReadTestPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "clock_read")
+    }
+}
+
+impl Payload for ClockReadTestPayload {
+    fn make_new_payload(&mut self, effects: &ExecutionEffects) {
+        if !effects.is_ok() {
+            effects.print_gas_summary();
+            error!("Clock read tx failed...");
+        }
+        self.gas.0 = effects.gas_object().0;
+    }
+    fn make_transaction(&mut self) -> VerifiedTransaction {
+        let gas_price = self.system_state_observer.gas_price();
+        let mut data = move_call_pt_data(
+            self.gas.1,
+            SUI_FRAMEWORK_OBJECT_ID,
+            CLOCK_MODULE_NAME.as_str(),
+            "timestamp_ms",
+            vec![],
+            vec![BenchMoveCallArg::Shared((
+                SUI_CLOCK_OBJECT_ID,
+                SUI_CLOCK_OBJECT_SHARED_VERSION,
+                false,
+            ))],
+            &self.gas.0,
+            gas_price * TEST_ONLY_GAS_UNIT_FOR_GENERIC,
+            gas_price,
+        );
+        *data.expiration_mut_for_testing() = self.system_state_observer.expiration();
+        to_sender_signed_transaction(data, &self.gas.2)
+    }
+    fn verify_effects(&self, effects: &ExecutionEffects) -> Result<(), String> {
+        if !effects.is_ok() {
+            return Err(format!("clock read failed with {}", effects.status()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct ClockReadWorkloadBuilder {
+    num_payloads: u64,
+}
+
+impl ClockReadWorkloadBuilder {
+    pub fn from(
+        workload_weight: f32,
+        target_qps: u64,
+        num_workers: u64,
+        in_flight_ratio: u64,
+    ) -> Option<WorkloadBuilderInfo> {
+        let target_qps = workload_target_qps(workload_weight, target_qps);
+        let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
+        let max_ops = target_qps * in_flight_ratio;
+        if max_ops == 0 || num_workers == 0 {
+            None
+        } else {
+            let workload_params = WorkloadParams {
+                target_qps,
+                num_workers,
+                max_ops,
+            };
+            let workload_builder =
+                Box::<dyn WorkloadBuilder<dyn Payload>>::from(Box::new(ClockReadWorkloadBuilder {
+                    num_payloads: max_ops,
+                }));
+            let builder_info = WorkloadBuilderInfo {
+                workload_params,
+                workload_builder,
+            };
+            Some(builder_info)
+        }
+    }
+}
+
+#[async_trait]
+impl WorkloadBuilder<dyn Payload> for ClockReadWorkloadBuilder {
+    async fn generate_coin_config_for_init(&self) -> Vec<GasCoinConfig> {
+        vec![]
+    }
+    async fn generate_coin_config_for_payloads(&self) -> Vec<GasCoinConfig> {
+        let amount = MAX_GAS_FOR_TESTING + ESTIMATED_COMPUTATION_COST;
+        (0..self.num_payloads)
+            .map(|_| {
+                let (address, keypair) = get_key_pair::<AccountKeyPair>();
+                GasCoinConfig {
+                    amount,
+                    address,
+                    keypair: Arc::new(keypair),
+                }
+            })
+            .collect()
+    }
+    async fn build(
+        &self,
+        _init_gas: Vec<Gas>,
+        payload_gas: Vec<Gas>,
+    ) -> Box<dyn Workload<dyn Payload>> {
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(ClockReadWorkload { payload_gas }))
+    }
+}
+
+#[derive(Debug)]
+pub struct ClockReadWorkload {
+    pub payload_gas: Vec<Gas>,
+}
+
+#[async_trait]
+impl Workload<dyn Payload> for ClockReadWorkload {
+    fn name(&self) -> &'static str {
+        "clock_read"
+    }
+    fn senders(&self) -> Vec<SuiAddress> {
+        self.payload_gas.iter().map(|gas| gas.1).collect()
+    }
+    async fn init(
+        &mut self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        _system_state_observer: Arc<SystemStateObserver>,
+    ) {
+    }
+    async fn make_test_payloads(
+        &self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) -> Vec<Box<dyn Payload>> {
+        info!("Creating clock read txn payloads, hang tight..");
+        self.payload_gas
+            .iter()
+            .map(|gas| {
+                Box::new(ClockReadTestPayload {
+                    gas: gas.clone(),
+                    system_state_observer: system_state_observer.clone(),
+                })
+            })
+            .map(|b| Box::<dyn Payload>::from(b))
+            .collect()
+    }
+}
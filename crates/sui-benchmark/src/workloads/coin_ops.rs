@@ -0,0 +1,253 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::in_memory_wallet::move_call_pt_impl;
+use crate::system_state_observer::SystemStateObserver;
+use crate::workloads::payload::Payload;
+use crate::workloads::workload::{
+    Workload, WorkloadBuilder, ESTIMATED_COMPUTATION_COST, MAX_GAS_FOR_TESTING,
+};
+use crate::workloads::{
+    workload_target_qps, Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams,
+};
+use crate::{BenchMoveCallArg, ExecutionEffects, ValidatorProxy};
+use async_trait::async_trait;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use std::sync::Arc;
+use sui_types::{
+    base_types::{ObjectRef, SuiAddress},
+    coin,
+    crypto::{get_key_pair, AccountKeyPair},
+    gas_coin::GAS,
+    messages::{VerifiedTransaction, TEST_ONLY_GAS_UNIT_FOR_GENERIC},
+    object::Owner,
+    SUI_FRAMEWORK_OBJECT_ID,
+};
+use tracing::{error, info};
+
+/// Alternates between splitting a coin into a random number of parts and merging the parts back.
+#[derive(Debug)]
+pub struct CoinOpsTestPayload {
+    /// The coin being split and merged, never used to pay for gas
+    coin: ObjectRef,
+    /// Coins created by the previous split, to be merged back into `coin` by the next transaction
+    split_coins: Vec<ObjectRef>,
+    /// Upper bound on the number of parts `coin` is split into
+    split_count: u64,
+    /// Picks the number of parts of each split
+    rng: StdRng,
+    gas: Gas,
+    system_state_observer: Arc<SystemStateObserver>,
+}
+
+impl std::fmt::Display for CoinOpsTestPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "coin_ops")
+    }
+}
+
+impl Payload for CoinOpsTestPayload {
+    fn make_new_payload(&mut self, effects: &ExecutionEffects) {
+        if !effects.is_ok() {
+            effects.print_gas_summary();
+            error!("Coin ops tx failed...");
+        }
+        self.gas.0 = effects.gas_object().0;
+        if let Some((coin, _)) = effects
+            .mutated()
+            .into_iter()
+            .find(|(object_ref, _)| object_ref.0 == self.coin.0)
+        {
+            self.coin = coin;
+        }
+        // A split creates the new coins owned by the sender, a merge deletes them
+        self.split_coins = effects
+            .created()
+            .into_iter()
+            .filter(|(_, owner)| *owner == Owner::AddressOwner(self.gas.1))
+            .map(|(object_ref, _)| object_ref)
+            .collect();
+    }
+    fn make_transaction(&mut self) -> VerifiedTransaction {
+        let gas_price = self.system_state_observer.gas_price();
+        let (function, arguments) = if self.split_coins.is_empty() {
+            let num_parts = self.rng.gen_range(2..=self.split_count);
+            (
+                coin::PAY_SPLIT_N_FUNC_NAME.as_str(),
+                vec![
+                    BenchMoveCallArg::ImmOrOwnedObject(self.coin),
+                    num_parts.into(),
+                ],
+            )
+        } else {
+            (
+                "join_vec",
+                vec![
+                    BenchMoveCallArg::ImmOrOwnedObject(self.coin),
+                    BenchMoveCallArg::ImmOrOwnedObjectVec(self.split_coins.clone()),
+                ],
+            )
+        };
+        move_call_pt_impl(
+            self.gas.1,
+            &self.gas.2,
+            SUI_FRAMEWORK_OBJECT_ID,
+            coin::PAY_MODULE_NAME.as_str(),
+            function,
+            vec![GAS::type_tag()],
+            arguments,
+            &self.gas.0,
+            gas_price * TEST_ONLY_GAS_UNIT_FOR_GENERIC,
+            gas_price,
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct CoinOpsWorkloadBuilder {
+    /// Owner of each payload's gas coin and of the coin it splits and merges
+    accounts: Vec<(SuiAddress, Arc<AccountKeyPair>)>,
+    split_count: u64,
+    rng_seed: u64,
+}
+
+impl CoinOpsWorkloadBuilder {
+    pub fn from(
+        workload_weight: f32,
+        target_qps: u64,
+        num_workers: u64,
+        in_flight_ratio: u64,
+        split_count: u64,
+        rng_seed: u64,
+    ) -> Option<WorkloadBuilderInfo> {
+        assert!(
+            split_count >= 2,
+            "Coins must be split into at least 2 parts"
+        );
+        let target_qps = workload_target_qps(workload_weight, target_qps);
+        let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
+        let max_ops = target_qps * in_flight_ratio;
+        if max_ops == 0 || num_workers == 0 {
+            None
+        } else {
+            let workload_params = WorkloadParams {
+                target_qps,
+                num_workers,
+                max_ops,
+            };
+            let accounts = (0..max_ops)
+                .map(|_| {
+                    let (address, keypair) = get_key_pair();
+                    (address, Arc::new(keypair))
+                })
+                .collect();
+            let workload_builder =
+                Box::<dyn WorkloadBuilder<dyn Payload>>::from(Box::new(CoinOpsWorkloadBuilder {
+                    accounts,
+                    split_count,
+                    rng_seed,
+                }));
+            let builder_info = WorkloadBuilderInfo {
+                workload_params,
+                workload_builder,
+            };
+            Some(builder_info)
+        }
+    }
+}
+
+#[async_trait]
+impl WorkloadBuilder<dyn Payload> for CoinOpsWorkloadBuilder {
+    async fn generate_coin_config_for_init(&self) -> Vec<GasCoinConfig> {
+        // Coins to split and merge, they are never used to pay for gas. The bank matches coins
+        // to configs by owner only, so these must be as large as the gas coins below
+        let amount = MAX_GAS_FOR_TESTING + ESTIMATED_COMPUTATION_COST;
+        self.accounts
+            .iter()
+            .map(|(address, keypair)| GasCoinConfig {
+                amount,
+                address: *address,
+                keypair: keypair.clone(),
+            })
+            .collect()
+    }
+    async fn generate_coin_config_for_payloads(&self) -> Vec<GasCoinConfig> {
+        let amount = MAX_GAS_FOR_TESTING + ESTIMATED_COMPUTATION_COST;
+        self.accounts
+            .iter()
+            .map(|(address, keypair)| GasCoinConfig {
+                amount,
+                address: *address,
+                keypair: keypair.clone(),
+            })
+            .collect()
+    }
+    async fn build(
+        &self,
+        init_gas: Vec<Gas>,
+        payload_gas: Vec<Gas>,
+    ) -> Box<dyn Workload<dyn Payload>> {
+        let coins_by_address: HashMap<SuiAddress, ObjectRef> = init_gas
+            .into_iter()
+            .map(|(coin, address, _)| (address, coin))
+            .collect();
+        let payload_coins = payload_gas
+            .into_iter()
+            .map(|gas| (coins_by_address[&gas.1], gas))
+            .collect();
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(CoinOpsWorkload {
+            split_count: self.split_count,
+            rng_seed: self.rng_seed,
+            payload_coins,
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub struct CoinOpsWorkload {
+    pub split_count: u64,
+    /// Seed of the random number of parts of each payload's splits
+    pub rng_seed: u64,
+    /// The coin to split and merge for each payload, along with the gas coin paying for it
+    pub payload_coins: Vec<(ObjectRef, Gas)>,
+}
+
+#[async_trait]
+impl Workload<dyn Payload> for CoinOpsWorkload {
+    fn name(&self) -> &'static str {
+        "coin_ops"
+    }
+    fn senders(&self) -> Vec<SuiAddress> {
+        self.payload_coins.iter().map(|(_, gas)| gas.1).collect()
+    }
+    async fn init(
+        &mut self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        _system_state_observer: Arc<SystemStateObserver>,
+    ) {
+    }
+    async fn make_test_payloads(
+        &self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) -> Vec<Box<dyn Payload>> {
+        info!("Creating coin ops txn payloads, hang tight..");
+        let mut seeds = StdRng::seed_from_u64(self.rng_seed);
+        self.payload_coins
+            .iter()
+            .map(|(coin, gas)| {
+                Box::new(CoinOpsTestPayload {
+                    coin: *coin,
+                    split_coins: vec![],
+                    split_count: self.split_count,
+                    rng: StdRng::seed_from_u64(seeds.gen()),
+                    gas: gas.clone(),
+                    system_state_observer: system_state_observer.clone(),
+                })
+            })
+            .map(|b| Box::<dyn Payload>::from(b))
+            .collect()
+    }
+}
@@ -0,0 +1,38 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module dynamic_field::dynamic_field {
+    use sui::dynamic_field;
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+
+    /// Owned by an address, holds `u64` dynamic fields named after their value
+    struct Parent has key {
+        id: UID,
+    }
+
+    /// Create a `Parent` without fields and send it to the sender
+    public entry fun create(ctx: &mut TxContext) {
+        let parent = Parent { id: object::new(ctx) };
+        transfer::transfer(parent, tx_context::sender(ctx))
+    }
+
+    /// Add `count` fields to `parent`, named 0 to `count - 1`, none of which may already exist
+    public entry fun add_fields(parent: &mut Parent, count: u64) {
+        let i = 0;
+        while (i < count) {
+            dynamic_field::add(&mut parent.id, i, i);
+            i = i + 1;
+        }
+    }
+
+    /// Remove the fields named 0 to `count - 1` from `parent`, all of which must exist
+    public entry fun remove_fields(parent: &mut Parent, count: u64) {
+        let i = 0;
+        while (i < count) {
+            let _value: u64 = dynamic_field::remove(&mut parent.id, i);
+            i = i + 1;
+        }
+    }
+}
@@ -0,0 +1,34 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module nft::nft {
+    use std::string::{Self, String};
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+    use sui::url::{Self, Url};
+
+    /// An NFT like object with a few fields of variable size
+    struct Nft has key, store {
+        id: UID,
+        name: String,
+        description: String,
+        url: Url,
+    }
+
+    /// Mint a new `Nft` and send it to the sender
+    public entry fun mint(
+        name: vector<u8>,
+        description: vector<u8>,
+        url: vector<u8>,
+        ctx: &mut TxContext
+    ) {
+        let nft = Nft {
+            id: object::new(ctx),
+            name: string::utf8(name),
+            description: string::utf8(description),
+            url: url::new_unsafe_from_bytes(url),
+        };
+        transfer::public_transfer(nft, tx_context::sender(ctx))
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+module wrap_unwrap::wrap_unwrap {
+    use std::option::{Self, Option};
+    use sui::object::{Self, UID};
+    use sui::transfer;
+    use sui::tx_context::{Self, TxContext};
+
+    /// Moved in and out of a `Parent`, in turn wrapped and owned by an address
+    struct Child has key, store {
+        id: UID,
+    }
+
+    /// Holds at most one wrapped `Child`
+    struct Parent has key {
+        id: UID,
+        child: Option<Child>,
+    }
+
+    /// Create a `Parent` holding a newly created `Child` and send it to the sender
+    public entry fun create(ctx: &mut TxContext) {
+        let child = Child { id: object::new(ctx) };
+        let parent = Parent {
+            id: object::new(ctx),
+            child: option::some(child),
+        };
+        transfer::transfer(parent, tx_context::sender(ctx))
+    }
+
+    /// Wrap `child` into `parent`, which must not already hold one
+    public entry fun wrap(parent: &mut Parent, child: Child) {
+        option::fill(&mut parent.child, child)
+    }
+
+    /// Unwrap the child held by `parent` and send it to the sender
+    public entry fun unwrap(parent: &mut Parent, ctx: &mut TxContext) {
+        let child = option::extract(&mut parent.child);
+        transfer::public_transfer(child, tx_context::sender(ctx))
+    }
+}
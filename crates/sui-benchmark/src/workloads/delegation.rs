@@ -7,10 +7,14 @@ use crate::workloads::workload::{Workload, WorkloadBuilder};
 use crate::workloads::workload::{
     ESTIMATED_COMPUTATION_COST, MAX_GAS_FOR_TESTING, STORAGE_COST_PER_COIN,
 };
-use crate::workloads::{Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams};
+use crate::workloads::{
+    workload_target_qps, Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams,
+};
 use crate::{ExecutionEffects, ValidatorProxy};
 use async_trait::async_trait;
+use rand::rngs::StdRng;
 use rand::seq::IteratorRandom;
+use rand::SeedableRng;
 use std::sync::Arc;
 use sui_core::test_utils::make_transfer_sui_transaction;
 use sui_types::base_types::{ObjectRef, SuiAddress};
@@ -61,10 +65,7 @@ impl Payload for DelegationTestPayload {
                 self.validator,
                 self.sender,
                 self.keypair.as_ref(),
-                self.system_state_observer
-                    .state
-                    .borrow()
-                    .reference_gas_price,
+                self.system_state_observer.gas_price(),
             ),
             None => make_transfer_sui_transaction(
                 self.gas,
@@ -72,10 +73,7 @@ impl Payload for DelegationTestPayload {
                 Some(1),
                 self.sender,
                 &self.keypair,
-                self.system_state_observer
-                    .state
-                    .borrow()
-                    .reference_gas_price,
+                self.system_state_observer.gas_price(),
             ),
         }
     }
@@ -84,6 +82,7 @@ impl Payload for DelegationTestPayload {
 #[derive(Debug)]
 pub struct DelegationWorkloadBuilder {
     count: u64,
+    rng_seed: u64,
 }
 
 impl DelegationWorkloadBuilder {
@@ -92,8 +91,9 @@ impl DelegationWorkloadBuilder {
         target_qps: u64,
         num_workers: u64,
         in_flight_ratio: u64,
+        rng_seed: u64,
     ) -> Option<WorkloadBuilderInfo> {
-        let target_qps = (workload_weight * target_qps as f32) as u64;
+        let target_qps = workload_target_qps(workload_weight, target_qps);
         let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
         let max_ops = target_qps * in_flight_ratio;
         if max_ops == 0 || num_workers == 0 {
@@ -105,7 +105,10 @@ impl DelegationWorkloadBuilder {
                 max_ops,
             };
             let workload_builder = Box::<dyn WorkloadBuilder<dyn Payload>>::from(Box::new(
-                DelegationWorkloadBuilder { count: max_ops },
+                DelegationWorkloadBuilder {
+                    count: max_ops,
+                    rng_seed,
+                },
             ));
             let builder_info = WorkloadBuilderInfo {
                 workload_params,
@@ -139,17 +142,31 @@ impl WorkloadBuilder<dyn Payload> for DelegationWorkloadBuilder {
         _init_gas: Vec<Gas>,
         payload_gas: Vec<Gas>,
     ) -> Box<dyn Workload<dyn Payload>> {
-        Box::<dyn Workload<dyn Payload>>::from(Box::new(DelegationWorkload { payload_gas }))
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(DelegationWorkload {
+            payload_gas,
+            rng_seed: self.rng_seed,
+        }))
     }
 }
 
 #[derive(Debug)]
 pub struct DelegationWorkload {
     payload_gas: Vec<Gas>,
+    /// Seed of the choice of validator each payload stakes with
+    rng_seed: u64,
 }
 
 #[async_trait]
 impl Workload<dyn Payload> for DelegationWorkload {
+    fn name(&self) -> &'static str {
+        "delegation"
+    }
+    fn senders(&self) -> Vec<SuiAddress> {
+        self.payload_gas.iter().map(|gas| gas.1).collect()
+    }
+    fn describe(&self) -> String {
+        format!("{}(payloads = {})", self.name(), self.payload_gas.len())
+    }
     async fn init(
         &mut self,
         _: Arc<dyn ValidatorProxy + Sync + Send>,
@@ -166,11 +183,12 @@ impl Workload<dyn Payload> for DelegationWorkload {
             .get_validators()
             .await
             .expect("failed to fetch validators");
+        let mut rng = StdRng::seed_from_u64(self.rng_seed);
 
         self.payload_gas
             .iter()
             .map(|(gas, owner, keypair)| {
-                let validator = *validators.iter().choose(&mut rand::thread_rng()).unwrap();
+                let validator = *validators.iter().choose(&mut rng).unwrap();
                 Box::new(DelegationTestPayload {
                     coin: None,
                     gas: *gas,
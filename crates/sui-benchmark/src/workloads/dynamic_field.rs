@@ -0,0 +1,296 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::in_memory_wallet::move_call_pt_data;
+use crate::system_state_observer::SystemStateObserver;
+use crate::workloads::payload::Payload;
+use crate::workloads::workload::{
+    Workload, WorkloadBuilder, ESTIMATED_COMPUTATION_COST, MAX_BUDGET, MAX_GAS_FOR_TESTING,
+    STORAGE_COST_PER_COIN,
+};
+use crate::workloads::{
+    workload_target_qps, Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams,
+};
+use crate::{ExecutionEffects, ValidatorProxy};
+use async_trait::async_trait;
+use std::path::PathBuf;
+use std::sync::Arc;
+use sui_types::{
+    base_types::{ObjectID, ObjectRef, SuiAddress},
+    crypto::get_key_pair,
+    messages::{
+        TransactionDataAPI, VerifiedTransaction, TEST_ONLY_GAS_UNIT_FOR_GENERIC,
+        TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+    },
+    object::Owner,
+    utils::to_sender_signed_transaction,
+};
+use test_utils::messages::create_publish_move_package_transaction;
+use test_utils::transaction::parse_package_ref;
+use tracing::{error, info};
+
+/// Each field is a child object which the object runtime loads and caches, at most 1000 of them
+/// per transaction
+pub const MAX_FIELD_COUNT: u64 = 1000;
+
+/// Creates a parent object, then alternates between adding `field_count` dynamic fields to it and
+/// removing them all, so that every transaction but the first creates or loads and deletes that
+/// many child objects of the parent.
+#[derive(Debug)]
+pub struct DynamicFieldTestPayload {
+    package_id: ObjectID,
+    /// The parent object, created by the first transaction
+    parent: Option<ObjectRef>,
+    /// Whether the parent holds its fields, in which case the next transaction removes them
+    has_fields: bool,
+    field_count: u64,
+    gas: Gas,
+    system_state_observer: Arc<SystemStateObserver>,
+}
+
+impl std::fmt::Display for DynamicFieldTestPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "dynamic_field")
+    }
+}
+
+impl Payload for DynamicFieldTestPayload {
+    fn make_new_payload(&mut self, effects: &ExecutionEffects) {
+        if !effects.is_ok() {
+            effects.print_gas_summary();
+            error!("Dynamic field tx failed...");
+        }
+        self.gas.0 = effects.gas_object().0;
+        self.parent = match self.parent {
+            None => effects
+                .created()
+                .into_iter()
+                .find(|(_, owner)| *owner == Owner::AddressOwner(self.gas.1))
+                .map(|(object_ref, _)| object_ref),
+            Some(parent) => {
+                if effects.is_ok() {
+                    self.has_fields = !self.has_fields;
+                }
+                effects
+                    .mutated()
+                    .into_iter()
+                    .find(|(object_ref, _)| object_ref.0 == parent.0)
+                    .map(|(object_ref, _)| object_ref)
+            }
+        };
+    }
+    fn make_transaction(&mut self) -> VerifiedTransaction {
+        let gas_price = self.system_state_observer.gas_price();
+        let (function, arguments) = match self.parent {
+            None => ("create", vec![]),
+            Some(parent) if self.has_fields => (
+                "remove_fields",
+                vec![parent.into(), self.field_count.into()],
+            ),
+            Some(parent) => ("add_fields", vec![parent.into(), self.field_count.into()]),
+        };
+        // A u64 field takes about as much storage as a coin
+        let gas_budget = (gas_price * TEST_ONLY_GAS_UNIT_FOR_GENERIC
+            + STORAGE_COST_PER_COIN * self.field_count)
+            .min(MAX_BUDGET);
+        let mut data = move_call_pt_data(
+            self.gas.1,
+            self.package_id,
+            "dynamic_field",
+            function,
+            vec![],
+            arguments,
+            &self.gas.0,
+            gas_budget,
+            gas_price,
+        );
+        *data.expiration_mut_for_testing() = self.system_state_observer.expiration();
+        to_sender_signed_transaction(data, &self.gas.2)
+    }
+    fn verify_effects(&self, effects: &ExecutionEffects) -> Result<(), String> {
+        if !effects.is_ok() {
+            return Err(format!("dynamic field tx failed with {}", effects.status()));
+        }
+        let Some(parent) = self.parent else {
+            return Ok(());
+        };
+        if self.has_fields {
+            let deleted = effects.deleted().len() as u64;
+            if deleted != self.field_count {
+                return Err(format!(
+                    "removing fields deleted {deleted} objects, expected {}",
+                    self.field_count
+                ));
+            }
+        } else {
+            let children = effects
+                .created()
+                .iter()
+                .filter(|(_, owner)| *owner == Owner::ObjectOwner(parent.0.into()))
+                .count() as u64;
+            if children != self.field_count {
+                return Err(format!(
+                    "adding fields created {children} children of the parent, expected {}",
+                    self.field_count
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct DynamicFieldWorkloadBuilder {
+    num_payloads: u64,
+    field_count: u64,
+}
+
+impl DynamicFieldWorkloadBuilder {
+    pub fn from(
+        workload_weight: f32,
+        target_qps: u64,
+        num_workers: u64,
+        in_flight_ratio: u64,
+        field_count: u64,
+    ) -> Option<WorkloadBuilderInfo> {
+        assert!(
+            (1..=MAX_FIELD_COUNT).contains(&field_count),
+            "Field count must be between 1 and {MAX_FIELD_COUNT}"
+        );
+        let target_qps = workload_target_qps(workload_weight, target_qps);
+        let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
+        let max_ops = target_qps * in_flight_ratio;
+        if max_ops == 0 || num_workers == 0 {
+            None
+        } else {
+            let workload_params = WorkloadParams {
+                target_qps,
+                num_workers,
+                max_ops,
+            };
+            let workload_builder = Box::<dyn WorkloadBuilder<dyn Payload>>::from(Box::new(
+                DynamicFieldWorkloadBuilder {
+                    num_payloads: max_ops,
+                    field_count,
+                },
+            ));
+            let builder_info = WorkloadBuilderInfo {
+                workload_params,
+                workload_builder,
+            };
+            Some(builder_info)
+        }
+    }
+}
+
+#[async_trait]
+impl WorkloadBuilder<dyn Payload> for DynamicFieldWorkloadBuilder {
+    async fn generate_coin_config_for_init(&self) -> Vec<GasCoinConfig> {
+        // Gas coin for publishing the dynamic_field package
+        let (address, keypair) = get_key_pair();
+        vec![GasCoinConfig {
+            amount: MAX_GAS_FOR_TESTING,
+            address,
+            keypair: Arc::new(keypair),
+        }]
+    }
+    async fn generate_coin_config_for_payloads(&self) -> Vec<GasCoinConfig> {
+        let mut configs = vec![];
+        let amount = MAX_GAS_FOR_TESTING + ESTIMATED_COMPUTATION_COST;
+        // Gas coins for running workload
+        for _i in 0..self.num_payloads {
+            let (address, keypair) = get_key_pair();
+            configs.push(GasCoinConfig {
+                amount,
+                address,
+                keypair: Arc::new(keypair),
+            });
+        }
+        configs
+    }
+    async fn build(
+        &self,
+        mut init_gas: Vec<Gas>,
+        payload_gas: Vec<Gas>,
+    ) -> Box<dyn Workload<dyn Payload>> {
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(DynamicFieldWorkload {
+            package_id: None,
+            field_count: self.field_count,
+            init_gas: init_gas.pop().unwrap(),
+            payload_gas,
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub struct DynamicFieldWorkload {
+    pub package_id: Option<ObjectID>,
+    pub field_count: u64,
+    pub init_gas: Gas,
+    pub payload_gas: Vec<Gas>,
+}
+
+#[async_trait]
+impl Workload<dyn Payload> for DynamicFieldWorkload {
+    fn name(&self) -> &'static str {
+        "dynamic_field"
+    }
+    fn describe(&self) -> String {
+        format!("{}(field_count = {})", self.name(), self.field_count)
+    }
+    fn senders(&self) -> Vec<SuiAddress> {
+        self.payload_gas.iter().map(|gas| gas.1).collect()
+    }
+    async fn init(
+        &mut self,
+        proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) {
+        if self.package_id.is_some() {
+            return;
+        }
+        let gas_price = system_state_observer.state.borrow().reference_gas_price;
+        let (gas, sender, keypair) = &self.init_gas;
+
+        info!("Publishing dynamic_field package");
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("src/workloads/data/dynamic_field");
+        let transaction = create_publish_move_package_transaction(
+            *gas,
+            path,
+            *sender,
+            keypair,
+            gas_price * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+            gas_price,
+        );
+        let effects = proxy
+            .execute_transaction_block(transaction.into())
+            .await
+            .unwrap();
+        self.package_id = Some(parse_package_ref(&effects.created()).unwrap().0);
+    }
+    async fn make_test_payloads(
+        &self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) -> Vec<Box<dyn Payload>> {
+        info!("Creating dynamic_field txn payloads, hang tight..");
+        let package_id = self
+            .package_id
+            .expect("Dynamic field workload must be initialized before creating payloads");
+        self.payload_gas
+            .iter()
+            .map(|gas| {
+                Box::new(DynamicFieldTestPayload {
+                    package_id,
+                    parent: None,
+                    has_fields: false,
+                    field_count: self.field_count,
+                    gas: gas.clone(),
+                    system_state_observer: system_state_observer.clone(),
+                })
+            })
+            .map(|b| Box::<dyn Payload>::from(b))
+            .collect()
+    }
+}
@@ -0,0 +1,226 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::system_state_observer::SystemStateObserver;
+use crate::workloads::payload::Payload;
+use crate::workloads::workload::{
+    Workload, WorkloadBuilder, ESTIMATED_COMPUTATION_COST, MAX_GAS_FOR_TESTING,
+};
+use crate::workloads::{
+    workload_target_qps, Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams,
+};
+use crate::{ExecutionEffects, ValidatorProxy};
+use async_trait::async_trait;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::sync::Arc;
+use sui_types::{
+    base_types::{ObjectRef, SuiAddress},
+    crypto::{get_key_pair, AccountKeyPair},
+    messages::{
+        TransactionData, TransactionDataAPI, VerifiedTransaction, TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+    },
+    utils::to_sender_signed_transaction,
+};
+use tracing::{error, info};
+
+/// Transfers its gas coin to its own sender, and for a fraction of the transactions equivocates
+/// instead: the transaction spends the version of the gas coin its previous transaction already
+/// spent, sending part of it elsewhere. Validators must reject such a conflicting transaction,
+/// either because their lock on that version is held by the previous transaction, if they have
+/// not executed it yet, or because that version was consumed. The driver counts these rejections
+/// apart from errors, and an accepted conflicting transaction as an error.
+#[derive(Debug)]
+pub struct EquivocationTestPayload {
+    /// Fraction of the transactions which conflict with the previous one, between 0.0 and 1.0
+    conflict_fraction: f32,
+    /// Whether the last transaction made conflicts with the previous one
+    conflicting: bool,
+    /// The version of the gas coin spent by the last transaction that was executed
+    spent_gas: Option<ObjectRef>,
+    /// Receives the coins split off by conflicting transactions, should any be executed
+    recipient: SuiAddress,
+    /// Picks which transactions conflict
+    rng: StdRng,
+    gas: Gas,
+    system_state_observer: Arc<SystemStateObserver>,
+}
+
+impl std::fmt::Display for EquivocationTestPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "equivocation")
+    }
+}
+
+impl Payload for EquivocationTestPayload {
+    fn make_new_payload(&mut self, effects: &ExecutionEffects) {
+        if !effects.is_ok() {
+            effects.print_gas_summary();
+            error!("Equivocation workload tx failed...");
+        }
+        self.spent_gas = Some(self.gas.0);
+        self.gas.0 = effects.gas_object().0;
+    }
+    fn make_transaction(&mut self) -> VerifiedTransaction {
+        let gas_price = self.system_state_observer.gas_price();
+        let gas_budget = gas_price * TEST_ONLY_GAS_UNIT_FOR_TRANSFER;
+        let sender = self.gas.1;
+        let conflicting_gas = self
+            .spent_gas
+            .filter(|_| self.rng.gen_bool(self.conflict_fraction as f64));
+        self.conflicting = conflicting_gas.is_some();
+        let mut data = match conflicting_gas {
+            Some(spent_gas) => TransactionData::new_transfer_sui(
+                self.recipient,
+                sender,
+                Some(1),
+                spent_gas,
+                gas_budget,
+                gas_price,
+            ),
+            None => TransactionData::new_transfer_sui(
+                sender, sender, None, self.gas.0, gas_budget, gas_price,
+            ),
+        };
+        *data.expiration_mut_for_testing() = self.system_state_observer.expiration();
+        to_sender_signed_transaction(data, &self.gas.2)
+    }
+    fn expects_rejection(&self) -> bool {
+        self.conflicting
+    }
+}
+
+#[derive(Debug)]
+pub struct EquivocationWorkloadBuilder {
+    num_payloads: u64,
+    conflict_fraction: f32,
+    rng_seed: u64,
+}
+
+impl EquivocationWorkloadBuilder {
+    pub fn from(
+        workload_weight: f32,
+        target_qps: u64,
+        num_workers: u64,
+        in_flight_ratio: u64,
+        conflict_fraction: f32,
+        rng_seed: u64,
+    ) -> Option<WorkloadBuilderInfo> {
+        assert!(
+            (0.0..=1.0).contains(&conflict_fraction),
+            "Conflict fraction must be between 0.0 and 1.0"
+        );
+        let target_qps = workload_target_qps(workload_weight, target_qps);
+        let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
+        let max_ops = target_qps * in_flight_ratio;
+        if max_ops == 0 || num_workers == 0 {
+            None
+        } else {
+            let workload_params = WorkloadParams {
+                target_qps,
+                num_workers,
+                max_ops,
+            };
+            let workload_builder = Box::<dyn WorkloadBuilder<dyn Payload>>::from(Box::new(
+                EquivocationWorkloadBuilder {
+                    num_payloads: max_ops,
+                    conflict_fraction,
+                    rng_seed,
+                },
+            ));
+            let builder_info = WorkloadBuilderInfo {
+                workload_params,
+                workload_builder,
+            };
+            Some(builder_info)
+        }
+    }
+}
+
+#[async_trait]
+impl WorkloadBuilder<dyn Payload> for EquivocationWorkloadBuilder {
+    async fn generate_coin_config_for_init(&self) -> Vec<GasCoinConfig> {
+        vec![]
+    }
+    async fn generate_coin_config_for_payloads(&self) -> Vec<GasCoinConfig> {
+        let amount = MAX_GAS_FOR_TESTING + ESTIMATED_COMPUTATION_COST;
+        (0..self.num_payloads)
+            .map(|_| {
+                let (address, keypair) = get_key_pair::<AccountKeyPair>();
+                GasCoinConfig {
+                    amount,
+                    address,
+                    keypair: Arc::new(keypair),
+                }
+            })
+            .collect()
+    }
+    async fn build(
+        &self,
+        _init_gas: Vec<Gas>,
+        payload_gas: Vec<Gas>,
+    ) -> Box<dyn Workload<dyn Payload>> {
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(EquivocationWorkload {
+            conflict_fraction: self.conflict_fraction,
+            rng_seed: self.rng_seed,
+            payload_gas,
+        }))
+    }
+}
+
+/// Equivocates on owned objects, the adversarial counterpart of the transfer workloads, to check
+/// that validators keep rejecting double spends under load.
+#[derive(Debug)]
+pub struct EquivocationWorkload {
+    pub conflict_fraction: f32,
+    /// Seed of the choices of which transactions of each payload conflict
+    pub rng_seed: u64,
+    pub payload_gas: Vec<Gas>,
+}
+
+#[async_trait]
+impl Workload<dyn Payload> for EquivocationWorkload {
+    fn name(&self) -> &'static str {
+        "equivocation"
+    }
+    fn describe(&self) -> String {
+        format!(
+            "{}(conflict_fraction = {})",
+            self.name(),
+            self.conflict_fraction
+        )
+    }
+    fn senders(&self) -> Vec<SuiAddress> {
+        self.payload_gas.iter().map(|gas| gas.1).collect()
+    }
+    async fn init(
+        &mut self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        _system_state_observer: Arc<SystemStateObserver>,
+    ) {
+    }
+    async fn make_test_payloads(
+        &self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) -> Vec<Box<dyn Payload>> {
+        info!("Creating equivocation txn payloads, hang tight..");
+        let mut seeds = StdRng::seed_from_u64(self.rng_seed);
+        self.payload_gas
+            .iter()
+            .map(|gas| {
+                let (recipient, _) = get_key_pair::<AccountKeyPair>();
+                Box::new(EquivocationTestPayload {
+                    conflict_fraction: self.conflict_fraction,
+                    conflicting: false,
+                    spent_gas: None,
+                    recipient,
+                    rng: StdRng::seed_from_u64(seeds.gen()),
+                    gas: gas.clone(),
+                    system_state_observer: system_state_observer.clone(),
+                })
+            })
+            .map(|b| Box::<dyn Payload>::from(b))
+            .collect()
+    }
+}
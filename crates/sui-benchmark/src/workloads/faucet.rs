@@ -0,0 +1,180 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::system_state_observer::SystemStateObserver;
+use crate::workloads::payload::Payload;
+use crate::workloads::workload::{
+    Workload, WorkloadBuilder, ESTIMATED_COMPUTATION_COST, MAX_GAS_FOR_TESTING,
+    STORAGE_COST_PER_COIN,
+};
+use crate::workloads::{Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams};
+use crate::{ExecutionEffects, ValidatorProxy};
+use async_trait::async_trait;
+use std::sync::Arc;
+use sui_types::{
+    base_types::SuiAddress,
+    crypto::get_key_pair,
+    messages::{
+        TransactionData, TransactionDataAPI, VerifiedTransaction, TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+    },
+    utils::to_sender_signed_transaction,
+};
+use tracing::{error, info};
+
+/// Mist sent to a sender by each top up
+const TOP_UP_AMOUNT: u64 = 1_000_000_000;
+/// Number of senders topped up by each faucet transaction
+const TOP_UP_BATCH_SIZE: usize = 10;
+/// Faucet transactions per second, low enough to stay in the background of the real workloads
+const FAUCET_TARGET_QPS: u64 = 1;
+/// Number of top ups the faucet coin is funded with, the faucet runs dry after this many
+const NUM_TOP_UPS: u64 = 1_000;
+
+/// Tops up the senders of the other workloads round robin, each transaction paying a batch of them
+/// from the faucet coin.
+#[derive(Debug)]
+pub struct FaucetTestPayload {
+    recipients: Arc<Vec<SuiAddress>>,
+    /// Index into `recipients` of the first sender to top up with the next transaction
+    next: usize,
+    gas: Gas,
+    system_state_observer: Arc<SystemStateObserver>,
+}
+
+impl std::fmt::Display for FaucetTestPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "faucet")
+    }
+}
+
+impl Payload for FaucetTestPayload {
+    fn make_new_payload(&mut self, effects: &ExecutionEffects) {
+        if !effects.is_ok() {
+            effects.print_gas_summary();
+            error!("Faucet tx failed...");
+        }
+        self.gas.0 = effects.gas_object().0;
+    }
+    fn make_transaction(&mut self) -> VerifiedTransaction {
+        let gas_price = self.system_state_observer.gas_price();
+        let recipients: Vec<SuiAddress> = (0..TOP_UP_BATCH_SIZE.min(self.recipients.len()))
+            .map(|i| self.recipients[(self.next + i) % self.recipients.len()])
+            .collect();
+        self.next = (self.next + recipients.len()) % self.recipients.len();
+        let amounts = vec![TOP_UP_AMOUNT; recipients.len()];
+        let mut data = TransactionData::new_pay_sui(
+            self.gas.1,
+            vec![],
+            recipients,
+            amounts,
+            self.gas.0,
+            gas_price * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+            gas_price,
+        )
+        .unwrap();
+        *data.expiration_mut_for_testing() = self.system_state_observer.expiration();
+        to_sender_signed_transaction(data, &self.gas.2)
+    }
+}
+
+#[derive(Debug)]
+pub struct FaucetWorkloadBuilder {
+    recipients: Vec<SuiAddress>,
+}
+
+impl FaucetWorkloadBuilder {
+    /// The faucet is not weighted against the other workloads, it always runs a single payload at
+    /// `FAUCET_TARGET_QPS` topping up `recipients`.
+    pub fn from(recipients: Vec<SuiAddress>) -> Option<WorkloadBuilderInfo> {
+        if recipients.is_empty() {
+            return None;
+        }
+        let workload_params = WorkloadParams {
+            target_qps: FAUCET_TARGET_QPS,
+            num_workers: 1,
+            max_ops: 1,
+        };
+        let workload_builder =
+            Box::<dyn WorkloadBuilder<dyn Payload>>::from(Box::new(FaucetWorkloadBuilder {
+                recipients,
+            }));
+        Some(WorkloadBuilderInfo {
+            workload_params,
+            workload_builder,
+        })
+    }
+}
+
+#[async_trait]
+impl WorkloadBuilder<dyn Payload> for FaucetWorkloadBuilder {
+    async fn generate_coin_config_for_init(&self) -> Vec<GasCoinConfig> {
+        vec![]
+    }
+    async fn generate_coin_config_for_payloads(&self) -> Vec<GasCoinConfig> {
+        // The faucet coin pays for the top ups and for its own gas
+        let amount = (TOP_UP_AMOUNT * TOP_UP_BATCH_SIZE as u64
+            + STORAGE_COST_PER_COIN * TOP_UP_BATCH_SIZE as u64)
+            * NUM_TOP_UPS
+            + MAX_GAS_FOR_TESTING
+            + ESTIMATED_COMPUTATION_COST;
+        let (address, keypair) = get_key_pair();
+        vec![GasCoinConfig {
+            amount,
+            address,
+            keypair: Arc::new(keypair),
+        }]
+    }
+    async fn build(
+        &self,
+        _init_gas: Vec<Gas>,
+        payload_gas: Vec<Gas>,
+    ) -> Box<dyn Workload<dyn Payload>> {
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(FaucetWorkload {
+            recipients: Arc::new(self.recipients.clone()),
+            payload_gas,
+        }))
+    }
+}
+
+/// Keeps the senders of the other workloads funded during unbounded runs. Top ups arrive as new
+/// coins owned by the senders, alongside the gas coins their payloads were created with.
+#[derive(Debug)]
+pub struct FaucetWorkload {
+    pub recipients: Arc<Vec<SuiAddress>>,
+    pub payload_gas: Vec<Gas>,
+}
+
+#[async_trait]
+impl Workload<dyn Payload> for FaucetWorkload {
+    fn name(&self) -> &'static str {
+        "faucet"
+    }
+    fn describe(&self) -> String {
+        format!("{}(recipients = {})", self.name(), self.recipients.len())
+    }
+    async fn init(
+        &mut self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        _system_state_observer: Arc<SystemStateObserver>,
+    ) {
+    }
+    async fn make_test_payloads(
+        &self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) -> Vec<Box<dyn Payload>> {
+        info!("Creating faucet txn payloads, hang tight..");
+        self.payload_gas
+            .iter()
+            .map(|gas| {
+                Box::new(FaucetTestPayload {
+                    recipients: self.recipients.clone(),
+                    next: 0,
+                    gas: gas.clone(),
+                    system_state_observer: system_state_observer.clone(),
+                })
+            })
+            .map(|b| Box::<dyn Payload>::from(b))
+            .collect()
+    }
+}
@@ -0,0 +1,255 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::system_state_observer::SystemStateObserver;
+use crate::workloads::payload::Payload;
+use crate::workloads::workload::{Workload, WorkloadBuilder, ESTIMATED_COMPUTATION_COST};
+use crate::workloads::{
+    workload_target_qps, Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams,
+};
+use crate::{ExecutionEffects, ValidatorProxy};
+use anyhow::bail;
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use std::sync::Arc;
+use sui_json_rpc_types::{SuiObjectDataOptions, SuiTransactionBlockResponseOptions};
+use sui_sdk::{SuiClient, SuiClientBuilder};
+use sui_types::{
+    base_types::{ObjectID, TransactionDigest},
+    crypto::get_key_pair,
+    messages::VerifiedTransaction,
+};
+use tracing::{info, warn};
+
+/// Number of objects created for the workload to read, the transactions which created them are
+/// read as well
+const NUM_QUERY_OBJECTS: u64 = 10;
+
+#[derive(Debug, Clone, Copy)]
+enum QueryTarget {
+    Object(ObjectID),
+    Transaction(TransactionDigest),
+}
+
+/// Reads objects and transactions from a fullnode over RPC, round robin.
+pub struct FullnodeQueryPayload {
+    client: SuiClient,
+    targets: Arc<Vec<QueryTarget>>,
+    next: usize,
+}
+
+impl std::fmt::Debug for FullnodeQueryPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("FullnodeQueryPayload")
+            .field("targets", &self.targets)
+            .field("next", &self.next)
+            .finish()
+    }
+}
+
+impl std::fmt::Display for FullnodeQueryPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "fullnode_query")
+    }
+}
+
+impl Payload for FullnodeQueryPayload {
+    fn make_new_payload(&mut self, _effects: &ExecutionEffects) {
+        unreachable!("Fullnode query payloads do not execute transactions")
+    }
+    fn make_transaction(&mut self) -> VerifiedTransaction {
+        unreachable!("Fullnode query payloads do not execute transactions")
+    }
+    fn make_query(&mut self) -> Option<BoxFuture<'static, anyhow::Result<()>>> {
+        let target = self.targets[self.next % self.targets.len()];
+        self.next += 1;
+        let client = self.client.clone();
+        Some(
+            async move {
+                match target {
+                    QueryTarget::Object(object_id) => {
+                        let response = client
+                            .read_api()
+                            .get_object_with_options(
+                                object_id,
+                                SuiObjectDataOptions::bcs_lossless(),
+                            )
+                            .await?;
+                        if let Some(error) = response.error {
+                            bail!("Error getting object {:?}: {}", object_id, error);
+                        }
+                    }
+                    QueryTarget::Transaction(digest) => {
+                        client
+                            .read_api()
+                            .get_transaction_with_options(
+                                digest,
+                                SuiTransactionBlockResponseOptions::new().with_effects(),
+                            )
+                            .await?;
+                    }
+                }
+                Ok(())
+            }
+            .boxed(),
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct FullnodeQueryWorkloadBuilder {
+    num_payloads: u64,
+    fullnode_rpc_url: String,
+}
+
+impl FullnodeQueryWorkloadBuilder {
+    pub fn from(
+        workload_weight: f32,
+        target_qps: u64,
+        num_workers: u64,
+        in_flight_ratio: u64,
+        fullnode_rpc_url: Option<String>,
+    ) -> Option<WorkloadBuilderInfo> {
+        let target_qps = workload_target_qps(workload_weight, target_qps);
+        let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
+        let max_ops = target_qps * in_flight_ratio;
+        if max_ops == 0 || num_workers == 0 {
+            return None;
+        }
+        let Some(fullnode_rpc_url) = fullnode_rpc_url else {
+            warn!("Skipping fullnode query workload, no fullnode rpc url was given");
+            return None;
+        };
+        let workload_params = WorkloadParams {
+            target_qps,
+            num_workers,
+            max_ops,
+        };
+        let workload_builder =
+            Box::<dyn WorkloadBuilder<dyn Payload>>::from(Box::new(FullnodeQueryWorkloadBuilder {
+                num_payloads: max_ops,
+                fullnode_rpc_url,
+            }));
+        let builder_info = WorkloadBuilderInfo {
+            workload_params,
+            workload_builder,
+        };
+        Some(builder_info)
+    }
+}
+
+#[async_trait]
+impl WorkloadBuilder<dyn Payload> for FullnodeQueryWorkloadBuilder {
+    async fn generate_coin_config_for_init(&self) -> Vec<GasCoinConfig> {
+        // Coins to read, they are never used to pay for gas
+        (0..NUM_QUERY_OBJECTS)
+            .map(|_| {
+                let (address, keypair) = get_key_pair();
+                GasCoinConfig {
+                    amount: ESTIMATED_COMPUTATION_COST,
+                    address,
+                    keypair: Arc::new(keypair),
+                }
+            })
+            .collect()
+    }
+    async fn generate_coin_config_for_payloads(&self) -> Vec<GasCoinConfig> {
+        vec![]
+    }
+    async fn build(
+        &self,
+        init_gas: Vec<Gas>,
+        _payload_gas: Vec<Gas>,
+    ) -> Box<dyn Workload<dyn Payload>> {
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(FullnodeQueryWorkload {
+            num_payloads: self.num_payloads,
+            fullnode_rpc_url: self.fullnode_rpc_url.clone(),
+            object_ids: init_gas.iter().map(|(obj_ref, _, _)| obj_ref.0).collect(),
+            client: None,
+            targets: vec![],
+        }))
+    }
+}
+
+pub struct FullnodeQueryWorkload {
+    pub num_payloads: u64,
+    pub fullnode_rpc_url: String,
+    pub object_ids: Vec<ObjectID>,
+    client: Option<SuiClient>,
+    targets: Vec<QueryTarget>,
+}
+
+impl std::fmt::Debug for FullnodeQueryWorkload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("FullnodeQueryWorkload")
+            .field("num_payloads", &self.num_payloads)
+            .field("fullnode_rpc_url", &self.fullnode_rpc_url)
+            .field("object_ids", &self.object_ids)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl Workload<dyn Payload> for FullnodeQueryWorkload {
+    fn name(&self) -> &'static str {
+        "fullnode_query"
+    }
+    async fn init(
+        &mut self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        _system_state_observer: Arc<SystemStateObserver>,
+    ) {
+        if self.client.is_some() {
+            return;
+        }
+        info!("Connecting to fullnode at {}", self.fullnode_rpc_url);
+        let client = SuiClientBuilder::default()
+            .build(&self.fullnode_rpc_url)
+            .await
+            .expect("Failed to connect to fullnode");
+        let mut targets = vec![];
+        for object_id in &self.object_ids {
+            targets.push(QueryTarget::Object(*object_id));
+            let previous_transaction = client
+                .read_api()
+                .get_object_with_options(
+                    *object_id,
+                    SuiObjectDataOptions::new().with_previous_transaction(),
+                )
+                .await
+                .ok()
+                .and_then(|response| response.data)
+                .and_then(|data| data.previous_transaction);
+            match previous_transaction {
+                Some(digest) => targets.push(QueryTarget::Transaction(digest)),
+                None => warn!("Failed to read the transaction which created {object_id}"),
+            }
+        }
+        self.client = Some(client);
+        self.targets = targets;
+    }
+    async fn make_test_payloads(
+        &self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        _system_state_observer: Arc<SystemStateObserver>,
+    ) -> Vec<Box<dyn Payload>> {
+        info!("Creating fullnode query payloads, hang tight..");
+        let client = self
+            .client
+            .clone()
+            .expect("Fullnode query workload must be initialized before creating payloads");
+        let targets = Arc::new(self.targets.clone());
+        (0..self.num_payloads as usize)
+            .map(|i| {
+                Box::new(FullnodeQueryPayload {
+                    client: client.clone(),
+                    targets: targets.clone(),
+                    // Spread the payloads over the targets
+                    next: i,
+                })
+            })
+            .map(|b| Box::<dyn Payload>::from(b))
+            .collect()
+    }
+}
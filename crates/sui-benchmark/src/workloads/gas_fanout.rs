@@ -0,0 +1,272 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::in_memory_wallet::move_call_pt_impl;
+use crate::system_state_observer::SystemStateObserver;
+use crate::workloads::payload::Payload;
+use crate::workloads::workload::{
+    Workload, WorkloadBuilder, ESTIMATED_COMPUTATION_COST, MAX_GAS_FOR_TESTING,
+};
+use crate::workloads::{
+    workload_target_qps, Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams,
+};
+use crate::{BenchMoveCallArg, ExecutionEffects, ValidatorProxy};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use sui_types::{
+    base_types::{ObjectRef, SuiAddress},
+    coin,
+    crypto::{get_key_pair, AccountKeyPair},
+    gas_coin::GAS,
+    messages::{VerifiedTransaction, TEST_ONLY_GAS_UNIT_FOR_GENERIC},
+    object::Owner,
+    SUI_FRAMEWORK_OBJECT_ID,
+};
+use tracing::{error, info};
+
+/// Merging the coins of a split back takes all of them, the coin they were split from and the
+/// gas coin as inputs, which must stay within the protocol's 2048 input objects
+pub const MAX_FANOUT_WIDTH: u64 = 2046;
+
+/// Alternates between splitting a coin into `fanout_width` new coins and merging all of them
+/// back, which deletes them, so that every transaction creates or deletes that many objects.
+#[derive(Debug)]
+pub struct GasFanoutTestPayload {
+    /// The coin being split and merged, never used to pay for gas
+    coin: ObjectRef,
+    /// Coins created by the previous split, to be merged back into `coin` by the next transaction
+    fanout_coins: Vec<ObjectRef>,
+    /// Number of coins created by each split
+    fanout_width: u64,
+    gas: Gas,
+    system_state_observer: Arc<SystemStateObserver>,
+}
+
+impl std::fmt::Display for GasFanoutTestPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "gas_fanout")
+    }
+}
+
+impl Payload for GasFanoutTestPayload {
+    fn make_new_payload(&mut self, effects: &ExecutionEffects) {
+        if !effects.is_ok() {
+            effects.print_gas_summary();
+            error!("Gas fanout tx failed...");
+        }
+        self.gas.0 = effects.gas_object().0;
+        if let Some((coin, _)) = effects
+            .mutated()
+            .into_iter()
+            .find(|(object_ref, _)| object_ref.0 == self.coin.0)
+        {
+            self.coin = coin;
+        }
+        // A split creates the new coins owned by the sender, a merge deletes them
+        self.fanout_coins = effects
+            .created()
+            .into_iter()
+            .filter(|(_, owner)| *owner == Owner::AddressOwner(self.gas.1))
+            .map(|(object_ref, _)| object_ref)
+            .collect();
+    }
+    fn make_transaction(&mut self) -> VerifiedTransaction {
+        let gas_price = self.system_state_observer.gas_price();
+        let (function, arguments) = if self.fanout_coins.is_empty() {
+            // The coin is divided into `n` parts, one of which it keeps
+            (
+                coin::PAY_SPLIT_N_FUNC_NAME.as_str(),
+                vec![
+                    BenchMoveCallArg::ImmOrOwnedObject(self.coin),
+                    (self.fanout_width + 1).into(),
+                ],
+            )
+        } else {
+            (
+                "join_vec",
+                vec![
+                    BenchMoveCallArg::ImmOrOwnedObject(self.coin),
+                    BenchMoveCallArg::ImmOrOwnedObjectVec(self.fanout_coins.clone()),
+                ],
+            )
+        };
+        move_call_pt_impl(
+            self.gas.1,
+            &self.gas.2,
+            SUI_FRAMEWORK_OBJECT_ID,
+            coin::PAY_MODULE_NAME.as_str(),
+            function,
+            vec![GAS::type_tag()],
+            arguments,
+            &self.gas.0,
+            gas_price * TEST_ONLY_GAS_UNIT_FOR_GENERIC,
+            gas_price,
+        )
+    }
+    fn verify_effects(&self, effects: &ExecutionEffects) -> Result<(), String> {
+        if !effects.is_ok() {
+            return Err(format!("fanout failed with {}", effects.status()));
+        }
+        if self.fanout_coins.is_empty() {
+            let created = effects.created().len() as u64;
+            if created != self.fanout_width {
+                return Err(format!(
+                    "split created {created} coins, expected {}",
+                    self.fanout_width
+                ));
+            }
+        } else {
+            let deleted = effects.deleted().len();
+            if deleted != self.fanout_coins.len() {
+                return Err(format!(
+                    "merge deleted {deleted} coins, expected {}",
+                    self.fanout_coins.len()
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct GasFanoutWorkloadBuilder {
+    /// Owner of each payload's gas coin and of the coin it splits and merges
+    accounts: Vec<(SuiAddress, Arc<AccountKeyPair>)>,
+    fanout_width: u64,
+}
+
+impl GasFanoutWorkloadBuilder {
+    pub fn from(
+        workload_weight: f32,
+        target_qps: u64,
+        num_workers: u64,
+        in_flight_ratio: u64,
+        fanout_width: u64,
+    ) -> Option<WorkloadBuilderInfo> {
+        assert!(
+            (1..=MAX_FANOUT_WIDTH).contains(&fanout_width),
+            "Fanout width must be between 1 and {MAX_FANOUT_WIDTH}"
+        );
+        let target_qps = workload_target_qps(workload_weight, target_qps);
+        let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
+        let max_ops = target_qps * in_flight_ratio;
+        if max_ops == 0 || num_workers == 0 {
+            None
+        } else {
+            let workload_params = WorkloadParams {
+                target_qps,
+                num_workers,
+                max_ops,
+            };
+            let accounts = (0..max_ops)
+                .map(|_| {
+                    let (address, keypair) = get_key_pair();
+                    (address, Arc::new(keypair))
+                })
+                .collect();
+            let workload_builder =
+                Box::<dyn WorkloadBuilder<dyn Payload>>::from(Box::new(GasFanoutWorkloadBuilder {
+                    accounts,
+                    fanout_width,
+                }));
+            let builder_info = WorkloadBuilderInfo {
+                workload_params,
+                workload_builder,
+            };
+            Some(builder_info)
+        }
+    }
+}
+
+#[async_trait]
+impl WorkloadBuilder<dyn Payload> for GasFanoutWorkloadBuilder {
+    async fn generate_coin_config_for_init(&self) -> Vec<GasCoinConfig> {
+        // Coins to split and merge, they are never used to pay for gas. The bank matches coins
+        // to configs by owner only, so these must be as large as the gas coins below
+        let amount = MAX_GAS_FOR_TESTING + ESTIMATED_COMPUTATION_COST;
+        self.accounts
+            .iter()
+            .map(|(address, keypair)| GasCoinConfig {
+                amount,
+                address: *address,
+                keypair: keypair.clone(),
+            })
+            .collect()
+    }
+    async fn generate_coin_config_for_payloads(&self) -> Vec<GasCoinConfig> {
+        let amount = MAX_GAS_FOR_TESTING + ESTIMATED_COMPUTATION_COST;
+        self.accounts
+            .iter()
+            .map(|(address, keypair)| GasCoinConfig {
+                amount,
+                address: *address,
+                keypair: keypair.clone(),
+            })
+            .collect()
+    }
+    async fn build(
+        &self,
+        init_gas: Vec<Gas>,
+        payload_gas: Vec<Gas>,
+    ) -> Box<dyn Workload<dyn Payload>> {
+        let coins_by_address: HashMap<SuiAddress, ObjectRef> = init_gas
+            .into_iter()
+            .map(|(coin, address, _)| (address, coin))
+            .collect();
+        let payload_coins = payload_gas
+            .into_iter()
+            .map(|gas| (coins_by_address[&gas.1], gas))
+            .collect();
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(GasFanoutWorkload {
+            fanout_width: self.fanout_width,
+            payload_coins,
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub struct GasFanoutWorkload {
+    pub fanout_width: u64,
+    /// The coin to split and merge for each payload, along with the gas coin paying for it
+    pub payload_coins: Vec<(ObjectRef, Gas)>,
+}
+
+#[async_trait]
+impl Workload<dyn Payload> for GasFanoutWorkload {
+    fn name(&self) -> &'static str {
+        "gas_fanout"
+    }
+    fn describe(&self) -> String {
+        format!("{}(fanout_width = {})", self.name(), self.fanout_width)
+    }
+    fn senders(&self) -> Vec<SuiAddress> {
+        self.payload_coins.iter().map(|(_, gas)| gas.1).collect()
+    }
+    async fn init(
+        &mut self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        _system_state_observer: Arc<SystemStateObserver>,
+    ) {
+    }
+    async fn make_test_payloads(
+        &self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) -> Vec<Box<dyn Payload>> {
+        info!("Creating gas fanout txn payloads, hang tight..");
+        self.payload_coins
+            .iter()
+            .map(|(coin, gas)| {
+                Box::new(GasFanoutTestPayload {
+                    coin: *coin,
+                    fanout_coins: vec![],
+                    fanout_width: self.fanout_width,
+                    gas: gas.clone(),
+                    system_state_observer: system_state_observer.clone(),
+                })
+            })
+            .map(|b| Box::<dyn Payload>::from(b))
+            .collect()
+    }
+}
@@ -0,0 +1,235 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::system_state_observer::SystemStateObserver;
+use crate::workloads::payload::Payload;
+use crate::workloads::workload::{
+    Workload, WorkloadBuilder, ESTIMATED_COMPUTATION_COST, MAX_GAS_FOR_TESTING,
+};
+use crate::workloads::{
+    workload_target_qps, Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams,
+};
+use crate::{ExecutionEffects, ValidatorProxy};
+use async_trait::async_trait;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::sync::Arc;
+use sui_types::{
+    base_types::{random_object_ref, SuiAddress},
+    crypto::{get_key_pair, get_key_pair_from_rng, AccountKeyPair},
+    messages::{
+        TransactionData, TransactionDataAPI, VerifiedTransaction, TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+    },
+    utils::to_sender_signed_transaction,
+};
+use tracing::{error, info};
+
+/// The ways a transaction is made invalid, each must be rejected before the gas coin is locked so
+/// the payload can keep using it.
+#[derive(Debug, Clone, Copy)]
+enum InvalidTransactionKind {
+    /// Signed by a key other than the sender's
+    BadSignature,
+    /// Pays for gas with an object which does not exist
+    UnknownGasObject,
+    /// Gas budget below the minimum a validator accepts
+    GasBudgetTooLow,
+}
+
+const INVALID_TRANSACTION_KINDS: &[InvalidTransactionKind] = &[
+    InvalidTransactionKind::BadSignature,
+    InvalidTransactionKind::UnknownGasObject,
+    InvalidTransactionKind::GasBudgetTooLow,
+];
+
+/// Transfers its gas coin to its own sender, making a fraction of the transactions invalid so
+/// they are rejected by validators.
+#[derive(Debug)]
+pub struct InvalidTransactionTestPayload {
+    /// Fraction of the transactions made invalid, between 0.0 and 1.0
+    invalid_fraction: f32,
+    /// Whether the last transaction made was invalid
+    invalid: bool,
+    /// Picks which transactions are invalid and how
+    rng: StdRng,
+    gas: Gas,
+    system_state_observer: Arc<SystemStateObserver>,
+}
+
+impl std::fmt::Display for InvalidTransactionTestPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "invalid_transaction")
+    }
+}
+
+impl Payload for InvalidTransactionTestPayload {
+    fn make_new_payload(&mut self, effects: &ExecutionEffects) {
+        if !effects.is_ok() {
+            effects.print_gas_summary();
+            error!("Invalid transaction workload tx failed...");
+        }
+        self.gas.0 = effects.gas_object().0;
+    }
+    fn make_transaction(&mut self) -> VerifiedTransaction {
+        let gas_price = self.system_state_observer.gas_price();
+        self.invalid = self.rng.gen_bool(self.invalid_fraction as f64);
+        let (sender, keypair) = (self.gas.1, &self.gas.2);
+        let kind = self
+            .invalid
+            .then(|| *INVALID_TRANSACTION_KINDS.choose(&mut self.rng).unwrap());
+        let gas_object = match kind {
+            Some(InvalidTransactionKind::UnknownGasObject) => random_object_ref(),
+            _ => self.gas.0,
+        };
+        let gas_budget = match kind {
+            Some(InvalidTransactionKind::GasBudgetTooLow) => 1,
+            _ => gas_price * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+        };
+        let mut data = TransactionData::new_transfer_sui(
+            sender, sender, None, gas_object, gas_budget, gas_price,
+        );
+        *data.expiration_mut_for_testing() = self.system_state_observer.expiration();
+        match kind {
+            Some(InvalidTransactionKind::BadSignature) => {
+                let (_, other_keypair) = get_key_pair_from_rng::<AccountKeyPair, _>(&mut self.rng);
+                to_sender_signed_transaction(data, &other_keypair)
+            }
+            _ => to_sender_signed_transaction(data, keypair),
+        }
+    }
+    fn expects_rejection(&self) -> bool {
+        self.invalid
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidTransactionWorkloadBuilder {
+    num_payloads: u64,
+    invalid_fraction: f32,
+    rng_seed: u64,
+}
+
+impl InvalidTransactionWorkloadBuilder {
+    pub fn from(
+        workload_weight: f32,
+        target_qps: u64,
+        num_workers: u64,
+        in_flight_ratio: u64,
+        invalid_fraction: f32,
+        rng_seed: u64,
+    ) -> Option<WorkloadBuilderInfo> {
+        assert!(
+            (0.0..=1.0).contains(&invalid_fraction),
+            "Invalid fraction must be between 0.0 and 1.0"
+        );
+        let target_qps = workload_target_qps(workload_weight, target_qps);
+        let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
+        let max_ops = target_qps * in_flight_ratio;
+        if max_ops == 0 || num_workers == 0 {
+            None
+        } else {
+            let workload_params = WorkloadParams {
+                target_qps,
+                num_workers,
+                max_ops,
+            };
+            let workload_builder = Box::<dyn WorkloadBuilder<dyn Payload>>::from(Box::new(
+                InvalidTransactionWorkloadBuilder {
+                    num_payloads: max_ops,
+                    invalid_fraction,
+                    rng_seed,
+                },
+            ));
+            let builder_info = WorkloadBuilderInfo {
+                workload_params,
+                workload_builder,
+            };
+            Some(builder_info)
+        }
+    }
+}
+
+#[async_trait]
+impl WorkloadBuilder<dyn Payload> for InvalidTransactionWorkloadBuilder {
+    async fn generate_coin_config_for_init(&self) -> Vec<GasCoinConfig> {
+        vec![]
+    }
+    async fn generate_coin_config_for_payloads(&self) -> Vec<GasCoinConfig> {
+        let mut configs = vec![];
+        let amount = MAX_GAS_FOR_TESTING + ESTIMATED_COMPUTATION_COST;
+        // Gas coins for running workload
+        for _i in 0..self.num_payloads {
+            let (address, keypair) = get_key_pair();
+            configs.push(GasCoinConfig {
+                amount,
+                address,
+                keypair: Arc::new(keypair),
+            });
+        }
+        configs
+    }
+    async fn build(
+        &self,
+        _init_gas: Vec<Gas>,
+        payload_gas: Vec<Gas>,
+    ) -> Box<dyn Workload<dyn Payload>> {
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(InvalidTransactionWorkload {
+            invalid_fraction: self.invalid_fraction,
+            rng_seed: self.rng_seed,
+            payload_gas,
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidTransactionWorkload {
+    pub invalid_fraction: f32,
+    /// Seed of the choices of which transactions of each payload are invalid
+    pub rng_seed: u64,
+    pub payload_gas: Vec<Gas>,
+}
+
+#[async_trait]
+impl Workload<dyn Payload> for InvalidTransactionWorkload {
+    fn name(&self) -> &'static str {
+        "invalid_transaction"
+    }
+    fn senders(&self) -> Vec<SuiAddress> {
+        self.payload_gas.iter().map(|gas| gas.1).collect()
+    }
+    fn describe(&self) -> String {
+        format!(
+            "{}(invalid_fraction = {})",
+            self.name(),
+            self.invalid_fraction
+        )
+    }
+    async fn init(
+        &mut self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        _system_state_observer: Arc<SystemStateObserver>,
+    ) {
+    }
+    async fn make_test_payloads(
+        &self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) -> Vec<Box<dyn Payload>> {
+        info!("Creating invalid transaction txn payloads, hang tight..");
+        let mut seeds = StdRng::seed_from_u64(self.rng_seed);
+        self.payload_gas
+            .iter()
+            .map(|gas| {
+                Box::new(InvalidTransactionTestPayload {
+                    invalid_fraction: self.invalid_fraction,
+                    invalid: false,
+                    rng: StdRng::seed_from_u64(seeds.gen()),
+                    gas: gas.clone(),
+                    system_state_observer: system_state_observer.clone(),
+                })
+            })
+            .map(|b| Box::<dyn Payload>::from(b))
+            .collect()
+    }
+}
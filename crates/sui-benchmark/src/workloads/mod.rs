@@ -3,12 +3,27 @@
 
 pub mod adversarial;
 pub mod batch_payment;
+pub mod clock_read;
+pub mod coin_ops;
 pub mod delegation;
+pub mod dynamic_field;
+pub mod equivocation;
+pub mod faucet;
+pub mod fullnode_query;
+pub mod gas_fanout;
+pub mod invalid_transaction;
+pub mod multi_input;
+pub mod nft;
 pub mod payload;
+pub mod publish;
+pub mod sequenced;
 pub mod shared_counter;
+pub mod size_mix;
 pub mod transfer_object;
+pub mod upgrade;
 pub mod workload;
 pub mod workload_configuration;
+pub mod wrap_unwrap;
 
 use std::sync::Arc;
 
@@ -38,6 +53,18 @@ pub struct WorkloadInfo {
 
 pub type Gas = (ObjectRef, SuiAddress, Arc<AccountKeyPair>);
 
+/// Share of `target_qps` that goes to a workload of the given weight. A workload with a nonzero
+/// weight always gets at least 1 qps, rather than being dropped from the mix when its share of
+/// a small total rounds down to nothing.
+pub fn workload_target_qps(workload_weight: f32, target_qps: u64) -> u64 {
+    let qps = (workload_weight * target_qps as f32) as u64;
+    if workload_weight > 0.0 && target_qps > 0 {
+        qps.max(1)
+    } else {
+        qps
+    }
+}
+
 #[derive(Clone)]
 pub struct GasCoinConfig {
     // amount of SUI to transfer to this gas coin
@@ -0,0 +1,246 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::system_state_observer::SystemStateObserver;
+use crate::workloads::payload::Payload;
+use crate::workloads::workload::{
+    Workload, WorkloadBuilder, ESTIMATED_COMPUTATION_COST, MAX_BUDGET, MAX_GAS_FOR_TESTING,
+    STORAGE_COST_PER_COIN,
+};
+use crate::workloads::{
+    workload_target_qps, Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams,
+};
+use crate::{ExecutionEffects, ValidatorProxy};
+use async_trait::async_trait;
+use std::sync::Arc;
+use sui_types::{
+    base_types::{ObjectRef, SuiAddress},
+    crypto::{get_key_pair, AccountKeyPair},
+    messages::{
+        TransactionData, TransactionDataAPI, VerifiedTransaction, TEST_ONLY_GAS_UNIT_FOR_GENERIC,
+    },
+    object::Owner,
+    utils::to_sender_signed_transaction,
+};
+use tracing::{error, info};
+
+/// Merging the input coins takes one argument per coin but the first, and splitting them back
+/// one per new coin, which must stay within the protocol's 512 arguments per command
+pub const MAX_INPUTS_PER_TX: u64 = 513;
+
+/// Value of each of the coins merged and split back, in MIST. Only their number matters
+const INPUT_COIN_AMOUNT: u64 = 1;
+
+/// Merges `inputs_per_tx` coins into one and splits it back into as many coins in a single
+/// transaction, so that every transaction locks and loads that many owned objects besides its gas
+/// coin. The first transaction of each payload splits the coins off its gas coin.
+#[derive(Debug)]
+pub struct MultiInputTestPayload {
+    /// Coins to take as inputs of the next transaction, the first of which the others are merged
+    /// into. Fewer than `inputs_per_tx` until the first transaction created them
+    coins: Vec<ObjectRef>,
+    inputs_per_tx: u64,
+    gas: Gas,
+    system_state_observer: Arc<SystemStateObserver>,
+}
+
+impl std::fmt::Display for MultiInputTestPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "multi_input")
+    }
+}
+
+impl Payload for MultiInputTestPayload {
+    fn make_new_payload(&mut self, effects: &ExecutionEffects) {
+        if !effects.is_ok() {
+            effects.print_gas_summary();
+            error!("Multi input tx failed...");
+        }
+        self.gas.0 = effects.gas_object().0;
+        // The merge keeps the first coin, the split creates the others anew
+        let merged_into = self.coins.first().and_then(|first| {
+            effects
+                .mutated()
+                .into_iter()
+                .find(|(object_ref, _)| object_ref.0 == first.0)
+        });
+        self.coins = merged_into
+            .into_iter()
+            .chain(
+                effects
+                    .created()
+                    .into_iter()
+                    .filter(|(_, owner)| *owner == Owner::AddressOwner(self.gas.1)),
+            )
+            .map(|(object_ref, _)| object_ref)
+            .collect();
+    }
+    fn make_transaction(&mut self) -> VerifiedTransaction {
+        let gas_price = self.system_state_observer.gas_price();
+        let sender = self.gas.1;
+        let gas_budget = (gas_price * TEST_ONLY_GAS_UNIT_FOR_GENERIC
+            + STORAGE_COST_PER_COIN * self.inputs_per_tx)
+            .min(MAX_BUDGET);
+        let mut data = if self.coins.len() as u64 == self.inputs_per_tx {
+            let num_splits = self.inputs_per_tx as usize - 1;
+            TransactionData::new_pay(
+                sender,
+                self.coins.clone(),
+                vec![sender; num_splits],
+                vec![INPUT_COIN_AMOUNT; num_splits],
+                self.gas.0,
+                gas_budget,
+                gas_price,
+            )
+        } else {
+            let num_coins = self.inputs_per_tx as usize;
+            TransactionData::new_pay_sui(
+                sender,
+                vec![],
+                vec![sender; num_coins],
+                vec![INPUT_COIN_AMOUNT; num_coins],
+                self.gas.0,
+                gas_budget,
+                gas_price,
+            )
+        }
+        .unwrap();
+        *data.expiration_mut_for_testing() = self.system_state_observer.expiration();
+        to_sender_signed_transaction(data, &self.gas.2)
+    }
+    fn verify_effects(&self, effects: &ExecutionEffects) -> Result<(), String> {
+        if !effects.is_ok() {
+            return Err(format!("multi input tx failed with {}", effects.status()));
+        }
+        if self.coins.len() as u64 == self.inputs_per_tx {
+            let deleted = effects.deleted().len() as u64;
+            if deleted != self.inputs_per_tx - 1 {
+                return Err(format!(
+                    "merge deleted {deleted} coins, expected {}",
+                    self.inputs_per_tx - 1
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct MultiInputWorkloadBuilder {
+    num_payloads: u64,
+    inputs_per_tx: u64,
+}
+
+impl MultiInputWorkloadBuilder {
+    pub fn from(
+        workload_weight: f32,
+        target_qps: u64,
+        num_workers: u64,
+        in_flight_ratio: u64,
+        inputs_per_tx: u64,
+    ) -> Option<WorkloadBuilderInfo> {
+        assert!(
+            (2..=MAX_INPUTS_PER_TX).contains(&inputs_per_tx),
+            "Inputs per transaction must be between 2 and {MAX_INPUTS_PER_TX}"
+        );
+        let target_qps = workload_target_qps(workload_weight, target_qps);
+        let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
+        let max_ops = target_qps * in_flight_ratio;
+        if max_ops == 0 || num_workers == 0 {
+            None
+        } else {
+            let workload_params = WorkloadParams {
+                target_qps,
+                num_workers,
+                max_ops,
+            };
+            let workload_builder = Box::<dyn WorkloadBuilder<dyn Payload>>::from(Box::new(
+                MultiInputWorkloadBuilder {
+                    num_payloads: max_ops,
+                    inputs_per_tx,
+                },
+            ));
+            let builder_info = WorkloadBuilderInfo {
+                workload_params,
+                workload_builder,
+            };
+            Some(builder_info)
+        }
+    }
+}
+
+#[async_trait]
+impl WorkloadBuilder<dyn Payload> for MultiInputWorkloadBuilder {
+    async fn generate_coin_config_for_init(&self) -> Vec<GasCoinConfig> {
+        vec![]
+    }
+    async fn generate_coin_config_for_payloads(&self) -> Vec<GasCoinConfig> {
+        // Each payload sends from an account of its own, so that payloads never contend for the
+        // locks of each other's coins
+        let amount = MAX_GAS_FOR_TESTING + ESTIMATED_COMPUTATION_COST;
+        (0..self.num_payloads)
+            .map(|_| {
+                let (address, keypair) = get_key_pair::<AccountKeyPair>();
+                GasCoinConfig {
+                    amount,
+                    address,
+                    keypair: Arc::new(keypair),
+                }
+            })
+            .collect()
+    }
+    async fn build(
+        &self,
+        _init_gas: Vec<Gas>,
+        payload_gas: Vec<Gas>,
+    ) -> Box<dyn Workload<dyn Payload>> {
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(MultiInputWorkload {
+            inputs_per_tx: self.inputs_per_tx,
+            payload_gas,
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub struct MultiInputWorkload {
+    pub inputs_per_tx: u64,
+    pub payload_gas: Vec<Gas>,
+}
+
+#[async_trait]
+impl Workload<dyn Payload> for MultiInputWorkload {
+    fn name(&self) -> &'static str {
+        "multi_input"
+    }
+    fn describe(&self) -> String {
+        format!("{}(inputs_per_tx = {})", self.name(), self.inputs_per_tx)
+    }
+    fn senders(&self) -> Vec<SuiAddress> {
+        self.payload_gas.iter().map(|gas| gas.1).collect()
+    }
+    async fn init(
+        &mut self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        _system_state_observer: Arc<SystemStateObserver>,
+    ) {
+    }
+    async fn make_test_payloads(
+        &self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) -> Vec<Box<dyn Payload>> {
+        info!("Creating multi input txn payloads, hang tight..");
+        self.payload_gas
+            .iter()
+            .map(|gas| {
+                Box::new(MultiInputTestPayload {
+                    coins: vec![],
+                    inputs_per_tx: self.inputs_per_tx,
+                    gas: gas.clone(),
+                    system_state_observer: system_state_observer.clone(),
+                })
+            })
+            .map(|b| Box::<dyn Payload>::from(b))
+            .collect()
+    }
+}
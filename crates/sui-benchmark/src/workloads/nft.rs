@@ -0,0 +1,246 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::in_memory_wallet::move_call_pt_impl;
+use crate::system_state_observer::SystemStateObserver;
+use crate::workloads::payload::Payload;
+use crate::workloads::workload::{
+    Workload, WorkloadBuilder, ESTIMATED_COMPUTATION_COST, MAX_GAS_FOR_TESTING,
+};
+use crate::workloads::{
+    workload_target_qps, Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams,
+};
+use crate::{ExecutionEffects, ValidatorProxy};
+use async_trait::async_trait;
+use std::path::PathBuf;
+use std::sync::Arc;
+use sui_core::test_utils::make_transfer_object_transaction;
+use sui_types::{
+    base_types::{ObjectID, ObjectRef, SuiAddress},
+    crypto::{get_key_pair, AccountKeyPair},
+    messages::{
+        VerifiedTransaction, TEST_ONLY_GAS_UNIT_FOR_GENERIC, TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+    },
+    object::Owner,
+};
+use test_utils::messages::create_publish_move_package_transaction;
+use test_utils::transaction::parse_package_ref;
+use tracing::{error, info};
+
+const NFT_NAME: &[u8] = b"Sui Benchmark NFT";
+const NFT_DESCRIPTION: &[u8] = b"An NFT minted and transferred by the sui-benchmark nft workload";
+const NFT_URL: &[u8] = b"https://sui.io/_nuxt/img/sui-logo.8d3c44e.svg";
+
+/// Alternates between minting an NFT to the sender and transferring that NFT away.
+#[derive(Debug)]
+pub struct NftTestPayload {
+    package_id: ObjectID,
+    /// The NFT minted by the previous transaction, to be transferred by the next one
+    nft: Option<ObjectRef>,
+    /// Receives every NFT minted by this payload
+    recipient: SuiAddress,
+    gas: Gas,
+    system_state_observer: Arc<SystemStateObserver>,
+}
+
+impl std::fmt::Display for NftTestPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "nft")
+    }
+}
+
+impl Payload for NftTestPayload {
+    fn make_new_payload(&mut self, effects: &ExecutionEffects) {
+        if !effects.is_ok() {
+            effects.print_gas_summary();
+            error!("Nft tx failed...");
+        }
+        self.gas.0 = effects.gas_object().0;
+        // A mint creates the NFT owned by the sender, a transfer creates nothing
+        self.nft = effects
+            .created()
+            .into_iter()
+            .find(|(_, owner)| *owner == Owner::AddressOwner(self.gas.1))
+            .map(|(object_ref, _)| object_ref);
+    }
+    fn follow_effects(&mut self, effects: &ExecutionEffects) {
+        // Mint from the package just published when following a publish, the sender keeps the
+        // NFT minted from the previous one
+        if let Some(package) = parse_package_ref(&effects.created()) {
+            self.package_id = package.0;
+            self.nft = None;
+        }
+    }
+    fn make_transaction(&mut self) -> VerifiedTransaction {
+        let gas_price = self.system_state_observer.gas_price();
+        match self.nft {
+            Some(nft) => make_transfer_object_transaction(
+                nft,
+                self.gas.0,
+                self.gas.1,
+                &self.gas.2,
+                self.recipient,
+                gas_price,
+            ),
+            None => move_call_pt_impl(
+                self.gas.1,
+                &self.gas.2,
+                self.package_id,
+                "nft",
+                "mint",
+                vec![],
+                vec![
+                    (&NFT_NAME.to_vec()).into(),
+                    (&NFT_DESCRIPTION.to_vec()).into(),
+                    (&NFT_URL.to_vec()).into(),
+                ],
+                &self.gas.0,
+                gas_price * TEST_ONLY_GAS_UNIT_FOR_GENERIC,
+                gas_price,
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct NftWorkloadBuilder {
+    num_payloads: u64,
+}
+
+impl NftWorkloadBuilder {
+    pub fn from(
+        workload_weight: f32,
+        target_qps: u64,
+        num_workers: u64,
+        in_flight_ratio: u64,
+    ) -> Option<WorkloadBuilderInfo> {
+        let target_qps = workload_target_qps(workload_weight, target_qps);
+        let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
+        let max_ops = target_qps * in_flight_ratio;
+        if max_ops == 0 || num_workers == 0 {
+            None
+        } else {
+            let workload_params = WorkloadParams {
+                target_qps,
+                num_workers,
+                max_ops,
+            };
+            let workload_builder =
+                Box::<dyn WorkloadBuilder<dyn Payload>>::from(Box::new(NftWorkloadBuilder {
+                    num_payloads: max_ops,
+                }));
+            let builder_info = WorkloadBuilderInfo {
+                workload_params,
+                workload_builder,
+            };
+            Some(builder_info)
+        }
+    }
+}
+
+#[async_trait]
+impl WorkloadBuilder<dyn Payload> for NftWorkloadBuilder {
+    async fn generate_coin_config_for_init(&self) -> Vec<GasCoinConfig> {
+        // Gas coin for publishing the nft package
+        let (address, keypair) = get_key_pair();
+        vec![GasCoinConfig {
+            amount: MAX_GAS_FOR_TESTING,
+            address,
+            keypair: Arc::new(keypair),
+        }]
+    }
+    async fn generate_coin_config_for_payloads(&self) -> Vec<GasCoinConfig> {
+        let mut configs = vec![];
+        let amount = MAX_GAS_FOR_TESTING + ESTIMATED_COMPUTATION_COST;
+        // Gas coins for running workload
+        for _i in 0..self.num_payloads {
+            let (address, keypair) = get_key_pair();
+            configs.push(GasCoinConfig {
+                amount,
+                address,
+                keypair: Arc::new(keypair),
+            });
+        }
+        configs
+    }
+    async fn build(
+        &self,
+        mut init_gas: Vec<Gas>,
+        payload_gas: Vec<Gas>,
+    ) -> Box<dyn Workload<dyn Payload>> {
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(NftWorkload {
+            package_id: None,
+            init_gas: init_gas.pop().unwrap(),
+            payload_gas,
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub struct NftWorkload {
+    pub package_id: Option<ObjectID>,
+    pub init_gas: Gas,
+    pub payload_gas: Vec<Gas>,
+}
+
+#[async_trait]
+impl Workload<dyn Payload> for NftWorkload {
+    fn name(&self) -> &'static str {
+        "nft"
+    }
+    fn senders(&self) -> Vec<SuiAddress> {
+        self.payload_gas.iter().map(|gas| gas.1).collect()
+    }
+    async fn init(
+        &mut self,
+        proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) {
+        if self.package_id.is_some() {
+            return;
+        }
+        let gas_price = system_state_observer.state.borrow().reference_gas_price;
+        let (gas, sender, keypair) = &self.init_gas;
+
+        info!("Publishing nft package");
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("src/workloads/data/nft");
+        let transaction = create_publish_move_package_transaction(
+            *gas,
+            path,
+            *sender,
+            keypair,
+            gas_price * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+            gas_price,
+        );
+        let effects = proxy
+            .execute_transaction_block(transaction.into())
+            .await
+            .unwrap();
+        self.package_id = Some(parse_package_ref(&effects.created()).unwrap().0);
+    }
+    async fn make_test_payloads(
+        &self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) -> Vec<Box<dyn Payload>> {
+        info!("Creating nft txn payloads, hang tight..");
+        let package_id = self
+            .package_id
+            .expect("Nft workload must be initialized before creating payloads");
+        self.payload_gas
+            .iter()
+            .map(|gas| {
+                let (recipient, _) = get_key_pair::<AccountKeyPair>();
+                Box::new(NftTestPayload {
+                    package_id,
+                    nft: None,
+                    recipient,
+                    gas: gas.clone(),
+                    system_state_observer: system_state_observer.clone(),
+                })
+            })
+            .map(|b| Box::<dyn Payload>::from(b))
+            .collect()
+    }
+}
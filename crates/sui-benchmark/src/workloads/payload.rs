@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::ExecutionEffects;
+use futures::future::BoxFuture;
 use std::fmt::Display;
 use sui_types::messages::VerifiedTransaction;
 
@@ -12,4 +13,34 @@ use sui_types::messages::VerifiedTransaction;
 pub trait Payload: Send + Sync + std::fmt::Debug + Display {
     fn make_new_payload(&mut self, effects: &ExecutionEffects);
     fn make_transaction(&mut self) -> VerifiedTransaction;
+    /// Read-only payloads return the next query to issue here, in which case the driver runs it
+    /// instead of calling `make_transaction()` and records its latency apart from transactions.
+    fn make_query(&mut self) -> Option<BoxFuture<'static, anyhow::Result<()>>> {
+        None
+    }
+    /// Whether the last transaction returned by `make_transaction()` was made invalid on purpose.
+    /// The driver counts the rejection of such a transaction apart from errors and does not retry
+    /// it, while executing it is counted as an error.
+    fn expects_rejection(&self) -> bool {
+        false
+    }
+    /// Check that `effects`, of the last transaction returned by `make_transaction()`, are what
+    /// that transaction should have done, beyond it being certified. Called before
+    /// `make_new_payload()` for a sample of the successful transactions, see
+    /// `BenchDriver::with_verify_effects`.
+    fn verify_effects(&self, _effects: &ExecutionEffects) -> Result<(), String> {
+        Ok(())
+    }
+    /// Whether other transactions were sequenced on a shared object between the previous
+    /// transaction of this payload and the last one returned by `make_transaction()`, given the
+    /// `effects` of the latter. Such a transaction read-modify-writes a version of the object it
+    /// did not see, which is the cost of contention. Called before `make_new_payload()`.
+    fn is_shared_object_conflict(&self, _effects: &ExecutionEffects) -> bool {
+        false
+    }
+    /// Called by a `SequencedWorkload` with the `effects` of the transaction of the previous step
+    /// of the sequence, which this payload's next transaction follows, before `make_transaction()`.
+    /// Payloads run as a later step pick up the objects they need from them here, e.g. the
+    /// package the previous step published.
+    fn follow_effects(&mut self, _effects: &ExecutionEffects) {}
 }
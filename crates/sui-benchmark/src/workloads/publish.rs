@@ -0,0 +1,233 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::system_state_observer::SystemStateObserver;
+use crate::workloads::payload::Payload;
+use crate::workloads::workload::{
+    Workload, WorkloadBuilder, ESTIMATED_COMPUTATION_COST, MAX_GAS_FOR_TESTING,
+};
+use crate::workloads::{
+    workload_target_qps, Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams,
+};
+use crate::{ExecutionEffects, ValidatorProxy};
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use sui_types::{
+    base_types::{ObjectID, SuiAddress},
+    crypto::get_key_pair,
+    messages::{
+        TransactionData, TransactionDataAPI, VerifiedTransaction, TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+    },
+    utils::to_sender_signed_transaction,
+    MOVE_STDLIB_OBJECT_ID, SUI_FRAMEWORK_OBJECT_ID,
+};
+use test_utils::messages::compile_move_package;
+use tracing::{error, info};
+
+/// Publishes the same package with every transaction, exercising package verification and loading.
+#[derive(Debug)]
+pub struct PublishTestPayload {
+    package: Arc<CompiledModules>,
+    gas: Gas,
+    system_state_observer: Arc<SystemStateObserver>,
+}
+
+impl std::fmt::Display for PublishTestPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "publish")
+    }
+}
+
+impl Payload for PublishTestPayload {
+    fn make_new_payload(&mut self, effects: &ExecutionEffects) {
+        if !effects.is_ok() {
+            effects.print_gas_summary();
+            error!("Publish tx failed...");
+        }
+        self.gas.0 = effects.gas_object().0;
+    }
+    fn make_transaction(&mut self) -> VerifiedTransaction {
+        let gas_price = self.system_state_observer.gas_price();
+        let mut data = TransactionData::new_module(
+            self.gas.1,
+            self.gas.0,
+            self.package.modules.clone(),
+            self.package.dependencies.clone(),
+            gas_price * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+            gas_price,
+        );
+        *data.expiration_mut_for_testing() = self.system_state_observer.expiration();
+        to_sender_signed_transaction(data, &self.gas.2)
+    }
+}
+
+/// The modules published by the workload, along with the packages they depend on.
+#[derive(Debug)]
+pub struct CompiledModules {
+    pub(crate) modules: Vec<Vec<u8>>,
+    pub(crate) dependencies: Vec<ObjectID>,
+}
+
+impl CompiledModules {
+    /// Read every `.mv` file in `dir`, in file name order, which must be a valid publishing order.
+    /// The modules may only depend on the Move standard library and the Sui framework.
+    fn from_dir(dir: &Path) -> Self {
+        let mut paths: Vec<_> = std::fs::read_dir(dir)
+            .unwrap_or_else(|e| panic!("Failed to read compiled modules from {dir:?}: {e}"))
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().map_or(false, |ext| ext == "mv"))
+            .collect();
+        paths.sort();
+        assert!(!paths.is_empty(), "No compiled modules found in {dir:?}");
+        Self {
+            modules: paths
+                .iter()
+                .map(|path| std::fs::read(path).unwrap())
+                .collect(),
+            dependencies: vec![MOVE_STDLIB_OBJECT_ID, SUI_FRAMEWORK_OBJECT_ID],
+        }
+    }
+
+    /// Compile the small package bundled with the benchmark.
+    pub(crate) fn bundled() -> Self {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("src/workloads/data/nft");
+        let (modules, dependencies) = compile_move_package(&path);
+        Self {
+            modules,
+            dependencies,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct PublishWorkloadBuilder {
+    num_payloads: u64,
+    /// Directory of compiled modules to publish, the bundled package is used if not set
+    package_dir: Option<PathBuf>,
+}
+
+impl PublishWorkloadBuilder {
+    pub fn from(
+        workload_weight: f32,
+        target_qps: u64,
+        num_workers: u64,
+        in_flight_ratio: u64,
+        package_dir: Option<PathBuf>,
+    ) -> Option<WorkloadBuilderInfo> {
+        let target_qps = workload_target_qps(workload_weight, target_qps);
+        let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
+        let max_ops = target_qps * in_flight_ratio;
+        if max_ops == 0 || num_workers == 0 {
+            None
+        } else {
+            let workload_params = WorkloadParams {
+                target_qps,
+                num_workers,
+                max_ops,
+            };
+            let workload_builder =
+                Box::<dyn WorkloadBuilder<dyn Payload>>::from(Box::new(PublishWorkloadBuilder {
+                    num_payloads: max_ops,
+                    package_dir,
+                }));
+            let builder_info = WorkloadBuilderInfo {
+                workload_params,
+                workload_builder,
+            };
+            Some(builder_info)
+        }
+    }
+}
+
+#[async_trait]
+impl WorkloadBuilder<dyn Payload> for PublishWorkloadBuilder {
+    async fn generate_coin_config_for_init(&self) -> Vec<GasCoinConfig> {
+        vec![]
+    }
+    async fn generate_coin_config_for_payloads(&self) -> Vec<GasCoinConfig> {
+        let mut configs = vec![];
+        let amount = MAX_GAS_FOR_TESTING + ESTIMATED_COMPUTATION_COST;
+        // Gas coins for running workload
+        for _i in 0..self.num_payloads {
+            let (address, keypair) = get_key_pair();
+            configs.push(GasCoinConfig {
+                amount,
+                address,
+                keypair: Arc::new(keypair),
+            });
+        }
+        configs
+    }
+    async fn build(
+        &self,
+        _init_gas: Vec<Gas>,
+        payload_gas: Vec<Gas>,
+    ) -> Box<dyn Workload<dyn Payload>> {
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(PublishWorkload {
+            package_dir: self.package_dir.clone(),
+            package: None,
+            payload_gas,
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub struct PublishWorkload {
+    pub package_dir: Option<PathBuf>,
+    pub package: Option<Arc<CompiledModules>>,
+    pub payload_gas: Vec<Gas>,
+}
+
+#[async_trait]
+impl Workload<dyn Payload> for PublishWorkload {
+    fn name(&self) -> &'static str {
+        "publish"
+    }
+    fn senders(&self) -> Vec<SuiAddress> {
+        self.payload_gas.iter().map(|gas| gas.1).collect()
+    }
+    async fn init(
+        &mut self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        _system_state_observer: Arc<SystemStateObserver>,
+    ) {
+        if self.package.is_some() {
+            return;
+        }
+        let package = match &self.package_dir {
+            Some(dir) => {
+                info!("Loading compiled modules from {dir:?}");
+                CompiledModules::from_dir(dir)
+            }
+            None => {
+                info!("Compiling bundled package");
+                CompiledModules::bundled()
+            }
+        };
+        self.package = Some(Arc::new(package));
+    }
+    async fn make_test_payloads(
+        &self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) -> Vec<Box<dyn Payload>> {
+        info!("Creating publish txn payloads, hang tight..");
+        let package = self
+            .package
+            .clone()
+            .expect("Publish workload must be initialized before creating payloads");
+        self.payload_gas
+            .iter()
+            .map(|gas| {
+                Box::new(PublishTestPayload {
+                    package: package.clone(),
+                    gas: gas.clone(),
+                    system_state_observer: system_state_observer.clone(),
+                })
+            })
+            .map(|b| Box::<dyn Payload>::from(b))
+            .collect()
+    }
+}
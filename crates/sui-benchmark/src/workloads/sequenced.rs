@@ -0,0 +1,214 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::system_state_observer::SystemStateObserver;
+use crate::workloads::payload::Payload;
+use crate::workloads::workload::{Workload, WorkloadBuilder};
+use crate::workloads::{Gas, GasCoinConfig, WorkloadBuilderInfo};
+use crate::{ExecutionEffects, ValidatorProxy};
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use sui_types::base_types::SuiAddress;
+use sui_types::messages::VerifiedTransaction;
+use tracing::info;
+
+/// Takes turns between a payload of the first step of a sequence and the corresponding payload of
+/// the second step: a transaction of `then` is only made once the previous transaction of `first`
+/// is confirmed, and `then` is handed that transaction's effects with `Payload::follow_effects`
+/// to pick up the objects it needs from them, e.g. the package it published.
+#[derive(Debug)]
+pub struct SequencedTestPayload {
+    name: &'static str,
+    first: Box<dyn Payload>,
+    then: Box<dyn Payload>,
+    /// Whether the transaction in flight, or the next one to make, is a transaction of `then`
+    then_turn: bool,
+}
+
+impl std::fmt::Display for SequencedTestPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl SequencedTestPayload {
+    fn current(&self) -> &dyn Payload {
+        if self.then_turn {
+            &*self.then
+        } else {
+            &*self.first
+        }
+    }
+}
+
+impl Payload for SequencedTestPayload {
+    fn make_new_payload(&mut self, effects: &ExecutionEffects) {
+        if self.then_turn {
+            self.then.make_new_payload(effects);
+            self.then_turn = false;
+        } else {
+            self.first.make_new_payload(effects);
+            // A failed first step is retried rather than followed
+            if effects.is_ok() {
+                self.then.follow_effects(effects);
+                self.then_turn = true;
+            }
+        }
+    }
+    fn make_transaction(&mut self) -> VerifiedTransaction {
+        if self.then_turn {
+            self.then.make_transaction()
+        } else {
+            self.first.make_transaction()
+        }
+    }
+    fn expects_rejection(&self) -> bool {
+        self.current().expects_rejection()
+    }
+    fn verify_effects(&self, effects: &ExecutionEffects) -> Result<(), String> {
+        self.current().verify_effects(effects)
+    }
+    fn is_shared_object_conflict(&self, effects: &ExecutionEffects) -> bool {
+        self.current().is_shared_object_conflict(effects)
+    }
+}
+
+/// Builds a `SequencedWorkload` out of the builders of its two steps, which are given the gas
+/// coins they ask for as if they were built on their own.
+#[derive(Debug)]
+pub struct SequencedWorkloadBuilder {
+    name: &'static str,
+    first: Box<dyn WorkloadBuilder<dyn Payload>>,
+    then: Box<dyn WorkloadBuilder<dyn Payload>>,
+    /// Number of the init and payload coins asked for by `first`, which come before those asked
+    /// for by `then`, recorded when generating the coin configs to split the coins in `build()`
+    first_init_coins: AtomicUsize,
+    first_payload_coins: AtomicUsize,
+}
+
+impl SequencedWorkloadBuilder {
+    /// Sequence the workloads built by `first` and `then`, which should be given the same weight
+    /// so that they have as many payloads each. The sequenced workload is run with the parameters
+    /// of `first`, and is not run if either step would not be.
+    pub fn from(
+        name: &'static str,
+        first: Option<WorkloadBuilderInfo>,
+        then: Option<WorkloadBuilderInfo>,
+    ) -> Option<WorkloadBuilderInfo> {
+        let (first, then) = (first?, then?);
+        let workload_builder =
+            Box::<dyn WorkloadBuilder<dyn Payload>>::from(Box::new(SequencedWorkloadBuilder {
+                name,
+                first: first.workload_builder,
+                then: then.workload_builder,
+                first_init_coins: AtomicUsize::new(0),
+                first_payload_coins: AtomicUsize::new(0),
+            }));
+        Some(WorkloadBuilderInfo {
+            workload_params: first.workload_params,
+            workload_builder,
+        })
+    }
+}
+
+#[async_trait]
+impl WorkloadBuilder<dyn Payload> for SequencedWorkloadBuilder {
+    async fn generate_coin_config_for_init(&self) -> Vec<GasCoinConfig> {
+        let mut configs = self.first.generate_coin_config_for_init().await;
+        self.first_init_coins
+            .store(configs.len(), Ordering::Relaxed);
+        configs.extend(self.then.generate_coin_config_for_init().await);
+        configs
+    }
+    async fn generate_coin_config_for_payloads(&self) -> Vec<GasCoinConfig> {
+        let mut configs = self.first.generate_coin_config_for_payloads().await;
+        self.first_payload_coins
+            .store(configs.len(), Ordering::Relaxed);
+        configs.extend(self.then.generate_coin_config_for_payloads().await);
+        configs
+    }
+    async fn build(
+        &self,
+        mut init_gas: Vec<Gas>,
+        mut payload_gas: Vec<Gas>,
+    ) -> Box<dyn Workload<dyn Payload>> {
+        let then_init_gas = init_gas.split_off(self.first_init_coins.load(Ordering::Relaxed));
+        let then_payload_gas =
+            payload_gas.split_off(self.first_payload_coins.load(Ordering::Relaxed));
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(SequencedWorkload {
+            name: self.name,
+            first: self.first.build(init_gas, payload_gas).await,
+            then: self.then.build(then_init_gas, then_payload_gas).await,
+        }))
+    }
+}
+
+/// Runs the payloads of `then` after the corresponding payloads of `first`, for multi-step
+/// journeys where each transaction depends on the outcome of the one before it, unlike the
+/// independent workloads of a weighted mix. Payloads are paired up in the order both workloads
+/// make them, the surplus of either workload is left out.
+#[derive(Debug)]
+pub struct SequencedWorkload {
+    pub name: &'static str,
+    pub first: Box<dyn Workload<dyn Payload>>,
+    pub then: Box<dyn Workload<dyn Payload>>,
+}
+
+#[async_trait]
+impl Workload<dyn Payload> for SequencedWorkload {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+    fn describe(&self) -> String {
+        format!(
+            "{}({} then {})",
+            self.name(),
+            self.first.describe(),
+            self.then.describe()
+        )
+    }
+    fn senders(&self) -> Vec<SuiAddress> {
+        let mut senders = self.first.senders();
+        senders.extend(self.then.senders());
+        senders
+    }
+    async fn init(
+        &mut self,
+        proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) {
+        self.first
+            .init(proxy.clone(), system_state_observer.clone())
+            .await;
+        self.then.init(proxy, system_state_observer).await;
+    }
+    async fn make_test_payloads(
+        &self,
+        proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) -> Vec<Box<dyn Payload>> {
+        info!("Creating {} txn payloads, hang tight..", self.name);
+        let first = self
+            .first
+            .make_test_payloads(proxy.clone(), system_state_observer.clone())
+            .await;
+        let then = self
+            .then
+            .make_test_payloads(proxy, system_state_observer)
+            .await;
+        first
+            .into_iter()
+            .zip(then)
+            .map(|(first, then)| {
+                Box::new(SequencedTestPayload {
+                    name: self.name,
+                    first,
+                    then,
+                    then_turn: false,
+                })
+            })
+            .map(|b| Box::<dyn Payload>::from(b))
+            .collect()
+    }
+}
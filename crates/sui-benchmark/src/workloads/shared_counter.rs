@@ -9,15 +9,17 @@ use crate::workloads::workload::{
     STORAGE_COST_PER_COUNTER,
 };
 use crate::workloads::GasCoinConfig;
-use crate::workloads::{Gas, WorkloadBuilderInfo, WorkloadParams};
+use crate::workloads::{workload_target_qps, Gas, WorkloadBuilderInfo, WorkloadParams};
 use crate::{ExecutionEffects, ValidatorProxy};
 use async_trait::async_trait;
 use futures::future::join_all;
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
 use std::sync::Arc;
 use sui_types::crypto::get_key_pair;
 use sui_types::{
-    base_types::{ObjectDigest, ObjectID, SequenceNumber},
+    base_types::{ObjectDigest, ObjectID, SequenceNumber, SuiAddress},
     messages::VerifiedTransaction,
 };
 use test_utils::messages::{make_counter_create_transaction, make_counter_increment_transaction};
@@ -28,6 +30,8 @@ pub struct SharedCounterTestPayload {
     package_id: ObjectID,
     counter_id: ObjectID,
     counter_initial_shared_version: SequenceNumber,
+    /// Version of the counter written by the last transaction of this payload, if any
+    last_counter_version: Option<SequenceNumber>,
     gas: Gas,
     system_state_observer: Arc<SystemStateObserver>,
 }
@@ -44,6 +48,11 @@ impl Payload for SharedCounterTestPayload {
             effects.print_gas_summary();
             error!("Shared counter tx failed...");
         }
+        self.last_counter_version = effects
+            .mutated()
+            .iter()
+            .find(|(object_ref, _)| object_ref.0 == self.counter_id)
+            .map(|(object_ref, _)| object_ref.1);
         self.gas.0 = effects.gas_object().0;
     }
     fn make_transaction(&mut self) -> VerifiedTransaction {
@@ -54,18 +63,42 @@ impl Payload for SharedCounterTestPayload {
             self.counter_initial_shared_version,
             self.gas.1,
             &self.gas.2,
-            self.system_state_observer
-                .state
-                .borrow()
-                .reference_gas_price,
+            self.system_state_observer.gas_price(),
         )
     }
+    fn verify_effects(&self, effects: &ExecutionEffects) -> Result<(), String> {
+        if !effects.is_ok() {
+            return Err(format!("increment failed with {}", effects.status()));
+        }
+        if !effects
+            .mutated()
+            .iter()
+            .any(|(object_ref, _)| object_ref.0 == self.counter_id)
+        {
+            return Err(format!("counter {} was not mutated", self.counter_id));
+        }
+        Ok(())
+    }
+    fn is_shared_object_conflict(&self, effects: &ExecutionEffects) -> bool {
+        let Some(last_counter_version) = self.last_counter_version else {
+            return false;
+        };
+        // The counter is read at the version consensus assigned, which is the one this payload
+        // wrote last unless another transaction incremented it in between
+        effects
+            .shared_objects()
+            .iter()
+            .find(|object_ref| object_ref.0 == self.counter_id)
+            .map_or(false, |object_ref| object_ref.1 != last_counter_version)
+    }
 }
 
 #[derive(Debug)]
 pub struct SharedCounterWorkloadBuilder {
     num_counters: u64,
     num_payloads: u64,
+    contention_factor: f32,
+    rng_seed: u64,
 }
 
 impl SharedCounterWorkloadBuilder {
@@ -75,8 +108,14 @@ impl SharedCounterWorkloadBuilder {
         num_workers: u64,
         in_flight_ratio: u64,
         shared_counter_hotness_factor: u32,
+        contention_factor: f32,
+        rng_seed: u64,
     ) -> Option<WorkloadBuilderInfo> {
-        let target_qps = (workload_weight * target_qps as f32) as u64;
+        assert!(
+            (0.0..=1.0).contains(&contention_factor),
+            "Contention factor must be between 0.0 and 1.0"
+        );
+        let target_qps = workload_target_qps(workload_weight, target_qps);
         let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
         let max_ops = target_qps * in_flight_ratio;
         let shared_counter_ratio =
@@ -94,6 +133,8 @@ impl SharedCounterWorkloadBuilder {
                 SharedCounterWorkloadBuilder {
                     num_counters: num_shared_counters,
                     num_payloads: max_ops,
+                    contention_factor,
+                    rng_seed,
                 },
             ));
             let builder_info = WorkloadBuilderInfo {
@@ -153,6 +194,8 @@ impl WorkloadBuilder<dyn Payload> for SharedCounterWorkloadBuilder {
         Box::<dyn Workload<dyn Payload>>::from(Box::new(SharedCounterWorkload {
             basics_package_id: None,
             counters: vec![],
+            contention_factor: self.contention_factor,
+            rng_seed: self.rng_seed,
             init_gas,
             payload_gas,
         }))
@@ -163,12 +206,31 @@ impl WorkloadBuilder<dyn Payload> for SharedCounterWorkloadBuilder {
 pub struct SharedCounterWorkload {
     pub basics_package_id: Option<ObjectID>,
     pub counters: Vec<(ObjectID, SequenceNumber, ObjectDigest)>,
+    /// Probability of a payload targeting the first counter rather than a random one, 0.0 spreads
+    /// the traffic over all counters and 1.0 sends all of it to a single hot counter
+    pub contention_factor: f32,
+    /// Seed of the choice of counter for each payload
+    pub rng_seed: u64,
     pub init_gas: Vec<Gas>,
     pub payload_gas: Vec<Gas>,
 }
 
 #[async_trait]
 impl Workload<dyn Payload> for SharedCounterWorkload {
+    fn name(&self) -> &'static str {
+        "shared_counter"
+    }
+    fn senders(&self) -> Vec<SuiAddress> {
+        self.payload_gas.iter().map(|gas| gas.1).collect()
+    }
+    fn describe(&self) -> String {
+        format!(
+            "{}(counters = {}, contention_factor = {})",
+            self.name(),
+            self.counters.len(),
+            self.contention_factor
+        )
+    }
     async fn init(
         &mut self,
         proxy: Arc<dyn ValidatorProxy + Sync + Send>,
@@ -230,16 +292,20 @@ impl Workload<dyn Payload> for SharedCounterWorkload {
             self.payload_gas.len(),
             self.counters.len()
         );
+        let mut rng = StdRng::seed_from_u64(self.rng_seed);
         for g in self.payload_gas.iter() {
-            // pick a random counter from the pool
-            let counter_ref = self
-                .counters
-                .choose(&mut rand::thread_rng())
-                .expect("Failed to get a random counter from the pool");
+            // pick the hot counter or a random counter from the pool
+            let counter_ref = if rng.gen_bool(self.contention_factor as f64) {
+                self.counters.first()
+            } else {
+                self.counters.choose(&mut rng)
+            }
+            .expect("Failed to get a random counter from the pool");
             shared_payloads.push(Box::new(SharedCounterTestPayload {
                 package_id: self.basics_package_id.unwrap(),
                 counter_id: counter_ref.0,
                 counter_initial_shared_version: counter_ref.1,
+                last_counter_version: None,
                 gas: g.clone(),
                 system_state_observer: system_state_observer.clone(),
             }));
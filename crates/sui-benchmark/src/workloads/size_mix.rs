@@ -0,0 +1,281 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::in_memory_wallet::move_call_pt_data;
+use crate::system_state_observer::SystemStateObserver;
+use crate::workloads::payload::Payload;
+use crate::workloads::workload::{
+    Workload, WorkloadBuilder, ESTIMATED_COMPUTATION_COST, MAX_GAS_FOR_TESTING,
+};
+use crate::workloads::{
+    workload_target_qps, Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams,
+};
+use crate::{BenchMoveCallArg, ExecutionEffects, ValidatorProxy};
+use anyhow::{anyhow, bail};
+use async_trait::async_trait;
+use itertools::Itertools;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::str::FromStr;
+use std::sync::Arc;
+use sui_types::{
+    base_types::SuiAddress,
+    crypto::{get_key_pair, AccountKeyPair},
+    messages::{
+        TransactionData, TransactionDataAPI, VerifiedTransaction, TEST_ONLY_GAS_UNIT_FOR_GENERIC,
+        TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+    },
+    utils::to_sender_signed_transaction,
+    MOVE_STDLIB_OBJECT_ID,
+};
+use tracing::{error, info};
+
+/// Largest argument a transaction of the size mix workload takes, which keeps it within the
+/// protocol's 16KiB limit on pure arguments, length prefix included
+pub const MAX_ARGUMENT_BYTES: usize = 16_000;
+
+/// Sizes in bytes of the Move call argument of the transactions of the size mix workload, each
+/// with a relative weight, where a size of 0 stands for a plain transfer without any argument.
+/// Written as comma separated `{bytes}:{weight}` pairs, e.g. "0:80,4096:20" for 80% transfers and
+/// 20% calls with a 4KB argument.
+#[derive(Debug, Clone)]
+pub struct SizeDistribution {
+    sizes: Vec<(usize, u32)>,
+    index: WeightedIndex<u32>,
+}
+
+impl SizeDistribution {
+    /// Draw the argument size of the next transaction
+    pub fn sample(&self, rng: &mut impl Rng) -> usize {
+        self.sizes[self.index.sample(rng)].0
+    }
+}
+
+impl FromStr for SizeDistribution {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let sizes = s
+            .split(',')
+            .map(|pair| -> anyhow::Result<(usize, u32)> {
+                let (size, weight) = pair
+                    .split_once(':')
+                    .ok_or_else(|| anyhow!("expected {{bytes}}:{{weight}}, got {pair:?}"))?;
+                let size: usize = size
+                    .trim()
+                    .parse()
+                    .map_err(|_| anyhow!("invalid size {size:?}"))?;
+                if size > MAX_ARGUMENT_BYTES {
+                    bail!("size {size} is over the maximum of {MAX_ARGUMENT_BYTES} bytes");
+                }
+                let weight = weight
+                    .trim()
+                    .parse()
+                    .map_err(|_| anyhow!("invalid weight {weight:?}"))?;
+                Ok((size, weight))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let index = WeightedIndex::new(sizes.iter().map(|(_, weight)| *weight))
+            .map_err(|e| anyhow!("invalid size weights: {e}"))?;
+        Ok(Self { sizes, index })
+    }
+}
+
+impl std::fmt::Display for SizeDistribution {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.sizes
+                .iter()
+                .map(|(size, weight)| format!("{size}:{weight}"))
+                .join(",")
+        )
+    }
+}
+
+/// Sends transactions of sizes drawn from a `SizeDistribution`: transfers of its gas coin to its
+/// own sender, and calls to `std::string::utf8` with an argument of the drawn size, which is
+/// checked but otherwise left unused. Uniformly sized workloads hide the costs of serializing,
+/// signing and sending transactions which only grow with their size.
+#[derive(Debug)]
+pub struct SizeMixTestPayload {
+    distribution: SizeDistribution,
+    /// Picks the size of each transaction
+    rng: StdRng,
+    gas: Gas,
+    system_state_observer: Arc<SystemStateObserver>,
+}
+
+impl std::fmt::Display for SizeMixTestPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "size_mix")
+    }
+}
+
+impl Payload for SizeMixTestPayload {
+    fn make_new_payload(&mut self, effects: &ExecutionEffects) {
+        if !effects.is_ok() {
+            effects.print_gas_summary();
+            error!("Size mix tx failed...");
+        }
+        self.gas.0 = effects.gas_object().0;
+    }
+    fn make_transaction(&mut self) -> VerifiedTransaction {
+        let gas_price = self.system_state_observer.gas_price();
+        let sender = self.gas.1;
+        let mut data = match self.distribution.sample(&mut self.rng) {
+            0 => TransactionData::new_transfer_sui(
+                sender,
+                sender,
+                None,
+                self.gas.0,
+                gas_price * TEST_ONLY_GAS_UNIT_FOR_TRANSFER,
+                gas_price,
+            ),
+            size => move_call_pt_data(
+                sender,
+                MOVE_STDLIB_OBJECT_ID,
+                "string",
+                "utf8",
+                vec![],
+                // ASCII, so that the bytes are valid UTF-8
+                vec![BenchMoveCallArg::Pure(
+                    bcs::to_bytes(&vec![b'a'; size]).unwrap(),
+                )],
+                &self.gas.0,
+                gas_price * TEST_ONLY_GAS_UNIT_FOR_GENERIC,
+                gas_price,
+            ),
+        };
+        *data.expiration_mut_for_testing() = self.system_state_observer.expiration();
+        to_sender_signed_transaction(data, &self.gas.2)
+    }
+    fn verify_effects(&self, effects: &ExecutionEffects) -> Result<(), String> {
+        if !effects.is_ok() {
+            return Err(format!("size mix tx failed with {}", effects.status()));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct SizeMixWorkloadBuilder {
+    num_payloads: u64,
+    distribution: SizeDistribution,
+    rng_seed: u64,
+}
+
+impl SizeMixWorkloadBuilder {
+    pub fn from(
+        workload_weight: f32,
+        target_qps: u64,
+        num_workers: u64,
+        in_flight_ratio: u64,
+        distribution: SizeDistribution,
+        rng_seed: u64,
+    ) -> Option<WorkloadBuilderInfo> {
+        let target_qps = workload_target_qps(workload_weight, target_qps);
+        let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
+        let max_ops = target_qps * in_flight_ratio;
+        if max_ops == 0 || num_workers == 0 {
+            None
+        } else {
+            let workload_params = WorkloadParams {
+                target_qps,
+                num_workers,
+                max_ops,
+            };
+            let workload_builder =
+                Box::<dyn WorkloadBuilder<dyn Payload>>::from(Box::new(SizeMixWorkloadBuilder {
+                    num_payloads: max_ops,
+                    distribution,
+                    rng_seed,
+                }));
+            let builder_info = WorkloadBuilderInfo {
+                workload_params,
+                workload_builder,
+            };
+            Some(builder_info)
+        }
+    }
+}
+
+#[async_trait]
+impl WorkloadBuilder<dyn Payload> for SizeMixWorkloadBuilder {
+    async fn generate_coin_config_for_init(&self) -> Vec<GasCoinConfig> {
+        vec![]
+    }
+    async fn generate_coin_config_for_payloads(&self) -> Vec<GasCoinConfig> {
+        let amount = MAX_GAS_FOR_TESTING + ESTIMATED_COMPUTATION_COST;
+        (0..self.num_payloads)
+            .map(|_| {
+                let (address, keypair) = get_key_pair::<AccountKeyPair>();
+                GasCoinConfig {
+                    amount,
+                    address,
+                    keypair: Arc::new(keypair),
+                }
+            })
+            .collect()
+    }
+    async fn build(
+        &self,
+        _init_gas: Vec<Gas>,
+        payload_gas: Vec<Gas>,
+    ) -> Box<dyn Workload<dyn Payload>> {
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(SizeMixWorkload {
+            distribution: self.distribution.clone(),
+            rng_seed: self.rng_seed,
+            payload_gas,
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub struct SizeMixWorkload {
+    pub distribution: SizeDistribution,
+    /// Seed of the sizes drawn by each payload
+    pub rng_seed: u64,
+    pub payload_gas: Vec<Gas>,
+}
+
+#[async_trait]
+impl Workload<dyn Payload> for SizeMixWorkload {
+    fn name(&self) -> &'static str {
+        "size_mix"
+    }
+    fn describe(&self) -> String {
+        format!("{}(distribution = {})", self.name(), self.distribution)
+    }
+    fn senders(&self) -> Vec<SuiAddress> {
+        self.payload_gas.iter().map(|gas| gas.1).collect()
+    }
+    async fn init(
+        &mut self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        _system_state_observer: Arc<SystemStateObserver>,
+    ) {
+    }
+    async fn make_test_payloads(
+        &self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) -> Vec<Box<dyn Payload>> {
+        info!("Creating size mix txn payloads, hang tight..");
+        let mut seeds = StdRng::seed_from_u64(self.rng_seed);
+        self.payload_gas
+            .iter()
+            .map(|gas| {
+                Box::new(SizeMixTestPayload {
+                    distribution: self.distribution.clone(),
+                    rng: StdRng::seed_from_u64(seeds.gen()),
+                    gas: gas.clone(),
+                    system_state_observer: system_state_observer.clone(),
+                })
+            })
+            .map(|b| Box::<dyn Payload>::from(b))
+            .collect()
+    }
+}
@@ -2,10 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use async_trait::async_trait;
+use rand::rngs::StdRng;
 use rand::seq::IteratorRandom;
+use rand::SeedableRng;
 use tracing::error;
 
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use crate::system_state_observer::SystemStateObserver;
@@ -14,13 +16,16 @@ use crate::workloads::workload::WorkloadBuilder;
 use crate::workloads::workload::{
     Workload, ESTIMATED_COMPUTATION_COST, MAX_GAS_FOR_TESTING, STORAGE_COST_PER_COIN,
 };
-use crate::workloads::{Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams};
+use crate::workloads::{
+    workload_target_qps, Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams,
+};
 use crate::{ExecutionEffects, ValidatorProxy};
 use sui_core::test_utils::make_transfer_object_transaction;
 use sui_types::{
     base_types::{ObjectRef, SuiAddress},
     crypto::{get_key_pair, AccountKeyPair},
     messages::VerifiedTransaction,
+    object::Owner,
 };
 
 /// TODO: This should be the amount that is being transfered instead of MAX_GAS.
@@ -73,12 +78,26 @@ impl Payload for TransferObjectTestPayload {
             self.transfer_from,
             keypair,
             self.transfer_to,
-            self.system_state_observer
-                .state
-                .borrow()
-                .reference_gas_price,
+            self.system_state_observer.gas_price(),
         )
     }
+    fn verify_effects(&self, effects: &ExecutionEffects) -> Result<(), String> {
+        if !effects.is_ok() {
+            return Err(format!("transfer failed with {}", effects.status()));
+        }
+        match effects
+            .mutated()
+            .iter()
+            .find(|(object_ref, _)| object_ref.0 == self.transfer_object.0)
+        {
+            Some((_, Owner::AddressOwner(owner))) if *owner == self.transfer_to => Ok(()),
+            Some((_, owner)) => Err(format!(
+                "object {} is owned by {owner} instead of {}",
+                self.transfer_object.0, self.transfer_to
+            )),
+            None => Err(format!("object {} was not mutated", self.transfer_object.0)),
+        }
+    }
 }
 
 impl std::fmt::Display for TransferObjectTestPayload {
@@ -91,6 +110,8 @@ impl std::fmt::Display for TransferObjectTestPayload {
 pub struct TransferObjectWorkloadBuilder {
     num_transfer_accounts: u64,
     num_payloads: u64,
+    /// Seed of the choice of the account which initially owns the transferred objects
+    rng_seed: u64,
 }
 
 impl TransferObjectWorkloadBuilder {
@@ -100,8 +121,9 @@ impl TransferObjectWorkloadBuilder {
         num_workers: u64,
         in_flight_ratio: u64,
         num_transfer_accounts: u64,
+        rng_seed: u64,
     ) -> Option<WorkloadBuilderInfo> {
-        let target_qps = (workload_weight * target_qps as f32) as u64;
+        let target_qps = workload_target_qps(workload_weight, target_qps);
         let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
         let max_ops = target_qps * in_flight_ratio;
         if max_ops == 0 || num_workers == 0 {
@@ -116,6 +138,7 @@ impl TransferObjectWorkloadBuilder {
                 TransferObjectWorkloadBuilder {
                     num_transfer_accounts,
                     num_payloads: max_ops,
+                    rng_seed,
                 },
             ));
             let builder_info = WorkloadBuilderInfo {
@@ -133,7 +156,7 @@ impl WorkloadBuilder<dyn Payload> for TransferObjectWorkloadBuilder {
         vec![]
     }
     async fn generate_coin_config_for_payloads(&self) -> Vec<GasCoinConfig> {
-        let mut address_map = HashMap::new();
+        let mut address_map = BTreeMap::new();
         // Have to include not just the coins that are going to be created and sent
         // but the coin being used as gas as well.
         let amount = MAX_GAS_FOR_TESTING
@@ -154,7 +177,10 @@ impl WorkloadBuilder<dyn Payload> for TransferObjectWorkloadBuilder {
             }
         }
 
-        let owner = *address_map.keys().choose(&mut rand::thread_rng()).unwrap();
+        let owner = *address_map
+            .keys()
+            .choose(&mut StdRng::seed_from_u64(self.rng_seed))
+            .unwrap();
 
         // transfer tokens
         let mut gas_configs = vec![];
@@ -190,6 +216,27 @@ pub struct TransferObjectWorkload {
 
 #[async_trait]
 impl Workload<dyn Payload> for TransferObjectWorkload {
+    fn name(&self) -> &'static str {
+        "transfer_object"
+    }
+    fn senders(&self) -> Vec<SuiAddress> {
+        // The first `num_tokens` coins are the objects being transferred
+        let mut senders: Vec<SuiAddress> = self.payload_gas[self.num_tokens as usize..]
+            .iter()
+            .map(|gas| gas.1)
+            .collect();
+        senders.sort();
+        senders.dedup();
+        senders
+    }
+    fn describe(&self) -> String {
+        format!(
+            "{}(tokens = {}, payloads = {})",
+            self.name(),
+            self.num_tokens,
+            self.payload_gas.len()
+        )
+    }
     async fn init(
         &mut self,
         _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
@@ -203,7 +250,7 @@ impl Workload<dyn Payload> for TransferObjectWorkload {
         system_state_observer: Arc<SystemStateObserver>,
     ) -> Vec<Box<dyn Payload>> {
         let (transfer_tokens, payload_gas) = self.payload_gas.split_at(self.num_tokens as usize);
-        let mut gas_by_address: HashMap<SuiAddress, Vec<Gas>> = HashMap::new();
+        let mut gas_by_address: BTreeMap<SuiAddress, Vec<Gas>> = BTreeMap::new();
         for gas in payload_gas.iter() {
             gas_by_address
                 .entry(gas.1)
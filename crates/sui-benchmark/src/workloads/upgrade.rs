@@ -0,0 +1,233 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::system_state_observer::SystemStateObserver;
+use crate::workloads::payload::Payload;
+use crate::workloads::publish::CompiledModules;
+use crate::workloads::workload::{
+    Workload, WorkloadBuilder, ESTIMATED_COMPUTATION_COST, MAX_GAS_FOR_TESTING,
+};
+use crate::workloads::{
+    workload_target_qps, Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams,
+};
+use crate::{ExecutionEffects, ValidatorProxy};
+use async_trait::async_trait;
+use std::sync::Arc;
+use sui_types::{
+    base_types::{ObjectID, ObjectRef, SuiAddress},
+    crypto::{get_key_pair, AccountKeyPair},
+    messages::{
+        TransactionData, TransactionDataAPI, VerifiedTransaction, TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+    },
+    move_package::{MovePackage, UpgradePolicy},
+    object::Owner,
+    utils::to_sender_signed_transaction,
+};
+use tracing::{error, info};
+
+/// Publishes the bundled package with its first transaction, then upgrades it to the same modules
+/// with every following one, exercising the upgrade compatibility checks and the loading of a new
+/// version of the package each time. Each payload upgrades a package of its own, whose
+/// `UpgradeCap` it owns, so its upgrades are applied in sequence.
+#[derive(Debug)]
+pub struct UpgradeTestPayload {
+    package: Arc<CompiledModules>,
+    /// Digest of `package`, which the `UpgradeCap` authorizes each upgrade to
+    digest: Vec<u8>,
+    /// The latest version of the package and the capability to upgrade it, once published
+    upgradeable: Option<(ObjectID, ObjectRef)>,
+    gas: Gas,
+    system_state_observer: Arc<SystemStateObserver>,
+}
+
+impl std::fmt::Display for UpgradeTestPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "upgrade")
+    }
+}
+
+impl Payload for UpgradeTestPayload {
+    fn make_new_payload(&mut self, effects: &ExecutionEffects) {
+        if !effects.is_ok() {
+            effects.print_gas_summary();
+            error!("Upgrade tx failed...");
+        }
+        self.gas.0 = effects.gas_object().0;
+        // Publishing creates the package and the cap, upgrading creates the new version of the
+        // package. The cap is mutated by every upgrade, even one that failed
+        let sender = Owner::AddressOwner(self.gas.1);
+        let mut package_id = self.upgradeable.map(|(package_id, _)| package_id);
+        let mut cap = self.upgradeable.map(|(_, cap)| cap);
+        for (object_ref, owner) in effects.created().into_iter().chain(effects.mutated()) {
+            if owner == Owner::Immutable {
+                package_id = Some(object_ref.0);
+            } else if owner == sender && object_ref.0 != self.gas.0 .0 {
+                cap = Some(object_ref);
+            }
+        }
+        self.upgradeable = package_id.zip(cap);
+    }
+    fn make_transaction(&mut self) -> VerifiedTransaction {
+        let gas_price = self.system_state_observer.gas_price();
+        let mut data = match self.upgradeable {
+            None => TransactionData::new_module(
+                self.gas.1,
+                self.gas.0,
+                self.package.modules.clone(),
+                self.package.dependencies.clone(),
+                gas_price * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+                gas_price,
+            ),
+            Some((package_id, cap)) => TransactionData::new_upgrade(
+                self.gas.1,
+                self.gas.0,
+                package_id,
+                self.package.modules.clone(),
+                self.package.dependencies.clone(),
+                (cap, Owner::AddressOwner(self.gas.1)),
+                UpgradePolicy::COMPATIBLE,
+                self.digest.clone(),
+                gas_price * TEST_ONLY_GAS_UNIT_FOR_PUBLISH * 2,
+                gas_price,
+            )
+            .unwrap(),
+        };
+        *data.expiration_mut_for_testing() = self.system_state_observer.expiration();
+        to_sender_signed_transaction(data, &self.gas.2)
+    }
+    fn verify_effects(&self, effects: &ExecutionEffects) -> Result<(), String> {
+        if !effects.is_ok() {
+            return Err(format!("upgrade failed with {}", effects.status()));
+        }
+        if !effects
+            .created()
+            .iter()
+            .any(|(_, owner)| *owner == Owner::Immutable)
+        {
+            return Err("no new version of the package was created".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct UpgradeWorkloadBuilder {
+    num_payloads: u64,
+}
+
+impl UpgradeWorkloadBuilder {
+    pub fn from(
+        workload_weight: f32,
+        target_qps: u64,
+        num_workers: u64,
+        in_flight_ratio: u64,
+    ) -> Option<WorkloadBuilderInfo> {
+        let target_qps = workload_target_qps(workload_weight, target_qps);
+        let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
+        let max_ops = target_qps * in_flight_ratio;
+        if max_ops == 0 || num_workers == 0 {
+            None
+        } else {
+            let workload_params = WorkloadParams {
+                target_qps,
+                num_workers,
+                max_ops,
+            };
+            let workload_builder =
+                Box::<dyn WorkloadBuilder<dyn Payload>>::from(Box::new(UpgradeWorkloadBuilder {
+                    num_payloads: max_ops,
+                }));
+            let builder_info = WorkloadBuilderInfo {
+                workload_params,
+                workload_builder,
+            };
+            Some(builder_info)
+        }
+    }
+}
+
+#[async_trait]
+impl WorkloadBuilder<dyn Payload> for UpgradeWorkloadBuilder {
+    async fn generate_coin_config_for_init(&self) -> Vec<GasCoinConfig> {
+        vec![]
+    }
+    async fn generate_coin_config_for_payloads(&self) -> Vec<GasCoinConfig> {
+        // Each payload sends from an account of its own, which owns the cap of its package
+        let amount = MAX_GAS_FOR_TESTING + ESTIMATED_COMPUTATION_COST;
+        (0..self.num_payloads)
+            .map(|_| {
+                let (address, keypair) = get_key_pair::<AccountKeyPair>();
+                GasCoinConfig {
+                    amount,
+                    address,
+                    keypair: Arc::new(keypair),
+                }
+            })
+            .collect()
+    }
+    async fn build(
+        &self,
+        _init_gas: Vec<Gas>,
+        payload_gas: Vec<Gas>,
+    ) -> Box<dyn Workload<dyn Payload>> {
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(UpgradeWorkload {
+            package: None,
+            payload_gas,
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub struct UpgradeWorkload {
+    pub package: Option<Arc<CompiledModules>>,
+    pub payload_gas: Vec<Gas>,
+}
+
+#[async_trait]
+impl Workload<dyn Payload> for UpgradeWorkload {
+    fn name(&self) -> &'static str {
+        "upgrade"
+    }
+    fn senders(&self) -> Vec<SuiAddress> {
+        self.payload_gas.iter().map(|gas| gas.1).collect()
+    }
+    async fn init(
+        &mut self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        _system_state_observer: Arc<SystemStateObserver>,
+    ) {
+        if self.package.is_none() {
+            info!("Compiling bundled package");
+            self.package = Some(Arc::new(CompiledModules::bundled()));
+        }
+    }
+    async fn make_test_payloads(
+        &self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) -> Vec<Box<dyn Payload>> {
+        info!("Creating upgrade txn payloads, hang tight..");
+        let package = self
+            .package
+            .clone()
+            .expect("Upgrade workload must be initialized before creating payloads");
+        let digest = MovePackage::compute_digest_for_modules_and_deps(
+            &package.modules,
+            &package.dependencies,
+        )
+        .to_vec();
+        self.payload_gas
+            .iter()
+            .map(|gas| {
+                Box::new(UpgradeTestPayload {
+                    package: package.clone(),
+                    digest: digest.clone(),
+                    upgradeable: None,
+                    gas: gas.clone(),
+                    system_state_observer: system_state_observer.clone(),
+                })
+            })
+            .map(|b| Box::<dyn Payload>::from(b))
+            .collect()
+    }
+}
@@ -3,6 +3,7 @@
 
 use async_trait::async_trait;
 use std::sync::Arc;
+use sui_types::base_types::SuiAddress;
 
 use crate::system_state_observer::SystemStateObserver;
 use crate::workloads::{Gas, GasCoinConfig};
@@ -41,6 +42,16 @@ pub trait WorkloadBuilder<T: Payload + ?Sized>: Send + Sync + std::fmt::Debug {
 /// payloads are created.
 #[async_trait]
 pub trait Workload<T: Payload + ?Sized>: Send + Sync + std::fmt::Debug {
+    /// Short name of the workload, used to attribute stats to it
+    fn name(&self) -> &'static str;
+    /// Name of the workload along with its parameters, for logging
+    fn describe(&self) -> String {
+        self.name().to_string()
+    }
+    /// Addresses paying for the gas of the workload's payloads
+    fn senders(&self) -> Vec<SuiAddress> {
+        vec![]
+    }
     async fn init(
         &mut self,
         proxy: Arc<dyn ValidatorProxy + Sync + Send>,
@@ -5,16 +5,435 @@ use crate::bank::BenchmarkBank;
 use crate::options::{Opts, RunSpec};
 use crate::system_state_observer::SystemStateObserver;
 use crate::workloads::batch_payment::BatchPaymentWorkloadBuilder;
+use crate::workloads::clock_read::ClockReadWorkloadBuilder;
+use crate::workloads::coin_ops::CoinOpsWorkloadBuilder;
 use crate::workloads::delegation::DelegationWorkloadBuilder;
+use crate::workloads::dynamic_field::{DynamicFieldWorkloadBuilder, MAX_FIELD_COUNT};
+use crate::workloads::equivocation::EquivocationWorkloadBuilder;
+use crate::workloads::faucet::FaucetWorkloadBuilder;
+use crate::workloads::fullnode_query::FullnodeQueryWorkloadBuilder;
+use crate::workloads::gas_fanout::{GasFanoutWorkloadBuilder, MAX_FANOUT_WIDTH};
+use crate::workloads::invalid_transaction::InvalidTransactionWorkloadBuilder;
+use crate::workloads::multi_input::{MultiInputWorkloadBuilder, MAX_INPUTS_PER_TX};
+use crate::workloads::nft::NftWorkloadBuilder;
+use crate::workloads::publish::PublishWorkloadBuilder;
+use crate::workloads::sequenced::SequencedWorkloadBuilder;
 use crate::workloads::shared_counter::SharedCounterWorkloadBuilder;
+use crate::workloads::size_mix::{SizeDistribution, SizeMixWorkloadBuilder};
 use crate::workloads::transfer_object::TransferObjectWorkloadBuilder;
+use crate::workloads::upgrade::UpgradeWorkloadBuilder;
+use crate::workloads::wrap_unwrap::WrapUnwrapWorkloadBuilder;
 use crate::workloads::WorkloadInfo;
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
+use strum::IntoEnumIterator;
+use strum_macros::{EnumIter, EnumString, IntoStaticStr};
+use tracing::info;
 
 use super::adversarial::{AdversarialPayloadCfg, AdversarialWorkloadBuilder};
 
+/// A workload which can be given a weight in a `WorkloadMix`, under its snake case name, e.g.
+/// "shared_counter". See `CombinationWorkloadBuilder`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumIter, EnumString, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum WorkloadKind {
+    SharedCounter,
+    TransferObject,
+    Delegation,
+    BatchPayment,
+    Adversarial,
+    Nft,
+    Publish,
+    FullnodeQuery,
+    CoinOps,
+    InvalidTransaction,
+    WrapUnwrap,
+    GasFanout,
+    MultiInput,
+    ClockRead,
+    Upgrade,
+    DynamicField,
+    PublishMint,
+    Equivocation,
+    SizeMix,
+}
+
+impl WorkloadKind {
+    /// The name of the workload in a `WorkloadMix`
+    pub fn name(self) -> &'static str {
+        self.into()
+    }
+}
+
+/// The workloads to run along with their parameters, loaded from a TOML or JSON file so traffic
+/// mixes can be defined without recompiling. For example:
+///
+/// ```toml
+/// shared_counter_contention_factor = 0.5
+///
+/// [weights]
+/// shared_counter = 2
+/// transfer_object = 1
+/// ```
+///
+/// Workloads missing from `weights` are not run, parameters which are not set take the same
+/// defaults as the `bench` command line options.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WorkloadMix {
+    /// Relative weight of each workload, keyed by workload name
+    pub weights: BTreeMap<String, u32>,
+    pub shared_counter_hotness_factor: u32,
+    pub shared_counter_contention_factor: f32,
+    pub batch_payment_size: u32,
+    pub adversarial_cfg: String,
+    pub publish_package_dir: Option<PathBuf>,
+    pub coin_ops_split_count: u64,
+    pub invalid_transaction_fraction: f32,
+    pub gas_fanout_width: u64,
+    pub multi_input_inputs_per_tx: u64,
+    pub dynamic_field_count: u64,
+    pub equivocation_conflict_fraction: f32,
+    /// See `SizeDistribution`
+    pub size_mix_distribution: String,
+    /// Seed of the random choices made by the workloads, a random seed is used if unset
+    pub rng_seed: Option<u64>,
+}
+
+impl Default for WorkloadMix {
+    fn default() -> Self {
+        Self {
+            weights: BTreeMap::new(),
+            shared_counter_hotness_factor: 50,
+            shared_counter_contention_factor: 0.0,
+            batch_payment_size: 15,
+            adversarial_cfg: "0-1.0".to_string(),
+            publish_package_dir: None,
+            coin_ops_split_count: 4,
+            invalid_transaction_fraction: 0.5,
+            gas_fanout_width: 64,
+            multi_input_inputs_per_tx: 16,
+            dynamic_field_count: 16,
+            equivocation_conflict_fraction: 0.5,
+            size_mix_distribution: "0:80,4096:20".to_string(),
+            rng_seed: None,
+        }
+    }
+}
+
+impl WorkloadMix {
+    /// Load a mix from `path`, parsed as JSON if it has a `.json` extension and as TOML otherwise.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read workload mix from {path:?}: {e}"))?;
+        let mix: Self = if path.extension().map_or(false, |ext| ext == "json") {
+            serde_json::from_str(&contents)?
+        } else {
+            toml::from_str(&contents)?
+        };
+        mix.validate()?;
+        Ok(mix)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if let Some(name) = self
+            .weights
+            .keys()
+            .find(|name| WorkloadKind::from_str(name).is_err())
+        {
+            let names: Vec<_> = WorkloadKind::iter().map(WorkloadKind::name).collect();
+            bail!("Unknown workload {name:?}, expected one of {names:?}");
+        }
+        if self.weights.values().all(|weight| *weight == 0) {
+            bail!("At least one workload must have a nonzero weight");
+        }
+        if !(0.0..=1.0).contains(&self.shared_counter_contention_factor) {
+            bail!(
+                "Shared counter contention factor must be between 0.0 and 1.0, got {}",
+                self.shared_counter_contention_factor
+            );
+        }
+        if !(0.0..=1.0).contains(&self.invalid_transaction_fraction) {
+            bail!(
+                "Invalid transaction fraction must be between 0.0 and 1.0, got {}",
+                self.invalid_transaction_fraction
+            );
+        }
+        if !(1..=MAX_FANOUT_WIDTH).contains(&self.gas_fanout_width) {
+            bail!(
+                "Gas fanout width must be between 1 and {MAX_FANOUT_WIDTH}, got {}",
+                self.gas_fanout_width
+            );
+        }
+        if !(2..=MAX_INPUTS_PER_TX).contains(&self.multi_input_inputs_per_tx) {
+            bail!(
+                "Multi input inputs per transaction must be between 2 and {MAX_INPUTS_PER_TX}, got {}",
+                self.multi_input_inputs_per_tx
+            );
+        }
+        if !(0.0..=1.0).contains(&self.equivocation_conflict_fraction) {
+            bail!(
+                "Equivocation conflict fraction must be between 0.0 and 1.0, got {}",
+                self.equivocation_conflict_fraction
+            );
+        }
+        if !(1..=MAX_FIELD_COUNT).contains(&self.dynamic_field_count) {
+            bail!(
+                "Dynamic field count must be between 1 and {MAX_FIELD_COUNT}, got {}",
+                self.dynamic_field_count
+            );
+        }
+        AdversarialPayloadCfg::from_str(&self.adversarial_cfg)
+            .map_err(|e| anyhow!("Invalid adversarial config: {e}"))?;
+        SizeDistribution::from_str(&self.size_mix_distribution)
+            .map_err(|e| anyhow!("Invalid size mix distribution: {e}"))?;
+        Ok(())
+    }
+
+    fn weight(&self, name: &str) -> u32 {
+        self.weights.get(name).copied().unwrap_or(0)
+    }
+
+    /// Fraction of the total weight given to workload `name`
+    fn share(&self, name: &str) -> f64 {
+        let total_weight: u32 = self.weights.values().sum();
+        self.weight(name) as f64 / total_weight as f64
+    }
+}
+
+/// Weight the shares of the peak mix of a `WorkloadSchedule` are rounded to
+const PEAK_MIX_WEIGHT_SCALE: f64 = 10_000.0;
+
+/// Traffic mixes run one after the other, each for the duration of its phase, repeating from the
+/// first phase once the last one has ended, e.g. to cover transfer-heavy, shared-object-heavy
+/// and publish-heavy traffic in a single soak test.
+///
+/// The workloads are built once, with `WorkloadConfiguration::build_workloads_from_schedule`,
+/// so phases only differ in their weights. Each workload is built for the highest share of the
+/// load any phase gives it, and `BenchDriver::with_schedule` slows its workers down to its share
+/// of the current phase, or pauses them in phases where it has no weight.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkloadSchedule {
+    phases: Vec<(Duration, WorkloadMix)>,
+}
+
+/// A phase of a workload schedule file, for example:
+///
+/// ```toml
+/// [[phases]]
+/// duration_secs = 600
+/// mix.weights = { transfer_object = 1 }
+///
+/// [[phases]]
+/// duration_secs = 300
+/// mix.weights = { shared_counter = 3, publish = 1 }
+/// ```
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SchedulePhase {
+    duration_secs: u64,
+    mix: WorkloadMix,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScheduleFile {
+    phases: Vec<SchedulePhase>,
+}
+
+impl WorkloadSchedule {
+    /// Load a schedule from `path`, parsed as JSON if it has a `.json` extension and as TOML
+    /// otherwise, see `SchedulePhase` for the format.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read workload schedule from {path:?}: {e}"))?;
+        let file: ScheduleFile = if path.extension().map_or(false, |ext| ext == "json") {
+            serde_json::from_str(&contents)?
+        } else {
+            toml::from_str(&contents)?
+        };
+        Self::new(
+            file.phases
+                .into_iter()
+                .map(|phase| (Duration::from_secs(phase.duration_secs), phase.mix))
+                .collect(),
+        )
+    }
+
+    pub fn new(phases: Vec<(Duration, WorkloadMix)>) -> Result<Self> {
+        let Some((_, first)) = phases.first() else {
+            bail!("Workload schedule needs at least one phase");
+        };
+        for (index, (duration, mix)) in phases.iter().enumerate() {
+            if duration.is_zero() {
+                bail!("Phase {index} of the workload schedule has no duration");
+            }
+            mix.validate()
+                .map_err(|e| anyhow!("Invalid mix in phase {index} of the schedule: {e}"))?;
+            let parameters = |mix: &WorkloadMix| WorkloadMix {
+                weights: BTreeMap::new(),
+                ..mix.clone()
+            };
+            if parameters(mix) != parameters(first) {
+                bail!(
+                    "Phase {index} of the workload schedule has other parameters than phase 0, \
+                    phases can only differ in their weights"
+                );
+            }
+        }
+        Ok(Self { phases })
+    }
+
+    pub fn phases(&self) -> &[(Duration, WorkloadMix)] {
+        &self.phases
+    }
+
+    /// Set the seed of the random choices made by the workloads in every phase
+    pub fn with_rng_seed(mut self, seed: u64) -> Self {
+        for (_, mix) in &mut self.phases {
+            mix.rng_seed = Some(seed);
+        }
+        self
+    }
+
+    /// Duration of one pass over every phase
+    pub fn cycle_duration(&self) -> Duration {
+        self.phases.iter().map(|(duration, _)| *duration).sum()
+    }
+
+    /// The pass over the phases and the index of the phase running `elapsed` after the start of
+    /// the benchmark
+    pub fn phase_at(&self, elapsed: Duration) -> (u64, usize) {
+        let cycle_duration = self.cycle_duration().as_nanos();
+        let cycle = (elapsed.as_nanos() / cycle_duration) as u64;
+        let mut into_cycle = Duration::from_nanos((elapsed.as_nanos() % cycle_duration) as u64);
+        for (index, (duration, _)) in self.phases.iter().enumerate() {
+            if into_cycle < *duration {
+                return (cycle, index);
+            }
+            into_cycle -= *duration;
+        }
+        unreachable!("{elapsed:?} into the benchmark is past the end of its cycle")
+    }
+
+    /// Highest share of the load any phase gives to workload `name`
+    fn peak_share(&self, name: &str) -> f64 {
+        self.phases
+            .iter()
+            .map(|(_, mix)| mix.share(name))
+            .fold(0.0, f64::max)
+    }
+
+    /// A mix weighting each workload of the schedule by its peak share, with the parameters of
+    /// the phases. The peak shares add up to more than the whole load unless every phase has the
+    /// same weights, see `peak_load`.
+    pub fn peak_mix(&self) -> WorkloadMix {
+        let mut mix = self.phases[0].1.clone();
+        mix.weights = self
+            .phases
+            .iter()
+            .flat_map(|(_, mix)| mix.weights.keys())
+            .map(|name| {
+                let weight = (self.peak_share(name) * PEAK_MIX_WEIGHT_SCALE).round() as u32;
+                (name.clone(), weight)
+            })
+            .filter(|(_, weight)| *weight > 0)
+            .collect();
+        mix
+    }
+
+    /// Sum of the peak shares of the workloads, i.e. the load the peak mix must be built for,
+    /// relative to the load of any single phase
+    pub fn peak_load(&self) -> f64 {
+        self.peak_mix().weights.values().sum::<u32>() as f64 / PEAK_MIX_WEIGHT_SCALE
+    }
+
+    /// Fraction of its peak rate workload `name` should run at `elapsed` after the start of the
+    /// benchmark. Workloads outside of the schedule, e.g. the faucet, always run at full rate
+    pub fn rate_scale_at(&self, name: &str, elapsed: Duration) -> f64 {
+        let peak_share = self.peak_share(name);
+        if peak_share == 0.0 {
+            return 1.0;
+        }
+        let (_, phase) = self.phase_at(elapsed);
+        self.phases[phase].1.share(name) / peak_share
+    }
+}
+
+/// Builds a validated `WorkloadMix` one workload at a time, for
+/// `WorkloadConfiguration::build_workloads_from_mix`. For example:
+///
+/// ```ignore
+/// let mix = CombinationWorkloadBuilder::new()
+///     .add(WorkloadKind::SharedCounter, 2)
+///     .add(WorkloadKind::TransferObject, 1)
+///     .build()?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CombinationWorkloadBuilder {
+    mix: WorkloadMix,
+}
+
+impl CombinationWorkloadBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from the parameters and weights of `mix`
+    pub fn from_mix(mix: WorkloadMix) -> Self {
+        Self { mix }
+    }
+
+    /// Give `workload` a weight relative to the other workloads, adding up the weights if the
+    /// same workload is added more than once
+    pub fn add(mut self, workload: WorkloadKind, weight: u32) -> Self {
+        *self
+            .mix
+            .weights
+            .entry(workload.name().to_string())
+            .or_default() += weight;
+        self
+    }
+
+    /// Seed the random choices of the workloads with `seed`, so that running the mix again with
+    /// the same seed makes the same choices
+    pub fn with_rng_seed(mut self, seed: u64) -> Self {
+        self.mix.rng_seed = Some(seed);
+        self
+    }
+
+    /// The combined mix, with workloads of weight zero removed and the others divided by their
+    /// greatest common divisor. Fails if no workload has a nonzero weight.
+    pub fn build(mut self) -> Result<WorkloadMix> {
+        self.mix.weights.retain(|_, weight| *weight > 0);
+        self.mix.validate()?;
+        let divisor = self
+            .mix
+            .weights
+            .values()
+            .fold(0, |divisor, weight| gcd(divisor, *weight));
+        for weight in self.mix.weights.values_mut() {
+            *weight /= divisor;
+        }
+        Ok(self.mix)
+    }
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
 pub struct WorkloadConfiguration;
 
 impl WorkloadConfiguration {
@@ -33,22 +452,109 @@ impl WorkloadConfiguration {
                 delegation,
                 batch_payment,
                 adversarial,
+                nft,
+                publish,
+                fullnode_query,
+                coin_ops,
+                invalid_transaction,
+                wrap_unwrap,
+                gas_fanout,
+                multi_input,
+                clock_read,
+                upgrade,
+                dynamic_field,
+                publish_mint,
+                equivocation,
+                size_mix,
                 adversarial_cfg,
                 batch_payment_size,
                 shared_counter_hotness_factor,
+                shared_counter_contention,
+                publish_package_dir,
+                coin_ops_split_count,
+                invalid_transaction_fraction,
+                gas_fanout_width,
+                multi_input_inputs_per_tx,
+                dynamic_field_count,
+                equivocation_conflict_fraction,
+                size_mix_distribution,
+                workload_mix,
+                workload_schedule,
+                workload_rng_seed,
                 ..
             } => {
-                Self::build_workloads(
+                if let Some(path) = workload_schedule {
+                    let mut schedule = WorkloadSchedule::load(&path)?;
+                    if let Some(seed) = workload_rng_seed {
+                        schedule = schedule.with_rng_seed(seed);
+                    }
+                    return Self::build_workloads_from_schedule(
+                        &schedule,
+                        num_workers,
+                        opts.num_transfer_accounts,
+                        opts.fullnode_rpc_addresses.first().cloned(),
+                        opts.run_duration.is_unbounded(),
+                        target_qps,
+                        in_flight_ratio,
+                        bank,
+                        system_state_observer,
+                        opts.gas_request_chunk_size,
+                    )
+                    .await;
+                }
+                // The command line takes fractions as percentages
+                let fraction = |percent: u32| percent.min(100) as f32 / 100.0;
+                let mix = match workload_mix {
+                    Some(path) => {
+                        let mut mix = WorkloadMix::load(&path)?;
+                        if workload_rng_seed.is_some() {
+                            mix.rng_seed = workload_rng_seed;
+                        }
+                        mix
+                    }
+                    None => CombinationWorkloadBuilder::from_mix(WorkloadMix {
+                        weights: BTreeMap::new(),
+                        shared_counter_hotness_factor,
+                        shared_counter_contention_factor: fraction(shared_counter_contention),
+                        batch_payment_size,
+                        adversarial_cfg,
+                        publish_package_dir,
+                        coin_ops_split_count,
+                        invalid_transaction_fraction: fraction(invalid_transaction_fraction),
+                        gas_fanout_width,
+                        multi_input_inputs_per_tx,
+                        dynamic_field_count,
+                        equivocation_conflict_fraction: fraction(equivocation_conflict_fraction),
+                        size_mix_distribution,
+                        rng_seed: workload_rng_seed,
+                    })
+                    .add(WorkloadKind::SharedCounter, shared_counter)
+                    .add(WorkloadKind::TransferObject, transfer_object)
+                    .add(WorkloadKind::Delegation, delegation)
+                    .add(WorkloadKind::BatchPayment, batch_payment)
+                    .add(WorkloadKind::Adversarial, adversarial)
+                    .add(WorkloadKind::Nft, nft)
+                    .add(WorkloadKind::Publish, publish)
+                    .add(WorkloadKind::FullnodeQuery, fullnode_query)
+                    .add(WorkloadKind::CoinOps, coin_ops)
+                    .add(WorkloadKind::InvalidTransaction, invalid_transaction)
+                    .add(WorkloadKind::WrapUnwrap, wrap_unwrap)
+                    .add(WorkloadKind::GasFanout, gas_fanout)
+                    .add(WorkloadKind::MultiInput, multi_input)
+                    .add(WorkloadKind::ClockRead, clock_read)
+                    .add(WorkloadKind::Upgrade, upgrade)
+                    .add(WorkloadKind::DynamicField, dynamic_field)
+                    .add(WorkloadKind::PublishMint, publish_mint)
+                    .add(WorkloadKind::Equivocation, equivocation)
+                    .add(WorkloadKind::SizeMix, size_mix)
+                    .build()?,
+                };
+                Self::build_workloads_from_mix(
+                    &mix,
                     num_workers,
                     opts.num_transfer_accounts,
-                    shared_counter,
-                    transfer_object,
-                    delegation,
-                    batch_payment,
-                    adversarial,
-                    AdversarialPayloadCfg::from_str(&adversarial_cfg).unwrap(),
-                    batch_payment_size,
-                    shared_counter_hotness_factor,
+                    opts.fullnode_rpc_addresses.first().cloned(),
+                    opts.run_duration.is_unbounded(),
                     target_qps,
                     in_flight_ratio,
                     bank,
@@ -60,69 +566,199 @@ impl WorkloadConfiguration {
         }
     }
 
-    pub async fn build_workloads(
+    /// Build the workloads of every phase of `schedule` for `BenchDriver::with_schedule`, see
+    /// `build_workloads_from_mix` for the other arguments. `target_qps` is the load of each phase,
+    /// the workloads are built for the peak load of the schedule so that none of them runs out of
+    /// payloads in the phase it is heaviest in.
+    pub async fn build_workloads_from_schedule(
+        schedule: &WorkloadSchedule,
         num_workers: u64,
         num_transfer_accounts: u64,
-        shared_counter_weight: u32,
-        transfer_object_weight: u32,
-        delegation_weight: u32,
-        batch_payment_weight: u32,
-        adversarial_weight: u32,
-        adversarial_cfg: AdversarialPayloadCfg,
-        batch_payment_size: u32,
-        shared_counter_hotness_factor: u32,
+        fullnode_rpc_url: Option<String>,
+        faucet: bool,
         target_qps: u64,
         in_flight_ratio: u64,
-        mut bank: BenchmarkBank,
+        bank: BenchmarkBank,
         system_state_observer: Arc<SystemStateObserver>,
         chunk_size: u64,
     ) -> Result<Vec<WorkloadInfo>> {
-        let total_weight = shared_counter_weight
-            + transfer_object_weight
-            + delegation_weight
-            + batch_payment_weight
-            + adversarial_weight;
-        let mut workload_builders = vec![];
-        let shared_workload = SharedCounterWorkloadBuilder::from(
-            shared_counter_weight as f32 / total_weight as f32,
-            target_qps,
-            num_workers,
-            in_flight_ratio,
-            shared_counter_hotness_factor,
-        );
-        workload_builders.push(shared_workload);
-        let transfer_workload = TransferObjectWorkloadBuilder::from(
-            transfer_object_weight as f32 / total_weight as f32,
-            target_qps,
-            num_workers,
-            in_flight_ratio,
+        let peak_load = schedule.peak_load();
+        Self::build_workloads_from_mix(
+            &schedule.peak_mix(),
+            (num_workers as f64 * peak_load).ceil() as u64,
             num_transfer_accounts,
-        );
-        workload_builders.push(transfer_workload);
-        let delegation_workload = DelegationWorkloadBuilder::from(
-            delegation_weight as f32 / total_weight as f32,
-            target_qps,
-            num_workers,
+            fullnode_rpc_url,
+            faucet,
+            (target_qps as f64 * peak_load).round() as u64,
             in_flight_ratio,
-        );
-        workload_builders.push(delegation_workload);
-        let batch_payment_workload = BatchPaymentWorkloadBuilder::from(
-            batch_payment_weight as f32 / total_weight as f32,
-            target_qps,
-            num_workers,
-            in_flight_ratio,
-            batch_payment_size,
-        );
-        workload_builders.push(batch_payment_workload);
-        let adversarial_workload = AdversarialWorkloadBuilder::from(
-            adversarial_weight as f32 / total_weight as f32,
-            target_qps,
-            num_workers,
-            in_flight_ratio,
-            adversarial_cfg,
-        );
-        workload_builders.push(adversarial_workload);
-        let (workload_params, workload_builders): (Vec<_>, Vec<_>) = workload_builders
+            bank,
+            system_state_observer,
+            chunk_size,
+        )
+        .await
+    }
+
+    /// Build the workloads described by `mix`, each getting its share of `target_qps` and
+    /// `num_workers`. If `faucet` is set, a faucet workload keeps the senders of the others funded.
+    pub async fn build_workloads_from_mix(
+        mix: &WorkloadMix,
+        num_workers: u64,
+        num_transfer_accounts: u64,
+        fullnode_rpc_url: Option<String>,
+        faucet: bool,
+        target_qps: u64,
+        in_flight_ratio: u64,
+        mut bank: BenchmarkBank,
+        system_state_observer: Arc<SystemStateObserver>,
+        chunk_size: u64,
+    ) -> Result<Vec<WorkloadInfo>> {
+        mix.validate()?;
+        let total_weight: u32 = mix.weights.values().sum();
+        // Every workload gets a seed of its own, drawn in a fixed order from the seed of the run
+        let rng_seed = mix.rng_seed.unwrap_or_else(rand::random);
+        info!("Workload RNG seed: {rng_seed}");
+        let mut seeds = StdRng::seed_from_u64(rng_seed);
+        let mut workload_builders = vec![];
+        for kind in WorkloadKind::iter() {
+            let weight = mix.weight(kind.name()) as f32 / total_weight as f32;
+            let workload_builder = match kind {
+                WorkloadKind::SharedCounter => SharedCounterWorkloadBuilder::from(
+                    weight,
+                    target_qps,
+                    num_workers,
+                    in_flight_ratio,
+                    mix.shared_counter_hotness_factor,
+                    mix.shared_counter_contention_factor,
+                    seeds.gen(),
+                ),
+                WorkloadKind::TransferObject => TransferObjectWorkloadBuilder::from(
+                    weight,
+                    target_qps,
+                    num_workers,
+                    in_flight_ratio,
+                    num_transfer_accounts,
+                    seeds.gen(),
+                ),
+                WorkloadKind::Delegation => DelegationWorkloadBuilder::from(
+                    weight,
+                    target_qps,
+                    num_workers,
+                    in_flight_ratio,
+                    seeds.gen(),
+                ),
+                WorkloadKind::BatchPayment => BatchPaymentWorkloadBuilder::from(
+                    weight,
+                    target_qps,
+                    num_workers,
+                    in_flight_ratio,
+                    mix.batch_payment_size,
+                ),
+                WorkloadKind::Adversarial => AdversarialWorkloadBuilder::from(
+                    weight,
+                    target_qps,
+                    num_workers,
+                    in_flight_ratio,
+                    AdversarialPayloadCfg::from_str(&mix.adversarial_cfg).unwrap(),
+                ),
+                WorkloadKind::Nft => {
+                    NftWorkloadBuilder::from(weight, target_qps, num_workers, in_flight_ratio)
+                }
+                WorkloadKind::Publish => PublishWorkloadBuilder::from(
+                    weight,
+                    target_qps,
+                    num_workers,
+                    in_flight_ratio,
+                    mix.publish_package_dir.clone(),
+                ),
+                WorkloadKind::FullnodeQuery => FullnodeQueryWorkloadBuilder::from(
+                    weight,
+                    target_qps,
+                    num_workers,
+                    in_flight_ratio,
+                    fullnode_rpc_url.clone(),
+                ),
+                WorkloadKind::CoinOps => CoinOpsWorkloadBuilder::from(
+                    weight,
+                    target_qps,
+                    num_workers,
+                    in_flight_ratio,
+                    mix.coin_ops_split_count,
+                    seeds.gen(),
+                ),
+                WorkloadKind::InvalidTransaction => InvalidTransactionWorkloadBuilder::from(
+                    weight,
+                    target_qps,
+                    num_workers,
+                    in_flight_ratio,
+                    mix.invalid_transaction_fraction,
+                    seeds.gen(),
+                ),
+                WorkloadKind::WrapUnwrap => WrapUnwrapWorkloadBuilder::from(
+                    weight,
+                    target_qps,
+                    num_workers,
+                    in_flight_ratio,
+                ),
+                WorkloadKind::GasFanout => GasFanoutWorkloadBuilder::from(
+                    weight,
+                    target_qps,
+                    num_workers,
+                    in_flight_ratio,
+                    mix.gas_fanout_width,
+                ),
+                WorkloadKind::MultiInput => MultiInputWorkloadBuilder::from(
+                    weight,
+                    target_qps,
+                    num_workers,
+                    in_flight_ratio,
+                    mix.multi_input_inputs_per_tx,
+                ),
+                WorkloadKind::ClockRead => {
+                    ClockReadWorkloadBuilder::from(weight, target_qps, num_workers, in_flight_ratio)
+                }
+                WorkloadKind::Upgrade => {
+                    UpgradeWorkloadBuilder::from(weight, target_qps, num_workers, in_flight_ratio)
+                }
+                WorkloadKind::DynamicField => DynamicFieldWorkloadBuilder::from(
+                    weight,
+                    target_qps,
+                    num_workers,
+                    in_flight_ratio,
+                    mix.dynamic_field_count,
+                ),
+                // Publishes the bundled nft package, then mints an NFT from the package just
+                // published
+                WorkloadKind::PublishMint => SequencedWorkloadBuilder::from(
+                    "publish_mint",
+                    PublishWorkloadBuilder::from(
+                        weight,
+                        target_qps,
+                        num_workers,
+                        in_flight_ratio,
+                        None,
+                    ),
+                    NftWorkloadBuilder::from(weight, target_qps, num_workers, in_flight_ratio),
+                ),
+                WorkloadKind::Equivocation => EquivocationWorkloadBuilder::from(
+                    weight,
+                    target_qps,
+                    num_workers,
+                    in_flight_ratio,
+                    mix.equivocation_conflict_fraction,
+                    seeds.gen(),
+                ),
+                WorkloadKind::SizeMix => SizeMixWorkloadBuilder::from(
+                    weight,
+                    target_qps,
+                    num_workers,
+                    in_flight_ratio,
+                    SizeDistribution::from_str(&mix.size_mix_distribution).unwrap(),
+                    seeds.gen(),
+                ),
+            };
+            workload_builders.push(workload_builder);
+        }
+        let (mut workload_params, workload_builders): (Vec<_>, Vec<_>) = workload_builders
             .into_iter()
             .flatten()
             .map(|x| (x.workload_params, x.workload_builder))
@@ -136,6 +772,30 @@ impl WorkloadConfiguration {
                 .init(bank.proxy.clone(), system_state_observer.clone())
                 .await;
         }
+        // Unbounded runs outlast the gas handed out up front, so keep the senders funded. The
+        // faucet needs the senders of the other workloads, which are only known once they're built
+        if faucet {
+            let senders = workloads
+                .iter()
+                .flat_map(|workload| workload.senders())
+                .collect();
+            if let Some(faucet_workload) = FaucetWorkloadBuilder::from(senders) {
+                let mut faucet = bank
+                    .generate(
+                        vec![faucet_workload.workload_builder],
+                        reference_gas_price,
+                        chunk_size,
+                    )
+                    .await?;
+                for workload in faucet.iter_mut() {
+                    workload
+                        .init(bank.proxy.clone(), system_state_observer.clone())
+                        .await;
+                }
+                workloads.extend(faucet);
+                workload_params.push(faucet_workload.workload_params);
+            }
+        }
         Ok(workloads
             .into_iter()
             .zip(workload_params)
@@ -146,3 +806,204 @@ impl WorkloadConfiguration {
             .collect())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workloads::workload_target_qps;
+
+    #[test]
+    fn test_workload_mix_parsing() {
+        let mix: WorkloadMix = toml::from_str(
+            r#"
+            shared_counter_contention_factor = 0.5
+
+            [weights]
+            shared_counter = 2
+            transfer_object = 1
+            "#,
+        )
+        .unwrap();
+        mix.validate().unwrap();
+        assert_eq!(mix.weight("shared_counter"), 2);
+        assert_eq!(mix.weight("delegation"), 0);
+        assert_eq!(mix.shared_counter_contention_factor, 0.5);
+        assert_eq!(
+            mix.batch_payment_size,
+            WorkloadMix::default().batch_payment_size
+        );
+
+        let json: WorkloadMix = serde_json::from_str(
+            r#"{
+                "weights": {"shared_counter": 2, "transfer_object": 1},
+                "shared_counter_contention_factor": 0.5
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(json, mix);
+
+        // Weights can't be negative
+        assert!(toml::from_str::<WorkloadMix>("[weights]\nnft = -1").is_err());
+        // Unknown parameters are rejected rather than silently ignored
+        assert!(toml::from_str::<WorkloadMix>("batch_size = 10").is_err());
+    }
+
+    #[test]
+    fn test_workload_mix_validation() {
+        let mut mix = WorkloadMix::default();
+        assert!(mix.validate().is_err());
+        mix.weights.insert("nft".to_string(), 0);
+        assert!(mix.validate().is_err());
+        mix.weights.insert("nft".to_string(), 1);
+        mix.validate().unwrap();
+        mix.gas_fanout_width = 0;
+        assert!(mix.validate().is_err());
+        mix.gas_fanout_width = MAX_FANOUT_WIDTH;
+        mix.validate().unwrap();
+        mix.multi_input_inputs_per_tx = 1;
+        assert!(mix.validate().is_err());
+        mix.multi_input_inputs_per_tx = MAX_INPUTS_PER_TX;
+        mix.validate().unwrap();
+        mix.dynamic_field_count = 0;
+        assert!(mix.validate().is_err());
+        mix.dynamic_field_count = MAX_FIELD_COUNT;
+        mix.validate().unwrap();
+        mix.equivocation_conflict_fraction = 1.5;
+        assert!(mix.validate().is_err());
+        mix.equivocation_conflict_fraction = 1.0;
+        mix.validate().unwrap();
+        mix.adversarial_cfg = "1-0.5-0.25".to_string();
+        mix.validate().unwrap();
+        mix.adversarial_cfg = "1-0.5-2".to_string();
+        assert!(mix.validate().is_err());
+        mix.adversarial_cfg = "1-0.5".to_string();
+        mix.size_mix_distribution = "0:1,16000:1".to_string();
+        mix.validate().unwrap();
+        mix.size_mix_distribution = "0:1,16001:1".to_string();
+        assert!(mix.validate().is_err());
+        mix.size_mix_distribution = "0:0,1024:0".to_string();
+        assert!(mix.validate().is_err());
+        mix.size_mix_distribution = "1024".to_string();
+        assert!(mix.validate().is_err());
+        mix.size_mix_distribution = "0:80,4096:20".to_string();
+        mix.weights.insert("not_a_workload".to_string(), 1);
+        assert!(mix.validate().is_err());
+    }
+
+    #[test]
+    fn test_workload_schedule() {
+        let mix = |weights: &[(&str, u32)]| WorkloadMix {
+            weights: weights
+                .iter()
+                .map(|(name, weight)| (name.to_string(), *weight))
+                .collect(),
+            ..WorkloadMix::default()
+        };
+        let minutes = |minutes| Duration::from_secs(60 * minutes);
+        assert!(WorkloadSchedule::new(vec![]).is_err());
+        assert!(WorkloadSchedule::new(vec![(Duration::ZERO, mix(&[("nft", 1)]))]).is_err());
+        assert!(WorkloadSchedule::new(vec![(minutes(1), mix(&[]))]).is_err());
+        let mut other_parameters = mix(&[("nft", 1)]);
+        other_parameters.batch_payment_size = 1;
+        assert!(WorkloadSchedule::new(vec![
+            (minutes(1), mix(&[("nft", 1)])),
+            (minutes(1), other_parameters)
+        ])
+        .is_err());
+
+        let schedule = WorkloadSchedule::new(vec![
+            (minutes(10), mix(&[("transfer_object", 1)])),
+            (
+                minutes(5),
+                mix(&[("shared_counter", 3), ("transfer_object", 1)]),
+            ),
+        ])
+        .unwrap();
+        assert_eq!(schedule.cycle_duration(), minutes(15));
+        assert_eq!(schedule.phase_at(Duration::ZERO), (0, 0));
+        assert_eq!(schedule.phase_at(minutes(10)), (0, 1));
+        assert_eq!(schedule.phase_at(minutes(31)), (2, 0));
+
+        // Transfers take the whole load of the first phase, shared counters 3/4 of the second
+        assert_eq!(
+            schedule.peak_mix().weights,
+            BTreeMap::from([
+                ("shared_counter".to_string(), 7_500),
+                ("transfer_object".to_string(), 10_000)
+            ])
+        );
+        assert_eq!(schedule.peak_load(), 1.75);
+        assert_eq!(schedule.rate_scale_at("transfer_object", minutes(1)), 1.0);
+        assert_eq!(schedule.rate_scale_at("transfer_object", minutes(11)), 0.25);
+        assert_eq!(schedule.rate_scale_at("shared_counter", minutes(1)), 0.0);
+        assert_eq!(schedule.rate_scale_at("shared_counter", minutes(11)), 1.0);
+        assert_eq!(schedule.rate_scale_at("faucet", minutes(11)), 1.0);
+
+        let file: ScheduleFile = toml::from_str(
+            r#"
+            [[phases]]
+            duration_secs = 600
+            mix.weights = { transfer_object = 1 }
+
+            [[phases]]
+            duration_secs = 300
+            mix.weights = { shared_counter = 3, transfer_object = 1 }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(file.phases.len(), 2);
+        assert_eq!(file.phases[1].duration_secs, 300);
+        assert_eq!(file.phases[1].mix.weight("shared_counter"), 3);
+    }
+
+    #[test]
+    fn test_workload_target_qps() {
+        assert_eq!(workload_target_qps(0.5, 100), 50);
+        // A small share of a small load still runs the workload
+        assert_eq!(workload_target_qps(0.05, 10), 1);
+        assert_eq!(workload_target_qps(0.0, 10), 0);
+        assert_eq!(workload_target_qps(0.05, 0), 0);
+        assert!(SizeMixWorkloadBuilder::from(
+            0.05,
+            10,
+            10,
+            1,
+            WorkloadMix::default()
+                .size_mix_distribution
+                .parse()
+                .unwrap(),
+            0
+        )
+        .is_some());
+    }
+
+    #[test]
+    fn test_combination_workload_builder() {
+        assert!(CombinationWorkloadBuilder::new().build().is_err());
+        assert!(CombinationWorkloadBuilder::new()
+            .add(WorkloadKind::Nft, 0)
+            .build()
+            .is_err());
+
+        let mix = CombinationWorkloadBuilder::new()
+            .add(WorkloadKind::SharedCounter, 4)
+            .add(WorkloadKind::TransferObject, 2)
+            .add(WorkloadKind::TransferObject, 4)
+            .add(WorkloadKind::Adversarial, 0)
+            .with_rng_seed(7)
+            .build()
+            .unwrap();
+        assert_eq!(mix.rng_seed, Some(7));
+        assert_eq!(
+            mix.weights,
+            BTreeMap::from([
+                ("shared_counter".to_string(), 2),
+                ("transfer_object".to_string(), 3)
+            ])
+        );
+
+        for kind in WorkloadKind::iter() {
+            assert_eq!(WorkloadKind::from_str(kind.name()).unwrap(), kind);
+        }
+    }
+}
@@ -0,0 +1,233 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::in_memory_wallet::move_call_pt_impl;
+use crate::system_state_observer::SystemStateObserver;
+use crate::workloads::payload::Payload;
+use crate::workloads::workload::{
+    Workload, WorkloadBuilder, ESTIMATED_COMPUTATION_COST, MAX_GAS_FOR_TESTING,
+};
+use crate::workloads::{
+    workload_target_qps, Gas, GasCoinConfig, WorkloadBuilderInfo, WorkloadParams,
+};
+use crate::{ExecutionEffects, ValidatorProxy};
+use async_trait::async_trait;
+use std::path::PathBuf;
+use std::sync::Arc;
+use sui_types::{
+    base_types::{ObjectID, ObjectRef, SuiAddress},
+    crypto::get_key_pair,
+    messages::{
+        VerifiedTransaction, TEST_ONLY_GAS_UNIT_FOR_GENERIC, TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+    },
+    object::Owner,
+};
+use test_utils::messages::create_publish_move_package_transaction;
+use test_utils::transaction::parse_package_ref;
+use tracing::{error, info};
+
+/// Creates a parent object holding a wrapped child, then alternates between unwrapping the child
+/// to the sender and wrapping it back into the parent.
+#[derive(Debug)]
+pub struct WrapUnwrapTestPayload {
+    package_id: ObjectID,
+    /// The parent object, created by the first transaction
+    parent: Option<ObjectRef>,
+    /// The child object while it is unwrapped and owned by the sender
+    child: Option<ObjectRef>,
+    gas: Gas,
+    system_state_observer: Arc<SystemStateObserver>,
+}
+
+impl std::fmt::Display for WrapUnwrapTestPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "wrap_unwrap")
+    }
+}
+
+impl Payload for WrapUnwrapTestPayload {
+    fn make_new_payload(&mut self, effects: &ExecutionEffects) {
+        if !effects.is_ok() {
+            effects.print_gas_summary();
+            error!("Wrap unwrap tx failed...");
+        }
+        self.gas.0 = effects.gas_object().0;
+        let owned_by_sender = |(object_ref, owner): &(ObjectRef, Owner)| {
+            (*owner == Owner::AddressOwner(self.gas.1)).then_some(*object_ref)
+        };
+        self.parent = match self.parent {
+            // The child is created wrapped, so the parent is the only object created
+            None => effects.created().iter().find_map(owned_by_sender),
+            Some(parent) => effects
+                .mutated()
+                .iter()
+                .find(|(object_ref, _)| object_ref.0 == parent.0)
+                .map(|(object_ref, _)| *object_ref),
+        };
+        // Wrapping the child takes it out of the effects, unwrapping puts it back
+        self.child = effects.unwrapped().iter().find_map(owned_by_sender);
+    }
+    fn make_transaction(&mut self) -> VerifiedTransaction {
+        let gas_price = self.system_state_observer.gas_price();
+        let (function, arguments) = match (self.parent, self.child) {
+            (None, _) => ("create", vec![]),
+            (Some(parent), None) => ("unwrap", vec![parent.into()]),
+            (Some(parent), Some(child)) => ("wrap", vec![parent.into(), child.into()]),
+        };
+        move_call_pt_impl(
+            self.gas.1,
+            &self.gas.2,
+            self.package_id,
+            "wrap_unwrap",
+            function,
+            vec![],
+            arguments,
+            &self.gas.0,
+            gas_price * TEST_ONLY_GAS_UNIT_FOR_GENERIC,
+            gas_price,
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct WrapUnwrapWorkloadBuilder {
+    num_payloads: u64,
+}
+
+impl WrapUnwrapWorkloadBuilder {
+    pub fn from(
+        workload_weight: f32,
+        target_qps: u64,
+        num_workers: u64,
+        in_flight_ratio: u64,
+    ) -> Option<WorkloadBuilderInfo> {
+        let target_qps = workload_target_qps(workload_weight, target_qps);
+        let num_workers = (workload_weight * num_workers as f32).ceil() as u64;
+        let max_ops = target_qps * in_flight_ratio;
+        if max_ops == 0 || num_workers == 0 {
+            None
+        } else {
+            let workload_params = WorkloadParams {
+                target_qps,
+                num_workers,
+                max_ops,
+            };
+            let workload_builder = Box::<dyn WorkloadBuilder<dyn Payload>>::from(Box::new(
+                WrapUnwrapWorkloadBuilder {
+                    num_payloads: max_ops,
+                },
+            ));
+            let builder_info = WorkloadBuilderInfo {
+                workload_params,
+                workload_builder,
+            };
+            Some(builder_info)
+        }
+    }
+}
+
+#[async_trait]
+impl WorkloadBuilder<dyn Payload> for WrapUnwrapWorkloadBuilder {
+    async fn generate_coin_config_for_init(&self) -> Vec<GasCoinConfig> {
+        // Gas coin for publishing the wrap_unwrap package
+        let (address, keypair) = get_key_pair();
+        vec![GasCoinConfig {
+            amount: MAX_GAS_FOR_TESTING,
+            address,
+            keypair: Arc::new(keypair),
+        }]
+    }
+    async fn generate_coin_config_for_payloads(&self) -> Vec<GasCoinConfig> {
+        let mut configs = vec![];
+        let amount = MAX_GAS_FOR_TESTING + ESTIMATED_COMPUTATION_COST;
+        // Gas coins for running workload
+        for _i in 0..self.num_payloads {
+            let (address, keypair) = get_key_pair();
+            configs.push(GasCoinConfig {
+                amount,
+                address,
+                keypair: Arc::new(keypair),
+            });
+        }
+        configs
+    }
+    async fn build(
+        &self,
+        mut init_gas: Vec<Gas>,
+        payload_gas: Vec<Gas>,
+    ) -> Box<dyn Workload<dyn Payload>> {
+        Box::<dyn Workload<dyn Payload>>::from(Box::new(WrapUnwrapWorkload {
+            package_id: None,
+            init_gas: init_gas.pop().unwrap(),
+            payload_gas,
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub struct WrapUnwrapWorkload {
+    pub package_id: Option<ObjectID>,
+    pub init_gas: Gas,
+    pub payload_gas: Vec<Gas>,
+}
+
+#[async_trait]
+impl Workload<dyn Payload> for WrapUnwrapWorkload {
+    fn name(&self) -> &'static str {
+        "wrap_unwrap"
+    }
+    fn senders(&self) -> Vec<SuiAddress> {
+        self.payload_gas.iter().map(|gas| gas.1).collect()
+    }
+    async fn init(
+        &mut self,
+        proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) {
+        if self.package_id.is_some() {
+            return;
+        }
+        let gas_price = system_state_observer.state.borrow().reference_gas_price;
+        let (gas, sender, keypair) = &self.init_gas;
+
+        info!("Publishing wrap_unwrap package");
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("src/workloads/data/wrap_unwrap");
+        let transaction = create_publish_move_package_transaction(
+            *gas,
+            path,
+            *sender,
+            keypair,
+            gas_price * TEST_ONLY_GAS_UNIT_FOR_PUBLISH,
+            gas_price,
+        );
+        let effects = proxy
+            .execute_transaction_block(transaction.into())
+            .await
+            .unwrap();
+        self.package_id = Some(parse_package_ref(&effects.created()).unwrap().0);
+    }
+    async fn make_test_payloads(
+        &self,
+        _proxy: Arc<dyn ValidatorProxy + Sync + Send>,
+        system_state_observer: Arc<SystemStateObserver>,
+    ) -> Vec<Box<dyn Payload>> {
+        info!("Creating wrap_unwrap txn payloads, hang tight..");
+        let package_id = self
+            .package_id
+            .expect("Wrap unwrap workload must be initialized before creating payloads");
+        self.payload_gas
+            .iter()
+            .map(|gas| {
+                Box::new(WrapUnwrapTestPayload {
+                    package_id,
+                    parent: None,
+                    child: None,
+                    gas: gas.clone(),
+                    system_state_observer: system_state_observer.clone(),
+                })
+            })
+            .map(|b| Box::<dyn Payload>::from(b))
+            .collect()
+    }
+}
@@ -3,22 +3,26 @@
 
 #[cfg(msim)]
 mod test {
+    use comfy_table::{Cell, ContentArrangement, Row, Table};
 
     use move_core_types::language_storage::StructTag;
     use rand::{distributions::uniform::SampleRange, thread_rng, Rng};
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
     use std::str::FromStr;
     use std::sync::atomic::{AtomicBool, Ordering};
     use std::sync::{Arc, Mutex};
     use std::time::{Duration, Instant};
     use sui_benchmark::bank::BenchmarkBank;
     use sui_benchmark::system_state_observer::SystemStateObserver;
-    use sui_benchmark::workloads::adversarial::AdversarialPayloadCfg;
-    use sui_benchmark::workloads::workload_configuration::WorkloadConfiguration;
+    use sui_benchmark::workloads::workload_configuration::{
+        CombinationWorkloadBuilder, WorkloadConfiguration, WorkloadKind, WorkloadMix,
+    };
     use sui_benchmark::{
-        drivers::{bench_driver::BenchDriver, driver::Driver, Interval},
+        drivers::{bench_driver::BenchDriver, driver::Driver, BenchmarkStats, Interval},
+        replay_transaction_log,
         util::get_ed25519_keypair_from_keystore,
-        LocalValidatorAggregatorProxy, ValidatorProxy,
+        LocalValidatorAggregatorProxy, QuorumThreshold, RecordingValidatorProxy, RoutingPolicy,
+        ValidatorProxy,
     };
     use sui_config::genesis::Genesis;
     use sui_config::{AUTHORITIES_DB_NAME, SUI_KEYSTORE_FILENAME};
@@ -41,7 +45,14 @@ mod test {
         dead_until: std::time::Instant,
     }
 
+    /// The latencies of the simulated network. Their samples, like every other random choice of
+    /// the simulator, are drawn from the seed of the test run, which the test runner takes from
+    /// MSIM_TEST_SEED and prints when a test fails, so a config does not carry a seed of its own.
     fn test_config() -> SimConfig {
+        // A TOML file of latency configs, see `SimFileConfig`, replaces the ones below
+        if let Ok(path) = std::env::var("SUI_SIM_CONFIG_PATH") {
+            return config_from_path(path);
+        }
         env_config(
             uniform_latency_ms(10..20),
             [
@@ -53,6 +64,8 @@ mod test {
                     "global_high_variance",
                     bimodal_latency_ms(60..80, 500..1500, 0.01),
                 ),
+                ("regional_heavy_tail", exponential_latency_ms(40)),
+                ("global_heavy_tail", pareto_latency_ms(60, 1.5)),
             ],
         )
     }
@@ -82,27 +95,239 @@ mod test {
     }
 
     #[sim_test(config = "test_config()")]
-    async fn test_simulated_load_restarts() {
+    async fn test_simulated_load_drain() {
         sui_protocol_config::ProtocolConfig::poison_get_for_min_version();
-        let test_cluster = Arc::new(build_test_cluster(4, 0).await);
-        let node_restarter = test_cluster
+        let test_cluster = build_test_cluster(4, 0).await;
+        // Without waiting, whatever is in flight when the run ends is abandoned rather than
+        // counted as an error, which test_simulated_load checks is rare
+        let benchmark_stats = test_simulated_load_with_drain_timeout(
+            TestInitData::new(&test_cluster).await,
+            10,
+            Duration::ZERO,
+        )
+        .await;
+        assert!(benchmark_stats.num_abandoned_txes > 0);
+    }
+
+    #[sim_test(config = "test_config()")]
+    async fn test_simulated_load_gas_fanout() {
+        sui_protocol_config::ProtocolConfig::poison_get_for_min_version();
+        let test_cluster = build_test_cluster(4, 0).await;
+        // Only splits and deletes coins, at a higher QPS than the mixed workload, to stress
+        // object creation and deletion
+        let mix = CombinationWorkloadBuilder::from_mix(WorkloadMix {
+            gas_fanout_width: get_var("SIM_STRESS_TEST_GAS_FANOUT_WIDTH", 64),
+            ..Default::default()
+        })
+        .add(WorkloadKind::GasFanout, 1)
+        .build()
+        .unwrap();
+        let drain_timeout_secs = get_var("SIM_STRESS_TEST_DRAIN_TIMEOUT_SECS", 30);
+        test_simulated_load_with_mix(
+            TestInitData::new(&test_cluster).await,
+            30,
+            Duration::from_secs(drain_timeout_secs),
+            mix,
+            get_var("SIM_STRESS_TEST_GAS_FANOUT_QPS", 100),
+        )
+        .await;
+    }
+
+    #[sim_test(config = "test_config()")]
+    async fn test_simulated_load_committee_changes() {
+        sui_protocol_config::ProtocolConfig::poison_get_for_min_version();
+        // Sizes of the committee after each epoch boundary, starting from the largest of them
+        let schedule: Vec<usize> =
+            get_var("SIM_STRESS_TEST_COMMITTEE_SCHEDULE", "6,7,4,5".to_string())
+                .split(',')
+                .map(|size| size.parse().unwrap())
+                .collect();
+        let epoch_duration_ms = 10000;
+        let test_cluster = Arc::new(
+            init_test_cluster_builder(4, epoch_duration_ms)
+                .with_committee_schedule(schedule.clone())
+                .build()
+                .await
+                .unwrap(),
+        );
+        let committee_schedule = test_cluster.run_committee_schedule();
+        let duration_secs = (schedule.len() as u64 + 1) * epoch_duration_ms / 1000;
+        let benchmark_stats =
+            test_simulated_load(TestInitData::new(&test_cluster).await, duration_secs).await;
+        // The schedule panics if a committee does not have the scheduled size
+        committee_schedule.await.unwrap();
+        assert!(benchmark_stats.num_success_txes > 0);
+        assert_eq!(
+            benchmark_stats.num_error_txes, 0,
+            "Errors while the committee changed: {:?}",
+            benchmark_stats.errors_by_category
+        );
+    }
+
+    #[sim_test(config = "test_config()")]
+    async fn test_simulated_load_validator_sweep() {
+        sui_protocol_config::ProtocolConfig::poison_get_for_min_version();
+        let counts: Vec<usize> = get_var("SIM_STRESS_TEST_SWEEP_VALIDATORS", "4,7,10".to_string())
+            .split(',')
+            .map(|count| count.parse().unwrap())
+            .collect();
+        let results = sweep_validators(&counts, default_workload_mix(), 15).await;
+        for (num_validators, benchmark_stats) in &results {
+            assert!(
+                benchmark_stats.num_success_txes > 0,
+                "No transaction succeeded with {num_validators} validators"
+            );
+        }
+    }
+
+    #[sim_test(config = "test_config()")]
+    async fn test_simulated_load_store_shards() {
+        sui_protocol_config::ProtocolConfig::poison_get_for_min_version();
+        let counts: Vec<usize> = get_var("SIM_STRESS_TEST_STORE_SHARDS", "1,8".to_string())
+            .split(',')
+            .map(|count| count.parse().unwrap())
+            .collect();
+        // Owned object transfers only lock objects in the store, without going through consensus
+        let mix = CombinationWorkloadBuilder::from_mix(WorkloadMix::default())
+            .add(WorkloadKind::TransferObject, 1)
+            .build()
+            .unwrap();
+        let drain_timeout_secs = get_var("SIM_STRESS_TEST_DRAIN_TIMEOUT_SECS", 30);
+        let target_qps = get_var("SIM_STRESS_TEST_QPS", 10);
+        let mut results = vec![];
+        for &num_shards in &counts {
+            let test_cluster = init_test_cluster_builder(4, 0)
+                .with_store_shards(num_shards)
+                .build()
+                .await
+                .unwrap();
+            let benchmark_stats = test_simulated_load_with_mix(
+                TestInitData::new(&test_cluster).await,
+                15,
+                Duration::from_secs(drain_timeout_secs),
+                mix.clone(),
+                target_qps,
+            )
+            .await;
+            assert!(
+                benchmark_stats.num_success_txes > 0,
+                "No transaction succeeded with {num_shards} store shards"
+            );
+            results.push((num_shards, benchmark_stats));
+        }
+        info!(
+            "store shard sweep:\n{}",
+            sweep_table("store shards", &results)
+        );
+    }
+
+    /// Submits the transactions logged by an earlier test run with SIM_STRESS_TEST_TRANSACTION_LOG
+    /// set, from the log at SIM_STRESS_TEST_REPLAY_LOG, and fails if any of them has a different
+    /// outcome. Run it with the seed, number of validators and epoch duration of that run, so that
+    /// the cluster starts from the same genesis.
+    #[ignore = "Needs SIM_STRESS_TEST_REPLAY_LOG"]
+    #[sim_test(config = "test_config()")]
+    async fn test_simulated_load_replay() {
+        sui_protocol_config::ProtocolConfig::poison_get_for_min_version();
+        let log_path = std::env::var("SIM_STRESS_TEST_REPLAY_LOG")
+            .expect("SIM_STRESS_TEST_REPLAY_LOG must be set to the log to replay");
+        let test_cluster = build_test_cluster(4, 0).await;
+        let registry = prometheus::Registry::new();
+        let proxy = LocalValidatorAggregatorProxy::from_genesis(
+            &test_cluster.swarm.config().genesis,
+            &registry,
+            None,
+        )
+        .await;
+        let mismatches = replay_transaction_log(&proxy, Path::new(&log_path))
+            .await
+            .unwrap();
+        assert!(
+            mismatches.is_empty(),
+            "{} replayed transactions had a different outcome than recorded",
+            mismatches.len()
+        );
+    }
+
+    /// Logs the seed of a node restarter if the test fails, so that the same restart schedule can
+    /// be replayed by setting SIM_STRESS_TEST_RESTARTER_SEED, along with the MSIM_TEST_SEED of the
+    /// failed run for the simulated network to behave the same.
+    struct RestarterSeedGuard(u64);
+
+    impl Drop for RestarterSeedGuard {
+        fn drop(&mut self) {
+            if std::thread::panicking() {
+                error!(
+                    "Test failed with node restarter seed {}, rerun with SIM_STRESS_TEST_RESTARTER_SEED={} and the same MSIM_TEST_SEED to replay it",
+                    self.0, self.0
+                );
+            }
+        }
+    }
+
+    fn run_node_restarter(test_cluster: &Arc<TestCluster>, crash_only: bool) -> RestarterSeedGuard {
+        let mut node_restarter = test_cluster
             .random_node_restarter()
             .with_kill_interval_secs(5, 15)
-            .with_restart_delay_secs(1, 10);
+            .with_restart_delay_secs(1, 10)
+            .with_crash_only(crash_only);
+        if let Ok(seed) = std::env::var("SIM_STRESS_TEST_RESTARTER_SEED") {
+            node_restarter = node_restarter.with_seed(seed.parse().unwrap());
+        }
         node_restarter.run();
+        RestarterSeedGuard(node_restarter.seed())
+    }
+
+    #[sim_test(config = "test_config()")]
+    async fn test_simulated_load_restarts() {
+        sui_protocol_config::ProtocolConfig::poison_get_for_min_version();
+        let test_cluster = Arc::new(build_test_cluster(4, 0).await);
+        let _seed_guard = run_node_restarter(&test_cluster, false);
         test_simulated_load(TestInitData::new(&test_cluster).await, 120).await;
     }
 
+    #[sim_test(config = "test_config()")]
+    async fn test_simulated_load_crash_restarts() {
+        sui_protocol_config::ProtocolConfig::poison_get_for_min_version();
+        let test_cluster = Arc::new(build_test_cluster(4, 0).await);
+        let _seed_guard = run_node_restarter(&test_cluster, true);
+        let benchmark_stats =
+            test_simulated_load(TestInitData::new(&test_cluster).await, 120).await;
+        assert!(benchmark_stats.num_success_txes > 0);
+        assert_eq!(
+            benchmark_stats.num_error_txes, 0,
+            "Errors after crash restarts: {:?}",
+            benchmark_stats.errors_by_category
+        );
+    }
+
+    #[sim_test(config = "test_config()")]
+    async fn test_simulated_load_partition() {
+        sui_protocol_config::ProtocolConfig::poison_get_for_min_version();
+        let test_cluster = Arc::new(build_test_cluster(4, 0).await);
+        let partitioned_cluster = test_cluster.clone();
+        let _handle = tokio::task::spawn(async move {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            // 3 of 4 validators still form a quorum, so the majority side keeps making progress
+            partitioned_cluster
+                .partition(vec![vec![0, 1, 2], vec![3]], Duration::from_secs(30))
+                .await;
+        });
+        let benchmark_stats = test_simulated_load(TestInitData::new(&test_cluster).await, 60).await;
+        assert!(benchmark_stats.num_success_txes > 0);
+        assert_eq!(
+            benchmark_stats.num_error_txes, 0,
+            "Errors during partition: {:?}",
+            benchmark_stats.errors_by_category
+        );
+    }
+
     #[ignore = "MUSTFIX"]
     #[sim_test(config = "test_config()")]
     async fn test_simulated_load_reconfig_restarts() {
         sui_protocol_config::ProtocolConfig::poison_get_for_min_version();
         let test_cluster = Arc::new(build_test_cluster(4, 1000).await);
-        let node_restarter = test_cluster
-            .random_node_restarter()
-            .with_kill_interval_secs(5, 15)
-            .with_restart_delay_secs(1, 10);
-        node_restarter.run();
+        let _seed_guard = run_node_restarter(&test_cluster, false);
         test_simulated_load(TestInitData::new(&test_cluster).await, 120).await;
     }
 
@@ -354,15 +579,84 @@ mod test {
         if epoch_duration_ms > 0 {
             builder = builder.with_epoch_duration_ms(epoch_duration_ms);
         }
+        // Grows the store before any traffic, to catch regressions that only show up with state
+        let num_preseeded_objects = get_var("SIM_STRESS_TEST_PRESEEDED_OBJECTS", 0);
+        if num_preseeded_objects > 0 {
+            builder = builder.with_preseeded_objects(num_preseeded_objects);
+        }
         builder
     }
 
+    /// Runs the same load for `test_duration_secs` on a fresh cluster of each of `counts`
+    /// validators, to show how throughput and latency scale with the size of the committee.
+    /// Returns the stats of each run along with its number of validators.
+    async fn sweep_validators(
+        counts: &[usize],
+        mix: WorkloadMix,
+        test_duration_secs: u64,
+    ) -> Vec<(usize, BenchmarkStats)> {
+        let drain_timeout_secs = get_var("SIM_STRESS_TEST_DRAIN_TIMEOUT_SECS", 30);
+        let target_qps = get_var("SIM_STRESS_TEST_QPS", 10);
+        let mut results = vec![];
+        for &num_validators in counts {
+            let test_cluster = init_test_cluster_builder(num_validators, 0)
+                .with_num_validators(num_validators)
+                .build()
+                .await
+                .unwrap();
+            let benchmark_stats = test_simulated_load_with_mix(
+                TestInitData::new(&test_cluster).await,
+                test_duration_secs,
+                Duration::from_secs(drain_timeout_secs),
+                mix.clone(),
+                target_qps,
+            )
+            .await;
+            results.push((num_validators, benchmark_stats));
+        }
+        info!(
+            "validator count sweep:\n{}",
+            sweep_table("validators", &results)
+        );
+        results
+    }
+
+    /// The stats of each run of a sweep over `parameter`, along with the value of `parameter`
+    fn sweep_table(parameter: &str, results: &[(usize, BenchmarkStats)]) -> Table {
+        let mut table = Table::new();
+        table
+            .set_content_arrangement(ContentArrangement::Dynamic)
+            .set_width(200)
+            .set_header(vec![
+                parameter,
+                "offered qps",
+                "tps",
+                "num success",
+                "num error",
+                "latency (p50)",
+                "latency (p99)",
+            ]);
+        for (value, stats) in results {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(value));
+            row.add_cell(Cell::new(format!("{:.2}", stats.offered_qps())));
+            row.add_cell(Cell::new(format!("{:.2}", stats.tps())));
+            row.add_cell(Cell::new(stats.num_success_txes));
+            row.add_cell(Cell::new(stats.num_error_txes));
+            row.add_cell(Cell::new(stats.p50()));
+            row.add_cell(Cell::new(stats.p99()));
+            table.add_row(row);
+        }
+        table
+    }
+
     #[derive(Clone)]
     struct TestInitData {
         keystore_path: PathBuf,
         genesis: Genesis,
         all_gas: Vec<(StructTag, ObjectRef)>,
         sender: SuiAddress,
+        fullnode_rpc_url: String,
     }
 
     impl TestInitData {
@@ -374,16 +668,152 @@ mod test {
                 all_gas: get_sui_gas_object_with_wallet_context(&test_cluster.wallet, &sender)
                     .await,
                 sender,
+                fullnode_rpc_url: test_cluster.rpc_url().to_string(),
             }
         }
     }
 
-    async fn test_simulated_load(init_data: TestInitData, test_duration_secs: u64) {
+    async fn test_simulated_load(
+        init_data: TestInitData,
+        test_duration_secs: u64,
+    ) -> BenchmarkStats {
+        let drain_timeout_secs = get_var("SIM_STRESS_TEST_DRAIN_TIMEOUT_SECS", 30);
+        test_simulated_load_with_drain_timeout(
+            init_data,
+            test_duration_secs,
+            Duration::from_secs(drain_timeout_secs),
+        )
+        .await
+    }
+
+    async fn test_simulated_load_with_drain_timeout(
+        init_data: TestInitData,
+        test_duration_secs: u64,
+        drain_timeout: Duration,
+    ) -> BenchmarkStats {
+        test_simulated_load_with_mix(
+            init_data,
+            test_duration_secs,
+            drain_timeout,
+            default_workload_mix(),
+            get_var("SIM_STRESS_TEST_QPS", 10),
+        )
+        .await
+    }
+
+    /// The workloads run by most of the tests, with weights and parameters that can be overridden
+    /// with environment variables
+    fn default_workload_mix() -> WorkloadMix {
+        CombinationWorkloadBuilder::from_mix(WorkloadMix {
+            batch_payment_size: get_var("SIM_BATCH_PAYMENT_SIZE", 15),
+            coin_ops_split_count: get_var("SIM_STRESS_TEST_COIN_OPS_SPLIT_COUNT", 4),
+            // Fraction of the invalid transaction workload's transactions which validators must
+            // reject
+            invalid_transaction_fraction: get_var("SIM_STRESS_TEST_INVALID_FRACTION", 0.5),
+            // Run random payloads at 100% load
+            adversarial_cfg: "0-1.0".to_string(),
+            shared_counter_hotness_factor: 50,
+            // 0.0 spreads shared counter traffic over all counters, 1.0 sends it all to one
+            // counter
+            shared_counter_contention_factor: get_var("SIM_STRESS_TEST_CONTENTION", 0.0),
+            // Owned coins, besides the gas coin, taken as inputs by each multi input transaction
+            multi_input_inputs_per_tx: get_var("SIM_STRESS_TEST_INPUTS_PER_TX", 16),
+            // Dynamic fields added to, then removed from, a parent object by each dynamic field
+            // transaction
+            dynamic_field_count: get_var("SIM_STRESS_TEST_FIELD_COUNT", 16),
+            // Fraction of the equivocation workload's transactions which conflict with the
+            // previous transaction of their sender, and validators must reject
+            equivocation_conflict_fraction: get_var("SIM_STRESS_TEST_CONFLICT_FRACTION", 0.5),
+            // Argument sizes in bytes of the size mix workload's transactions with their weights,
+            // as "{bytes}:{weight},..."
+            size_mix_distribution: get_var(
+                "SIM_STRESS_TEST_SIZE_DISTRIBUTION",
+                "0:80,4096:20".to_string(),
+            ),
+            // Along with SIM_STRESS_TEST_RESTARTER_SEED, repeats the random choices of a failed
+            // run, whose seed is logged at the start of the run
+            rng_seed: std::env::var("SIM_STRESS_TEST_WORKLOAD_SEED")
+                .ok()
+                .map(|seed| seed.parse().unwrap()),
+            ..Default::default()
+        })
+        .add(WorkloadKind::SharedCounter, 1)
+        .add(WorkloadKind::TransferObject, 1)
+        .add(WorkloadKind::Delegation, 1)
+        .add(WorkloadKind::BatchPayment, 1)
+        .add(WorkloadKind::Nft, 1)
+        .add(
+            WorkloadKind::Publish,
+            get_var("SIM_STRESS_TEST_PUBLISH_WEIGHT", 1),
+        )
+        .add(
+            WorkloadKind::FullnodeQuery,
+            get_var("SIM_STRESS_TEST_FULLNODE_QUERY_WEIGHT", 0),
+        )
+        .add(
+            WorkloadKind::CoinOps,
+            get_var("SIM_STRESS_TEST_COIN_OPS_WEIGHT", 1),
+        )
+        .add(
+            WorkloadKind::InvalidTransaction,
+            get_var("SIM_STRESS_TEST_INVALID_TX_WEIGHT", 1),
+        )
+        .add(
+            WorkloadKind::WrapUnwrap,
+            get_var("SIM_STRESS_TEST_WRAP_UNWRAP_WEIGHT", 0),
+        )
+        .add(
+            WorkloadKind::GasFanout,
+            get_var("SIM_STRESS_TEST_GAS_FANOUT_WEIGHT", 0),
+        )
+        .add(
+            WorkloadKind::MultiInput,
+            get_var("SIM_STRESS_TEST_MULTI_INPUT_WEIGHT", 0),
+        )
+        .add(
+            WorkloadKind::ClockRead,
+            get_var("SIM_STRESS_TEST_CLOCK_READ_WEIGHT", 0),
+        )
+        .add(
+            WorkloadKind::Upgrade,
+            get_var("SIM_STRESS_TEST_UPGRADE_WEIGHT", 0),
+        )
+        .add(
+            WorkloadKind::DynamicField,
+            get_var("SIM_STRESS_TEST_DYNAMIC_FIELD_WEIGHT", 0),
+        )
+        .add(
+            WorkloadKind::PublishMint,
+            get_var("SIM_STRESS_TEST_PUBLISH_MINT_WEIGHT", 0),
+        )
+        .add(
+            WorkloadKind::Equivocation,
+            get_var("SIM_STRESS_TEST_EQUIVOCATION_WEIGHT", 0),
+        )
+        .add(
+            WorkloadKind::SizeMix,
+            get_var("SIM_STRESS_TEST_SIZE_MIX_WEIGHT", 0),
+        )
+        // TODO: re-enable this when we figure out why it is causing connection errors and making
+        // tests run for ever
+        .add(WorkloadKind::Adversarial, 0)
+        .build()
+        .unwrap()
+    }
+
+    async fn test_simulated_load_with_mix(
+        init_data: TestInitData,
+        test_duration_secs: u64,
+        drain_timeout: Duration,
+        mix: WorkloadMix,
+        target_qps: u64,
+    ) -> BenchmarkStats {
         let TestInitData {
             keystore_path,
             genesis,
             all_gas,
             sender,
+            fullnode_rpc_url,
         } = init_data;
 
         let ed25519_keypair =
@@ -394,50 +824,58 @@ mod test {
         let pay_coin = (pay_coin.clone(), sender, ed25519_keypair.clone());
 
         let registry = prometheus::Registry::new();
+        // "broadcast", "lowest-latency-first", "round-robin" or "random", see `RoutingPolicy`
+        let routing_policy = get_var("SIM_STRESS_TEST_ROUTING_POLICY", RoutingPolicy::Broadcast);
+        // "validity", "quorum" or "all", see `QuorumThreshold`
+        let quorum_threshold = get_var("SIM_STRESS_TEST_QUORUM_THRESHOLD", QuorumThreshold::Quorum);
+        let proxy = LocalValidatorAggregatorProxy::from_genesis(&genesis, &registry, None)
+            .await
+            .with_routing_policy(routing_policy)
+            .with_quorum_threshold(quorum_threshold);
+        // Logs every transaction, setup included, for test_simulated_load_replay
         let proxy: Arc<dyn ValidatorProxy + Send + Sync> =
-            Arc::new(LocalValidatorAggregatorProxy::from_genesis(&genesis, &registry, None).await);
-
-        let bank = BenchmarkBank::new(proxy.clone(), primary_gas, vec![pay_coin]);
+            match std::env::var("SIM_STRESS_TEST_TRANSACTION_LOG") {
+                Ok(log_path) => Arc::new(
+                    RecordingValidatorProxy::new(Box::new(proxy), Path::new(&log_path)).unwrap(),
+                ),
+                Err(_) => Arc::new(proxy),
+            };
+
+        let mut bank = BenchmarkBank::new(proxy.clone(), primary_gas, vec![pay_coin]);
+        // Every payload sends from an account of its own unless a shared pool size is given
+        let num_accounts = get_var("SIM_STRESS_TEST_NUM_ACCOUNTS", 0);
+        if num_accounts > 0 {
+            bank = bank.with_num_accounts(num_accounts);
+        }
         let system_state_observer = {
             let mut system_state_observer = SystemStateObserver::new(proxy.clone());
             if let Ok(_) = system_state_observer.state.changed().await {
                 info!("Got the new state (reference gas price and/or protocol config) from system state object");
             }
+            // A single price or a "<min>-<max>" range, the reference gas price is used if unset
+            if let Ok(gas_price) = std::env::var("SIM_STRESS_TEST_GAS_PRICE") {
+                system_state_observer =
+                    system_state_observer.with_gas_price(gas_price.parse().unwrap());
+            }
+            if let Ok(epochs_ahead) = std::env::var("SIM_STRESS_TEST_EXPIRATION_EPOCHS") {
+                system_state_observer =
+                    system_state_observer.with_expiration(epochs_ahead.parse().unwrap());
+            }
             Arc::new(system_state_observer)
         };
 
         // The default test parameters are somewhat conservative in order to keep the running time
         // of the test reasonable in CI.
-        let target_qps = get_var("SIM_STRESS_TEST_QPS", 10);
         let num_workers = get_var("SIM_STRESS_TEST_WORKERS", 10);
         let in_flight_ratio = get_var("SIM_STRESS_TEST_IFR", 2);
-        let batch_payment_size = get_var("SIM_BATCH_PAYMENT_SIZE", 15);
-        let shared_counter_weight = 1;
-        let transfer_object_weight = 1;
         let num_transfer_accounts = 2;
-        let delegation_weight = 1;
-        let batch_payment_weight = 1;
 
-        // Run random payloads at 100% load
-        let adversarial_cfg = AdversarialPayloadCfg::from_str("0-1.0").unwrap();
-
-        // TODO: re-enable this when we figure out why it is causing connection errors and making
-        // tests run for ever
-        let adversarial_weight = 0;
-
-        let shared_counter_hotness_factor = 50;
-
-        let workloads = WorkloadConfiguration::build_workloads(
+        let workloads = WorkloadConfiguration::build_workloads_from_mix(
+            &mix,
             num_workers,
             num_transfer_accounts,
-            shared_counter_weight,
-            transfer_object_weight,
-            delegation_weight,
-            batch_payment_weight,
-            adversarial_weight,
-            adversarial_cfg,
-            batch_payment_size,
-            shared_counter_hotness_factor,
+            Some(fullnode_rpc_url),
+            false,
             target_qps,
             in_flight_ratio,
             bank,
@@ -447,7 +885,16 @@ mod test {
         .await
         .unwrap();
 
-        let driver = BenchDriver::new(5, false);
+        let warmup_secs = get_var("SIM_STRESS_TEST_WARMUP_SECS", 0);
+        let stagger_secs = get_var("SIM_STRESS_TEST_STAGGER_SECS", 0);
+        let mut driver = BenchDriver::new(5, false)
+            .with_warmup(Duration::from_secs(warmup_secs))
+            .with_stagger(Duration::from_secs(stagger_secs))
+            .with_drain_timeout(drain_timeout)
+            .with_verify_effects(get_var("SIM_STRESS_TEST_VERIFY_EFFECTS_RATE", 0.1));
+        if let Ok(record_path) = std::env::var("SIM_STRESS_TEST_RECORD_PATH") {
+            driver = driver.with_record_path(record_path.into());
+        }
 
         // Use 0 for unbounded
         let test_duration_secs = get_var("SIM_STRESS_TEST_DURATION_SECS", test_duration_secs);
@@ -471,9 +918,48 @@ mod test {
             .await
             .unwrap();
 
+        if let Ok(stats_path) = std::env::var("SIM_STRESS_TEST_STATS_PATH") {
+            std::fs::write(&stats_path, benchmark_stats.to_json().unwrap()).unwrap();
+            info!("Wrote benchmark stats to {stats_path}");
+        }
+        // Accumulates one row per run, to follow performance across many runs
+        if let Ok(csv_path) = std::env::var("SIM_STRESS_TEST_SUMMARY_CSV") {
+            benchmark_stats
+                .append_summary_csv(Path::new(&csv_path))
+                .unwrap();
+            info!("Appended benchmark summary to {csv_path}");
+        }
+
         // TODO: make this stricter (== 0) when we have reliable error retrying on the client.
-        assert!(benchmark_stats.num_error_txes < 30);
+        assert!(
+            benchmark_stats.num_error_txes < 30,
+            "Too many errors: {:?}",
+            benchmark_stats.errors_by_category
+        );
+
+        assert_eq!(
+            benchmark_stats.num_semantic_failures, 0,
+            "Transactions succeeded with unexpected effects"
+        );
 
+        // Catches severe throughput regressions, 0 disables the check. Any warmup is already
+        // excluded from the stats so this only covers the steady state.
+        let min_tps = get_var("SIM_STRESS_TEST_MIN_TPS", 0.0);
+        if min_tps > 0.0 {
+            benchmark_stats.assert_min_throughput(min_tps);
+        }
+
+        tracing::info!(
+            "latency_ms(p50/p95/p99/max) = {}/{}/{}/{}",
+            benchmark_stats.p50(),
+            benchmark_stats.p95(),
+            benchmark_stats.p99(),
+            benchmark_stats.max()
+        );
+        if benchmark_stats.stats_by_epoch.len() > 1 {
+            tracing::info!("per epoch stats:\n{}", benchmark_stats.epochs_to_table());
+        }
         tracing::info!("end of test {:?}", benchmark_stats);
+        benchmark_stats
     }
 }
@@ -46,6 +46,13 @@ use typed_store::rocks::util::is_ref_count_value;
 
 const NUM_SHARDS: usize = 4096;
 
+#[cfg(msim)]
+thread_local! {
+    /// Number of shards of the lock tables of the stores opened on this thread, see
+    /// `AuthorityStore::set_num_shards_for_testing`
+    static NUM_SHARDS_FOR_TESTING: std::cell::Cell<Option<usize>> = std::cell::Cell::new(None);
+}
+
 /// ALL_OBJ_VER determines whether we want to store all past
 /// versions of every object in the store. Authority doesn't store
 /// them, but other entities such as replicas will.
@@ -115,6 +122,15 @@ impl AuthorityStore {
         .await
     }
 
+    /// Partition the object lock tables of the stores opened afterwards on this thread across
+    /// `num_shards` shards, instead of the 4 the simulator otherwise uses, or go back to that
+    /// default if `None`. The simulator runs every node on the same thread, so this applies to all
+    /// the nodes of a test, including the ones restarted later on.
+    #[cfg(msim)]
+    pub fn set_num_shards_for_testing(num_shards: Option<usize>) {
+        NUM_SHARDS_FOR_TESTING.with(|cur| cur.set(num_shards));
+    }
+
     pub async fn open_with_committee_for_testing(
         path: &Path,
         db_options: Option<Options>,
@@ -145,14 +161,25 @@ impl AuthorityStore {
     ) -> SuiResult<Self> {
         let epoch = committee.epoch;
 
+        #[cfg(msim)]
+        let num_shards_for_testing = NUM_SHARDS_FOR_TESTING.with(|num_shards| num_shards.get());
+        #[cfg(not(msim))]
+        let num_shards_for_testing = None;
+        let (mutex_table, objects_lock_table) = match num_shards_for_testing {
+            Some(num_shards) => (
+                MutexTable::new_for_testing(num_shards),
+                RwLockTable::new_for_testing(num_shards),
+            ),
+            None => (MutexTable::new(NUM_SHARDS), RwLockTable::new(NUM_SHARDS)),
+        };
         let store = Self {
-            mutex_table: MutexTable::new(NUM_SHARDS),
+            mutex_table,
             perpetual_tables,
             executed_effects_notify_read: NotifyRead::new(),
             root_state_notify_read:
                 NotifyRead::<EpochId, (CheckpointSequenceNumber, Accumulator)>::new(),
             execution_lock: RwLock::new(epoch),
-            objects_lock_table: Arc::new(RwLockTable::new(NUM_SHARDS)),
+            objects_lock_table: Arc::new(objects_lock_table),
             indirect_objects_threshold,
             enable_epoch_sui_conservation_check,
         };
@@ -21,8 +21,10 @@ pub use tower;
 #[cfg(msim)]
 pub mod configs {
     use msim::*;
+    use serde::Deserialize;
     use std::collections::HashMap;
     use std::ops::Range;
+    use std::path::Path;
     use std::time::Duration;
 
     use tracing::info;
@@ -76,6 +78,32 @@ pub mod configs {
         }
     }
 
+    // msim only samples latencies from uniform and bimodal distributions, so heavy-tailed
+    // distributions are approximated by a bimodal one: the bulk of samples are drawn uniformly
+    // from below the TAIL_START quantile, and the rest from between it and the TAIL_END quantile.
+    const TAIL_START: f64 = 0.9;
+    const TAIL_END: f64 = 0.999;
+
+    fn heavy_tailed_latency_ms(quantile: impl Fn(f64) -> f64, min: u64) -> SimConfig {
+        let tail_start = (quantile(TAIL_START).round() as u64).max(min + 1);
+        let tail_end = (quantile(TAIL_END).round() as u64).max(tail_start + 1);
+        bimodal_latency_ms(min..tail_start, tail_start..tail_end, 1.0 - TAIL_START)
+    }
+
+    /// A network with latency approximately exponentially distributed with the given mean.
+    pub fn exponential_latency_ms(mean: u64) -> SimConfig {
+        assert!(mean > 0, "Mean latency must be positive");
+        heavy_tailed_latency_ms(|p| -(mean as f64) * (1.0 - p).ln(), 0)
+    }
+
+    /// A network with latency approximately pareto distributed, with a minimum latency of `scale`
+    /// and a tail that gets heavier as `shape` decreases.
+    pub fn pareto_latency_ms(scale: u64, shape: f64) -> SimConfig {
+        assert!(scale > 0, "Pareto scale must be positive");
+        assert!(shape > 0.0, "Pareto shape must be positive");
+        heavy_tailed_latency_ms(|p| scale as f64 / (1.0 - p).powf(1.0 / shape), scale)
+    }
+
     /// Select from among a number of configs using the SUI_SIM_CONFIG env var.
     pub fn env_config(
         // Config to use when SUI_SIM_CONFIG is not set.
@@ -101,6 +129,119 @@ pub mod configs {
             default
         }
     }
+
+    /// The latency distribution of a network, as described in a config file. Exactly one of the
+    /// fields must be set, ranges are `[start, end)` in milliseconds.
+    #[derive(Deserialize, Debug)]
+    #[serde(deny_unknown_fields)]
+    pub struct LatencyFileConfig {
+        pub uniform_latency_ms: Option<[u64; 2]>,
+        pub bimodal_latency_ms: Option<BimodalLatencyFileConfig>,
+        /// The mean latency.
+        pub exponential_latency_ms: Option<u64>,
+        pub pareto_latency_ms: Option<ParetoLatencyFileConfig>,
+    }
+
+    /// Arguments of `bimodal_latency_ms`.
+    #[derive(Deserialize, Debug)]
+    #[serde(deny_unknown_fields)]
+    pub struct BimodalLatencyFileConfig {
+        pub baseline: [u64; 2],
+        pub degraded: [u64; 2],
+        pub degraded_freq: f64,
+    }
+
+    /// Arguments of `pareto_latency_ms`.
+    #[derive(Deserialize, Debug)]
+    #[serde(deny_unknown_fields)]
+    pub struct ParetoLatencyFileConfig {
+        pub scale: u64,
+        pub shape: f64,
+    }
+
+    /// A set of network configs loaded from a TOML file, e.g.
+    ///
+    /// ```toml
+    /// [default]
+    /// uniform_latency_ms = [10, 20]
+    ///
+    /// [regions.global_high_variance.bimodal_latency_ms]
+    /// baseline = [60, 80]
+    /// degraded = [500, 1500]
+    /// degraded_freq = 0.01
+    ///
+    /// [regions.regional_heavy_tail]
+    /// exponential_latency_ms = 40
+    ///
+    /// [regions.global_heavy_tail.pareto_latency_ms]
+    /// scale = 60
+    /// shape = 1.5
+    /// ```
+    #[derive(Deserialize, Debug)]
+    #[serde(deny_unknown_fields)]
+    pub struct SimFileConfig {
+        /// Config to use when SUI_SIM_CONFIG is not set.
+        pub default: LatencyFileConfig,
+        /// Configs selected by the value of SUI_SIM_CONFIG.
+        #[serde(default)]
+        pub regions: HashMap<String, LatencyFileConfig>,
+    }
+
+    impl LatencyFileConfig {
+        fn to_sim_config(&self) -> SimConfig {
+            match (
+                &self.uniform_latency_ms,
+                &self.bimodal_latency_ms,
+                &self.exponential_latency_ms,
+                &self.pareto_latency_ms,
+            ) {
+                (Some([start, end]), None, None, None) => uniform_latency_ms(*start..*end),
+                (None, Some(bimodal), None, None) => bimodal_latency_ms(
+                    bimodal.baseline[0]..bimodal.baseline[1],
+                    bimodal.degraded[0]..bimodal.degraded[1],
+                    bimodal.degraded_freq,
+                ),
+                (None, None, Some(mean), None) => exponential_latency_ms(*mean),
+                (None, None, None, Some(pareto)) => pareto_latency_ms(pareto.scale, pareto.shape),
+                _ => panic!(
+                    "Exactly one of uniform_latency_ms, bimodal_latency_ms, exponential_latency_ms and pareto_latency_ms must be set, got {:?}",
+                    self
+                ),
+            }
+        }
+    }
+
+    /// Like `env_config`, but with the default and named configs read from the TOML file at
+    /// `path`, see `SimFileConfig` for the format. Lets latency profiles be swept without
+    /// recompiling.
+    pub fn config_from_path(path: impl AsRef<Path>) -> SimConfig {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .unwrap_or_else(|e| panic!("Failed to read sim config {}: {}", path.display(), e));
+        let file_config: SimFileConfig = toml::from_str(&contents)
+            .unwrap_or_else(|e| panic!("Failed to parse sim config {}: {}", path.display(), e));
+        if let Some(env) = std::env::var("SUI_SIM_CONFIG").ok() {
+            if let Some(cfg) = file_config.regions.get(&env) {
+                info!(
+                    "Using config {} from {} for SUI_SIM_CONFIG={}",
+                    env,
+                    path.display(),
+                    env
+                );
+                cfg.to_sim_config()
+            } else {
+                panic!(
+                    "No config found for SUI_SIM_CONFIG={} in {}. Available configs are: {:?}",
+                    env,
+                    path.display(),
+                    file_config.regions.keys()
+                );
+            }
+        } else {
+            info!("Using default config from {}", path.display());
+            file_config.default.to_sim_config()
+        }
+    }
 }
 
 #[cfg(not(msim))]
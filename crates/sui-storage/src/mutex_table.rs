@@ -103,7 +103,20 @@ impl<K: Hash + Eq + Send + Sync + 'static, L: Lock + 'static> LockTable<K, L> {
         cleanup_entries_threshold: usize,
     ) -> Self {
         let num_shards = if cfg!(msim) { 4 } else { num_shards };
+        Self::new_with_exact_shards(
+            num_shards,
+            cleanup_period,
+            cleanup_initial_delay,
+            cleanup_entries_threshold,
+        )
+    }
 
+    fn new_with_exact_shards(
+        num_shards: usize,
+        cleanup_period: Duration,
+        cleanup_initial_delay: Duration,
+        cleanup_entries_threshold: usize,
+    ) -> Self {
         let lock_table: Arc<Vec<RwLock<InnerLockTable<K, L>>>> = Arc::new(
             (0..num_shards)
                 .into_iter()
@@ -148,6 +161,17 @@ impl<K: Hash + Eq + Send + Sync + 'static, L: Lock + 'static> LockTable<K, L> {
         )
     }
 
+    /// Same as `new`, but also keeps `num_shards` under the simulator, which otherwise always uses
+    /// 4 shards. Only meant for tests measuring contention on the table.
+    pub fn new_for_testing(num_shards: usize) -> Self {
+        Self::new_with_exact_shards(
+            num_shards,
+            Duration::from_secs(10),
+            Duration::from_secs(10),
+            10_000,
+        )
+    }
+
     pub fn size(&self) -> usize {
         self.size.load(Ordering::SeqCst)
     }
@@ -85,6 +85,28 @@ impl Container {
         }
     }
 
+    /// Kill every task of the node at once, as if its process crashed, so that nothing gets a
+    /// chance to shut down cleanly. The node can be brought back with `restart`.
+    pub fn crash(&self) {
+        if let Some(handle) = &self.handle {
+            info!("crashing {}", handle.node_id);
+            sui_simulator::runtime::Handle::current().kill(handle.node_id);
+        }
+    }
+
+    /// Restart a crashed node, which recovers from whatever state it left behind.
+    pub fn restart(&self) {
+        if let Some(handle) = &self.handle {
+            info!("restarting {}", handle.node_id);
+            sui_simulator::runtime::Handle::current().restart(handle.node_id);
+        }
+    }
+
+    /// Id of the simulator node the container runs in, `None` once it has been shut down.
+    pub fn node_id(&self) -> Option<sui_simulator::task::NodeId> {
+        self.handle.as_ref().map(|handle| handle.node_id)
+    }
+
     /// Get a SuiNodeHandle to the node owned by the container.
     pub fn get_node_handle(&self) -> Option<SuiNodeHandle> {
         Some(SuiNodeHandle::new(self.node_watch.borrow().upgrade()?))
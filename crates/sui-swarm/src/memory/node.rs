@@ -68,6 +68,34 @@ impl Node {
         *self.container.lock().unwrap() = None;
     }
 
+    /// Crash this Node without letting it shut down, see `restart` to bring it back.
+    #[cfg(msim)]
+    pub fn crash(&self) {
+        info!(name =% self.name().concise(), "crashing in-memory node");
+        if let Some(container) = self.container.lock().unwrap().as_ref() {
+            container.crash();
+        }
+    }
+
+    /// Restart this Node after a `crash`, from the state it left on disk.
+    #[cfg(msim)]
+    pub fn restart(&self) {
+        info!(name =% self.name().concise(), "restarting crashed in-memory node");
+        if let Some(container) = self.container.lock().unwrap().as_ref() {
+            container.restart();
+        }
+    }
+
+    /// Id of the simulator node this Node runs in, `None` if it is stopped.
+    #[cfg(msim)]
+    pub fn sim_node_id(&self) -> Option<sui_simulator::task::NodeId> {
+        self.container
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|c| c.node_id())
+    }
+
     /// If this Node is currently running
     pub fn is_running(&self) -> bool {
         self.container
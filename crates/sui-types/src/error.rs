@@ -506,6 +506,16 @@ pub enum SuiError {
 pub enum VMMVerifierErrorSubStatusCode {
     MULTIPLE_RETURN_VALUES_NOT_ALLOWED = 0,
     INVALID_OBJECT_CREATION = 1,
+    VERIFICATION_TIMEOUT = 2,
+    /// Only reported as a warning by tooling, never fails verification
+    UNREACHABLE_CODE = 3,
+    UID_WRITTEN_THROUGH_REFERENCE = 4,
+    /// Only reported as a warning by tooling, never fails verification
+    UID_RETURNED = 5,
+    /// Only reported as a warning by tooling, never fails verification
+    UID_ADDED_TO_VECTOR = 6,
+    /// Only reported as a warning by tooling, never fails verification
+    UID_EMITTED_IN_EVENT = 7,
 }
 
 #[repr(u64)]
@@ -0,0 +1,17 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use move_binary_format::file_format::CompiledModule;
+use std::collections::BTreeMap;
+
+fuzz_target!(|bytes: &[u8]| {
+    // Only the bounds checks of the deserializer run first, so that the Sui verifiers see the
+    // malformed modules the Move bytecode verifier would otherwise reject: none of them may
+    // panic on those, whatever order the verifiers end up running in.
+    let Ok(module) = CompiledModule::deserialize(bytes) else {
+        return;
+    };
+    let _ = sui_verifier::verifier::verify_module(&module, &BTreeMap::new());
+});
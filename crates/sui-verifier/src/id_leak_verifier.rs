@@ -13,33 +13,48 @@
 //! 3. Added to a vector
 //! 4. Passed to a function cal::;
 use move_binary_format::{
+    access::ModuleAccess,
     binary_views::{BinaryIndexedView, FunctionView},
+    control_flow_graph::ControlFlowGraph,
     errors::PartialVMError,
     file_format::{
         Bytecode, CodeOffset, CompiledModule, FunctionDefinitionIndex, FunctionHandle, LocalIndex,
         StructDefinition, StructFieldInformation,
     },
 };
+use move_bytecode_source_map::source_map::SourceMap;
 use move_bytecode_verifier::{
     absint::{AbstractDomain, AbstractInterpreter, JoinResult, TransferFunctions},
-    meter::Meter,
+    meter::{DummyMeter, Meter},
 };
+use move_command_line_common::files::FileHash;
 use move_core_types::{
     account_address::AccountAddress, ident_str, identifier::IdentStr, vm_status::StatusCode,
 };
-use std::{collections::BTreeMap, error::Error};
+use serde::{Serialize, Serializer};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    error::Error,
+    fmt,
+    time::{Duration, Instant},
+};
 use sui_types::{
     clock::CLOCK_MODULE_NAME,
-    error::{ExecutionError, VMMVerifierErrorSubStatusCode},
+    error::{ExecutionError, ExecutionErrorKind, VMMVerifierErrorSubStatusCode},
     id::OBJECT_MODULE_NAME,
     sui_system_state::SUI_SYSTEM_MODULE_NAME,
     SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_ADDRESS,
 };
+use tracing::{trace, trace_span};
 
-use crate::{verification_failure, TEST_SCENARIO_MODULE_NAME};
+use crate::{
+    sarif::{self, SarifLog},
+    TEST_SCENARIO_MODULE_NAME,
+};
 
+/// What the id_leak analysis knows about a value, as recorded in a `TraceStep`
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-enum AbstractValue {
+pub enum AbstractValue {
     Fresh,
     Other,
 }
@@ -70,11 +85,18 @@ const SUI_CLOCK_CREATE: FunctionIdent = (
     CLOCK_MODULE_NAME,
     ident_str!("create"),
 );
+const EVENT_EMIT: FunctionIdent = (
+    &SUI_FRAMEWORK_ADDRESS,
+    ident_str!("event"),
+    ident_str!("emit"),
+);
 const FRESH_ID_FUNCTIONS: &[FunctionIdent] = &[OBJECT_NEW, OBJECT_NEW_UID_FROM_HASH, TS_NEW_OBJECT];
 const FUNCTIONS_TO_SKIP: &[FunctionIdent] = &[SUI_SYSTEM_CREATE, SUI_CLOCK_CREATE];
 
 impl AbstractValue {
-    pub fn join(&self, value: &AbstractValue) -> AbstractValue {
+    /// The least upper bound of two values, where `Other` is the top of the lattice. It must stay
+    /// idempotent, commutative and associative for the analysis to reach a fixpoint.
+    pub(crate) fn join(&self, value: &AbstractValue) -> AbstractValue {
         if self == value {
             *value
         } else {
@@ -83,60 +105,762 @@ impl AbstractValue {
     }
 }
 
+/// Controls how ID leak verification failures are surfaced to the caller.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum VerifierMode {
+    /// Reject the module on the first failure. This is the only mode used when publishing.
+    #[default]
+    Enforce,
+    /// Verify every function and report the failures as warnings, without rejecting the module.
+    Warn,
+}
+
+/// Where a UID leaks to, as the reasons a function can be rejected by this verifier and the
+/// warnings it reports in `VerifierMode::Warn`. Each kind is reported with its own
+/// `VMMVerifierErrorSubStatusCode`, which lets tooling tell them apart.
+///
+/// A UID returned by any function but those creating fresh ones is not fresh, and neither is one
+/// taken back out of a vector, so a fresh UID leaking through a return value or a vector cannot be
+/// used to create an object anymore. Those leaks are only reported as warnings.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum IdLeakKind {
+    /// A fresh UID is returned to the caller. Never rejects a function.
+    Return,
+    /// A fresh UID was written through a mutable reference, e.g. into a struct field. UID does
+    /// not have drop, so the Move bytecode verifier rejects this first.
+    Reference,
+    /// An object was packed with a UID that was not freshly created.
+    Struct,
+    /// A fresh UID is added to a vector. Never rejects a function.
+    Vector,
+    /// A function creating a fresh UID is called through a handle returning more than one value.
+    FunctionCall,
+    /// A fresh UID is emitted as an event. `sui::event::emit` requires copy and drop, which UID
+    /// does not have, so the Move bytecode verifier rejects this first. Never rejects a function.
+    Event,
+    /// A basic block cannot be reached from the entry of its function. The analysis never visits
+    /// it, so a leak in it goes unreported, but neither can it run. Never rejects a function.
+    UnreachableCode,
+}
+
+impl IdLeakKind {
+    pub fn sub_status(self) -> VMMVerifierErrorSubStatusCode {
+        match self {
+            IdLeakKind::Return => VMMVerifierErrorSubStatusCode::UID_RETURNED,
+            IdLeakKind::Reference => VMMVerifierErrorSubStatusCode::UID_WRITTEN_THROUGH_REFERENCE,
+            IdLeakKind::Struct => VMMVerifierErrorSubStatusCode::INVALID_OBJECT_CREATION,
+            IdLeakKind::Vector => VMMVerifierErrorSubStatusCode::UID_ADDED_TO_VECTOR,
+            IdLeakKind::FunctionCall => {
+                VMMVerifierErrorSubStatusCode::MULTIPLE_RETURN_VALUES_NOT_ALLOWED
+            }
+            IdLeakKind::Event => VMMVerifierErrorSubStatusCode::UID_EMITTED_IN_EVENT,
+            IdLeakKind::UnreachableCode => VMMVerifierErrorSubStatusCode::UNREACHABLE_CODE,
+        }
+    }
+
+    /// Whether `verify_module` rejects a function with a finding of this kind
+    pub fn rejects(self) -> bool {
+        matches!(
+            self,
+            IdLeakKind::Reference | IdLeakKind::Struct | IdLeakKind::FunctionCall
+        )
+    }
+
+    pub fn from_sub_status(sub_status: u64) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.sub_status() as u64 == sub_status)
+    }
+
+    /// Every kind, in the order of the rules of the SARIF logs of `VerificationReport::to_sarif`
+    pub const ALL: [IdLeakKind; 7] = [
+        IdLeakKind::Struct,
+        IdLeakKind::FunctionCall,
+        IdLeakKind::UnreachableCode,
+        IdLeakKind::Reference,
+        IdLeakKind::Return,
+        IdLeakKind::Vector,
+        IdLeakKind::Event,
+    ];
+
+    /// Identifier of the kind as a SARIF rule, stable across releases
+    pub fn rule_id(self) -> &'static str {
+        match self {
+            IdLeakKind::Return => "id-leak/return",
+            IdLeakKind::Reference => "id-leak/written-through-reference",
+            IdLeakKind::Struct => "id-leak/invalid-object-creation",
+            IdLeakKind::Vector => "id-leak/vector",
+            IdLeakKind::FunctionCall => "id-leak/multiple-return-values",
+            IdLeakKind::Event => "id-leak/event",
+            IdLeakKind::UnreachableCode => "id-leak/unreachable-code",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            IdLeakKind::Return => "Fresh UID returned to the caller",
+            IdLeakKind::Reference => "Fresh UID written through a mutable reference",
+            IdLeakKind::Struct => "Object packed with a UID that is not fresh",
+            IdLeakKind::Vector => "Fresh UID added to a vector",
+            IdLeakKind::FunctionCall => {
+                "Function creating a fresh UID called as returning more than one value"
+            }
+            IdLeakKind::Event => "Fresh UID emitted as an event",
+            IdLeakKind::UnreachableCode => "Code unreachable from the entry of its function",
+        }
+    }
+
+    fn error(self, message: String) -> PartialVMError {
+        PartialVMError::new(StatusCode::UNKNOWN_VERIFICATION_ERROR)
+            .with_message(message)
+            .with_sub_status(self.sub_status() as u64)
+    }
+}
+
+/// The source of the `ExecutionError`s of this verifier, which keeps the sub-status of a failure,
+/// and so its `IdLeakKind`, alongside its message.
+pub struct IdLeakError {
+    pub message: String,
+    /// The `VMMVerifierErrorSubStatusCode` of the failure, unset for internal errors
+    pub sub_status: Option<u64>,
+}
+
+impl IdLeakError {
+    /// The `IdLeakError` `err` was built from, if it is a failure of this verifier
+    pub fn of(err: &ExecutionError) -> Option<&IdLeakError> {
+        err.source().as_ref()?.downcast_ref()
+    }
+
+    /// The kind of leak the failure is about, unset for timeouts and internal errors
+    pub fn kind(&self) -> Option<IdLeakKind> {
+        self.sub_status.and_then(IdLeakKind::from_sub_status)
+    }
+}
+
+/// Only the message, quoted, as the sources of the errors of the other verifiers are strings and
+/// execution errors print their source with `Debug`
+impl fmt::Debug for IdLeakError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.message, f)
+    }
+}
+
+impl fmt::Display for IdLeakError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for IdLeakError {}
+
+fn id_leak_failure(message: String, sub_status: Option<u64>) -> ExecutionError {
+    ExecutionError::new_with_source(
+        ExecutionErrorKind::SuiMoveVerificationError,
+        IdLeakError {
+            message,
+            sub_status,
+        },
+    )
+}
+
+/// A function that `verify_module` would reject, with a leak it does not reject, or with
+/// unreachable code, as reported by `VerifierMode::Warn`.
+#[derive(Debug)]
+pub struct IdLeakWarning {
+    pub kind: IdLeakKind,
+    pub error: ExecutionError,
+    /// The function the warning is about
+    pub function: FunctionDefinitionIndex,
+    /// The bytecode the warning is about, unset if the function was rejected outside of any
+    pub offset: Option<CodeOffset>,
+}
+
+/// The Move source a module was compiled from, as emitted by the compiler alongside it.
+pub struct ModuleSource<'a> {
+    pub source_map: &'a SourceMap,
+    /// Path and contents of the source files referenced by `source_map`
+    pub files: &'a BTreeMap<FileHash, (String, String)>,
+}
+
+impl ModuleSource<'_> {
+    /// The `path:line` of the source of the bytecode at `offset` in function `index`, if known
+    fn location(&self, index: FunctionDefinitionIndex, offset: CodeOffset) -> Option<String> {
+        let (path, line) = self.file_line(index, offset)?;
+        Some(format!("{path}:{line}"))
+    }
+
+    /// The path of the source file of the bytecode at `offset` in function `index`, and its line
+    /// in the file, starting from 1
+    fn file_line(
+        &self,
+        index: FunctionDefinitionIndex,
+        offset: CodeOffset,
+    ) -> Option<(&str, usize)> {
+        let loc = self.source_map.get_code_location(index, offset).ok()?;
+        let (path, contents) = self.files.get(&loc.file_hash())?;
+        let line = contents.get(..loc.start() as usize)?.matches('\n').count() + 1;
+        Some((path.as_str(), line))
+    }
+}
+
+/// Verify `module`, failing with a timeout error instead if it is still being verified at
+/// `deadline`. The deadline is checked between functions and at the end of every basic block. As
+/// it depends on wall-clock time, a deadline must only be used where rejecting a module does not
+/// need to be deterministic, e.g. when a validator first receives a package, never in execution.
+///
+/// If the `source` of the module is given, a failure also points at the source file and line of
+/// the offending bytecode. It only changes the error message, so it is meant for developer
+/// tooling, execution never has the source.
 pub fn verify_module(
     module: &CompiledModule,
     meter: &mut impl Meter,
+    deadline: Option<Instant>,
+    source: Option<&ModuleSource>,
 ) -> Result<(), ExecutionError> {
-    verify_id_leak(module, meter)
+    verify_id_leak(module, meter, deadline, source)
 }
 
-fn verify_id_leak(module: &CompiledModule, meter: &mut impl Meter) -> Result<(), ExecutionError> {
+/// Same as `verify_module`, but in `VerifierMode::Warn` the failures of every function are
+/// collected and returned as warnings instead of failing. Meant for linting, never for execution.
+/// Internal errors of the verifier are still returned as an error in both modes.
+///
+/// `VerifierMode::Warn` also warns about every basic block unreachable from the entry of its
+/// function, as `IdLeakKind::UnreachableCode`, since leaks in those blocks are not analyzed.
+pub fn verify_module_with_mode(
+    module: &CompiledModule,
+    meter: &mut impl Meter,
+    mode: VerifierMode,
+) -> Result<Vec<IdLeakWarning>, ExecutionError> {
+    if mode == VerifierMode::Enforce {
+        return verify_id_leak(module, meter, None, None).map(|()| vec![]);
+    }
+
     let binary_view = BinaryIndexedView::Module(module);
-    for (index, func_def) in module.function_defs.iter().enumerate() {
-        let code = match func_def.code.as_ref() {
-            Some(code) => code,
-            None => continue,
-        };
-        let handle = binary_view.function_handle_at(func_def.function);
-        let func_view =
-            FunctionView::function(module, FunctionDefinitionIndex(index as u16), code, handle);
-        let initial_state = AbstractState::new(&func_view);
-        let mut verifier = IDLeakAnalysis::new(&binary_view, &func_view);
-        let function_to_verify = verifier.cur_function();
-        if FUNCTIONS_TO_SKIP
+    let mut warnings = vec![];
+    for index in 0..module.function_defs.len() {
+        let index = FunctionDefinitionIndex(index as u16);
+        let (result, _, _, leaks) =
+            verify_function_instrumented(module, &binary_view, index, meter, None, false, false);
+        if let Err(err) = result {
+            let kind = err.error.sub_status().and_then(IdLeakKind::from_sub_status);
+            let offset = err.offset;
+            let error = to_verification_failure(module, &binary_view, index, err, None);
+            match kind {
+                Some(kind) => warnings.push(IdLeakWarning {
+                    kind,
+                    error,
+                    function: index,
+                    offset,
+                }),
+                None => return Err(error),
+            }
+        }
+        for (offset, (kind, message)) in leaks {
+            let err = FunctionError {
+                error: kind.error(message),
+                offset: Some(offset),
+            };
+            warnings.push(IdLeakWarning {
+                kind,
+                error: to_verification_failure(module, &binary_view, index, err, None),
+                function: index,
+                offset: Some(offset),
+            });
+        }
+        for offset in unreachable_blocks(module, &binary_view, index) {
+            let err = FunctionError {
+                error: IdLeakKind::UnreachableCode
+                    .error(format!("Unreachable code at offset {offset}.")),
+                offset: Some(offset),
+            };
+            warnings.push(IdLeakWarning {
+                kind: IdLeakKind::UnreachableCode,
+                error: to_verification_failure(module, &binary_view, index, err, None),
+                function: index,
+                offset: Some(offset),
+            });
+        }
+    }
+    Ok(warnings)
+}
+
+/// The first offset of every basic block of function `index` which no path from the entry of the
+/// function reaches, and which the abstract interpreter therefore skips
+fn unreachable_blocks(
+    module: &CompiledModule,
+    binary_view: &BinaryIndexedView,
+    index: FunctionDefinitionIndex,
+) -> Vec<CodeOffset> {
+    let func_def = module.function_def_at(index);
+    let Some(code) = func_def.code.as_ref() else {
+        return vec![];
+    };
+    let handle = binary_view.function_handle_at(func_def.function);
+    let func_view = FunctionView::function(module, index, code, handle);
+    let cfg = func_view.cfg();
+    let reachable: BTreeSet<_> = cfg
+        .reachable_from(cfg.entry_block_id())
+        .into_iter()
+        .collect();
+    cfg.blocks()
+        .into_iter()
+        .filter(|block_id| !reachable.contains(block_id))
+        .map(|block_id| cfg.block_start(block_id))
+        .collect()
+}
+
+/// The findings of `verify_modules_report` for a set of modules, e.g. all the modules of a
+/// package, meant to be serialized as a single artifact for CI.
+#[derive(Debug, Default, Serialize)]
+pub struct VerificationReport {
+    pub modules: Vec<ModuleReport>,
+    /// Number of findings of each kind, across all modules
+    pub counts: BTreeMap<IdLeakKind, usize>,
+}
+
+impl VerificationReport {
+    /// Whether `verify_module` accepts every module of the report, warnings about unreachable code
+    /// aside
+    pub fn is_clean(&self) -> bool {
+        self.modules.iter().all(|module| {
+            module
+                .findings
+                .iter()
+                .all(|finding| !finding.kind.rejects())
+                && module.error.is_none()
+        })
+    }
+
+    /// The report as a SARIF log, for code scanning tools. Each finding is a result of the rule of
+    /// its kind, at an error level if `verify_module` rejects it and a warning level otherwise,
+    /// located at its function and bytecode offset. If `sources` has the source of its module,
+    /// keyed by module id as in `ModuleReport::module`, the result also points at the source line.
+    /// Modules the verifier failed on are reported as notifications of a failed invocation.
+    pub fn to_sarif(&self, sources: &BTreeMap<String, ModuleSource>) -> SarifLog {
+        let kinds = IdLeakKind::ALL;
+        let rules = kinds
             .iter()
-            .any(|to_skip| function_to_verify == *to_skip)
-        {
+            .map(|kind| sarif::ReportingDescriptor {
+                id: kind.rule_id().to_string(),
+                short_description: sarif::Message::new(kind.description()),
+                default_configuration: sarif::ReportingConfiguration {
+                    level: sarif_level(*kind),
+                },
+            })
+            .collect();
+        let mut results = vec![];
+        let mut notifications = vec![];
+        for module in &self.modules {
+            let source = sources.get(&module.module);
+            for finding in &module.findings {
+                let physical_location = source
+                    .zip(finding.offset)
+                    .and_then(|(source, offset)| source.file_line(finding.function_index, offset))
+                    .map(|(path, line)| sarif::PhysicalLocation::new(path, line));
+                results.push(sarif::SarifResult {
+                    rule_id: finding.kind.rule_id().to_string(),
+                    rule_index: kinds.iter().position(|kind| *kind == finding.kind).unwrap(),
+                    level: sarif_level(finding.kind),
+                    message: sarif::Message::new(&finding.message),
+                    locations: vec![sarif::Location {
+                        physical_location,
+                        logical_locations: vec![sarif::LogicalLocation::function(format!(
+                            "{}::{}",
+                            module.module, finding.function
+                        ))],
+                        properties: finding
+                            .offset
+                            .map(|code_offset| sarif::LocationProperties { code_offset }),
+                    }],
+                });
+            }
+            if let Some(error) = &module.error {
+                notifications.push(sarif::Notification {
+                    level: sarif::Level::Error,
+                    message: sarif::Message::new(error),
+                    locations: vec![sarif::Location {
+                        physical_location: None,
+                        logical_locations: vec![sarif::LogicalLocation::module(&module.module)],
+                        properties: None,
+                    }],
+                });
+            }
+        }
+        SarifLog::new(sarif::Run {
+            tool: sarif::Tool {
+                driver: sarif::ToolComponent {
+                    name: SARIF_TOOL_NAME.to_string(),
+                    rules,
+                },
+            },
+            invocations: vec![sarif::Invocation {
+                execution_successful: notifications.is_empty(),
+                tool_execution_notifications: notifications,
+            }],
+            results,
+        })
+    }
+}
+
+/// Name of this verifier in SARIF logs
+const SARIF_TOOL_NAME: &str = "sui-id-leak-verifier";
+
+fn sarif_level(kind: IdLeakKind) -> sarif::Level {
+    if kind.rejects() {
+        sarif::Level::Error
+    } else {
+        sarif::Level::Warning
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModuleReport {
+    /// The module's id, as `address::name`
+    pub module: String,
+    pub findings: Vec<IdLeakFinding>,
+    /// Set if the verifier failed on the module for a reason other than an id leak, the
+    /// findings of the module are then incomplete
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IdLeakFinding {
+    pub kind: IdLeakKind,
+    pub message: String,
+    /// Name of the function the finding is about
+    pub function: String,
+    /// The bytecode the finding is about in the function, if any
+    pub offset: Option<CodeOffset>,
+    #[serde(skip)]
+    function_index: FunctionDefinitionIndex,
+}
+
+/// Verify every module in `modules` in `VerifierMode::Warn`, collecting the findings of all of
+/// them rather than stopping at the first one. Unlike `verify_module` this never fails and is not
+/// metered, it is meant for tooling and never for execution.
+///
+/// With `skip_framework`, modules at `SUI_FRAMEWORK_ADDRESS`, which are verified when the
+/// framework is built, are left out of the report instead of being verified again. Only the
+/// module's own address counts, modules which call into the framework are always verified.
+pub fn verify_modules_report(
+    modules: &[CompiledModule],
+    skip_framework: bool,
+) -> VerificationReport {
+    let mut report = VerificationReport::default();
+    for module in modules {
+        if skip_framework && *module.address() == SUI_FRAMEWORK_ADDRESS {
             continue;
         }
-        verifier
-            .analyze_function(initial_state, &func_view, meter)
-            .map_err(|err| {
-                if let Some(message) = err.source().as_ref() {
-                    let function_name = binary_view
-                        .identifier_at(binary_view.function_handle_at(func_def.function).name);
-                    let module_name = module.self_id();
-                    verification_failure(format!(
-                        "{} Found in {module_name}::{function_name}",
-                        message
-                    ))
-                } else {
-                    verification_failure(err.to_string())
+        let (findings, error) =
+            match verify_module_with_mode(module, &mut DummyMeter, VerifierMode::Warn) {
+                Ok(warnings) => (warnings, None),
+                Err(err) => (vec![], Some(error_message(&err))),
+            };
+        let findings: Vec<_> = findings
+            .into_iter()
+            .map(|warning| {
+                let handle = module.function_def_at(warning.function).function;
+                IdLeakFinding {
+                    kind: warning.kind,
+                    message: error_message(&warning.error),
+                    function: module
+                        .identifier_at(module.function_handle_at(handle).name)
+                        .to_string(),
+                    offset: warning.offset,
+                    function_index: warning.function,
                 }
-            })?;
+            })
+            .collect();
+        for finding in &findings {
+            *report.counts.entry(finding.kind).or_default() += 1;
+        }
+        report.modules.push(ModuleReport {
+            module: module.self_id().to_string(),
+            findings,
+            error,
+        });
+    }
+    report
+}
+
+/// The message of a failure, which is kept as the source of the error
+fn error_message(err: &ExecutionError) -> String {
+    err.source()
+        .as_ref()
+        .map(|source| source.to_string())
+        .unwrap_or_else(|| err.to_string())
+}
+
+/// The abstract state and stack right after the bytecode at `offset` was interpreted, as recorded
+/// by `trace_function`.
+#[derive(Clone, Debug)]
+pub struct TraceStep {
+    pub offset: CodeOffset,
+    pub state: AbstractState,
+    pub stack: Vec<AbstractValue>,
+}
+
+/// Run the id_leak analysis of a single function, recording a `TraceStep` for every bytecode it
+/// interprets, for explaining why the function is rejected. Blocks are revisited until the
+/// analysis reaches a fixpoint, so an offset can appear more than once. If the function is
+/// rejected, the last step is at the offending bytecode.
+///
+/// The same steps are also logged at trace level, within an `id_leak` span per function and an
+/// `id_leak_bytecode` span per offset, for every function verified, e.g. with
+/// `RUST_LOG=sui_verifier=trace`.
+pub fn trace_function(
+    module: &CompiledModule,
+    index: FunctionDefinitionIndex,
+    meter: &mut impl Meter,
+) -> (Vec<TraceStep>, Result<(), ExecutionError>) {
+    let binary_view = BinaryIndexedView::Module(module);
+    let (result, trace, _, _) =
+        verify_function_instrumented(module, &binary_view, index, meter, None, true, false);
+    let result =
+        result.map_err(|err| to_verification_failure(module, &binary_view, index, err, None));
+    (trace, result)
+}
+
+/// The kinds of bytecode `VerifierStats` breaks the verification time down by
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum BytecodeCategory {
+    Call,
+    CallGeneric,
+    /// `Pack` and `PackGeneric`
+    Pack,
+    /// `Unpack` and `UnpackGeneric`
+    Unpack,
+    /// Every `Vec*` bytecode
+    Vector,
+    Other,
+}
+
+impl BytecodeCategory {
+    pub fn of(bytecode: &Bytecode) -> Self {
+        match bytecode {
+            Bytecode::Call(_) => BytecodeCategory::Call,
+            Bytecode::CallGeneric(_) => BytecodeCategory::CallGeneric,
+            Bytecode::Pack(_) | Bytecode::PackGeneric(_) => BytecodeCategory::Pack,
+            Bytecode::Unpack(_) | Bytecode::UnpackGeneric(_) => BytecodeCategory::Unpack,
+            Bytecode::VecPack(..)
+            | Bytecode::VecLen(_)
+            | Bytecode::VecImmBorrow(_)
+            | Bytecode::VecMutBorrow(_)
+            | Bytecode::VecPushBack(_)
+            | Bytecode::VecPopBack(_)
+            | Bytecode::VecUnpack(..)
+            | Bytecode::VecSwap(_) => BytecodeCategory::Vector,
+            _ => BytecodeCategory::Other,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize)]
+pub struct CategoryStats {
+    /// Number of bytecodes of the category interpreted, counting every revisit of a block
+    pub count: u64,
+    pub total_time: Duration,
+}
+
+/// Where the time of verifying a module went, by category of bytecode, as returned by
+/// `verify_module_with_stats`. Only the interpretation of bytecodes is timed, not the joins of
+/// states between blocks.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct VerifierStats {
+    pub by_category: BTreeMap<BytecodeCategory, CategoryStats>,
+    /// Number of blocks interpreted in each function until the analysis reached a fixpoint. A
+    /// block is interpreted again every time a join changes the state at its start, so functions
+    /// with loops whose states take many joins to settle stand out against their number of blocks
+    #[serde(serialize_with = "serialize_by_function_index")]
+    pub block_visits: BTreeMap<FunctionDefinitionIndex, u64>,
+}
+
+fn serialize_by_function_index<S: Serializer>(
+    map: &BTreeMap<FunctionDefinitionIndex, u64>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(map.iter().map(|(index, count)| (index.0, count)))
+}
+
+impl VerifierStats {
+    fn record(&mut self, bytecode: &Bytecode, elapsed: Duration) {
+        let stats = self
+            .by_category
+            .entry(BytecodeCategory::of(bytecode))
+            .or_default();
+        stats.count += 1;
+        stats.total_time += elapsed;
+    }
+
+    fn record_block_visit(&mut self, function: FunctionDefinitionIndex) {
+        *self.block_visits.entry(function).or_default() += 1;
+    }
+
+    fn add(&mut self, other: &VerifierStats) {
+        for (category, other) in &other.by_category {
+            let stats = self.by_category.entry(*category).or_default();
+            stats.count += other.count;
+            stats.total_time += other.total_time;
+        }
+        for (function, visits) in &other.block_visits {
+            *self.block_visits.entry(*function).or_default() += visits;
+        }
+    }
+}
+
+/// Same as `verify_module` without a deadline, but also timing the interpretation of every
+/// bytecode and counting the blocks interpreted in every function, for profiling the verifier on
+/// large modules. The stats cover the functions verified up to the first failure, if any. Timing
+/// every bytecode slows verification down, so this is meant for tooling and never for execution.
+pub fn verify_module_with_stats(
+    module: &CompiledModule,
+    meter: &mut impl Meter,
+) -> (Result<(), ExecutionError>, VerifierStats) {
+    let binary_view = BinaryIndexedView::Module(module);
+    let mut stats = VerifierStats::default();
+    for index in 0..module.function_defs.len() {
+        let index = FunctionDefinitionIndex(index as u16);
+        let (result, _, function_stats, _) =
+            verify_function_instrumented(module, &binary_view, index, meter, None, false, true);
+        stats.add(&function_stats.unwrap_or_default());
+        if let Err(err) = result {
+            let err = to_verification_failure(module, &binary_view, index, err, None);
+            return (Err(err), stats);
+        }
+    }
+    (Ok(()), stats)
+}
+
+fn verify_id_leak(
+    module: &CompiledModule,
+    meter: &mut impl Meter,
+    deadline: Option<Instant>,
+    source: Option<&ModuleSource>,
+) -> Result<(), ExecutionError> {
+    let binary_view = BinaryIndexedView::Module(module);
+    for index in 0..module.function_defs.len() {
+        if is_past(deadline) {
+            return Err(id_leak_failure(
+                format!("Verification of {} timed out", module.self_id()),
+                Some(VMMVerifierErrorSubStatusCode::VERIFICATION_TIMEOUT as u64),
+            ));
+        }
+        let index = FunctionDefinitionIndex(index as u16);
+        verify_function(module, &binary_view, index, meter, deadline)
+            .map_err(|err| to_verification_failure(module, &binary_view, index, err, source))?;
     }
 
     Ok(())
 }
 
+fn verify_function(
+    module: &CompiledModule,
+    binary_view: &BinaryIndexedView,
+    index: FunctionDefinitionIndex,
+    meter: &mut impl Meter,
+    deadline: Option<Instant>,
+) -> Result<(), FunctionError> {
+    verify_function_instrumented(module, binary_view, index, meter, deadline, false, false).0
+}
+
+/// Verify a function, also returning its trace if `trace` is set (and an empty one otherwise), its
+/// `VerifierStats` if `stats` is set, and the kind and message of the leaks it does not reject by
+/// offset.
+fn verify_function_instrumented(
+    module: &CompiledModule,
+    binary_view: &BinaryIndexedView,
+    index: FunctionDefinitionIndex,
+    meter: &mut impl Meter,
+    deadline: Option<Instant>,
+    trace: bool,
+    stats: bool,
+) -> (
+    Result<(), FunctionError>,
+    Vec<TraceStep>,
+    Option<VerifierStats>,
+    BTreeMap<CodeOffset, (IdLeakKind, String)>,
+) {
+    let func_def = module.function_def_at(index);
+    let code = match func_def.code.as_ref() {
+        Some(code) => code,
+        None => return (Ok(()), vec![], None, BTreeMap::new()),
+    };
+    let handle = binary_view.function_handle_at(func_def.function);
+    let func_view = FunctionView::function(module, index, code, handle);
+    let initial_state = AbstractState::new(&func_view);
+    let mut verifier = IDLeakAnalysis::new(binary_view, &func_view, deadline, trace, stats);
+    let function_to_verify = verifier.cur_function();
+    if FUNCTIONS_TO_SKIP
+        .iter()
+        .any(|to_skip| function_to_verify == *to_skip)
+    {
+        return (Ok(()), vec![], None, BTreeMap::new());
+    }
+    let (address, module_name, function_name) = function_to_verify;
+    let _span = trace_span!(
+        "id_leak",
+        address = %address,
+        module = %module_name,
+        function = %function_name
+    )
+    .entered();
+    let result = verifier
+        .analyze_function(initial_state, &func_view, meter)
+        .map_err(|error| FunctionError {
+            error,
+            offset: verifier.failed_at,
+        });
+    (
+        result,
+        verifier.trace.unwrap_or_default(),
+        verifier.stats,
+        verifier.leaks,
+    )
+}
+
+/// Why a function was rejected
+struct FunctionError {
+    error: PartialVMError,
+    /// The bytecode being interpreted when the function was rejected, unset if it was rejected
+    /// outside of any bytecode, e.g. by the meter
+    offset: Option<CodeOffset>,
+}
+
+fn to_verification_failure(
+    module: &CompiledModule,
+    binary_view: &BinaryIndexedView,
+    index: FunctionDefinitionIndex,
+    err: FunctionError,
+    source: Option<&ModuleSource>,
+) -> ExecutionError {
+    let location = source
+        .zip(err.offset)
+        .and_then(|(source, offset)| source.location(index, offset))
+        .map(|location| format!(" at {location}"))
+        .unwrap_or_default();
+    if let Some(message) = err.error.source().as_ref() {
+        let func_def = module.function_def_at(index);
+        let function_name =
+            binary_view.identifier_at(binary_view.function_handle_at(func_def.function).name);
+        let module_name = module.self_id();
+        id_leak_failure(
+            format!("{message} Found in {module_name}::{function_name}{location}"),
+            err.error.sub_status(),
+        )
+    } else {
+        id_leak_failure(format!("{}{location}", err.error), err.error.sub_status())
+    }
+}
+
+/// The abstract values of the locals of a function, as recorded in a `TraceStep`
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub(crate) struct AbstractState {
+pub struct AbstractState {
     locals: BTreeMap<LocalIndex, AbstractValue>,
 }
 
 impl AbstractState {
     /// create a new abstract state
-    pub fn new(function_view: &FunctionView) -> Self {
+    pub(crate) fn new(function_view: &FunctionView) -> Self {
         let mut state = AbstractState {
             locals: BTreeMap::new(),
         };
@@ -149,6 +873,17 @@ impl AbstractState {
 
         state
     }
+
+    /// A state in which each local holds the given value
+    #[cfg(test)]
+    pub(crate) fn from_locals(locals: BTreeMap<LocalIndex, AbstractValue>) -> Self {
+        AbstractState { locals }
+    }
+
+    /// The abstract value of every local that currently holds a value
+    pub fn locals(&self) -> &BTreeMap<LocalIndex, AbstractValue> {
+        &self.locals
+    }
 }
 
 impl AbstractDomain for AbstractState {
@@ -161,8 +896,11 @@ impl AbstractDomain for AbstractState {
         let mut changed = false;
         for (local, value) in &state.locals {
             let old_value = *self.locals.get(local).unwrap_or(&AbstractValue::Other);
-            changed |= *value != old_value;
-            self.locals.insert(*local, value.join(&old_value));
+            let new_value = value.join(&old_value);
+            // Only report a change when the join moved up the lattice, reporting one for a value
+            // that was already absorbed would revisit loops forever
+            changed |= new_value != old_value;
+            self.locals.insert(*local, new_value);
         }
         if changed {
             Ok(JoinResult::Changed)
@@ -176,20 +914,75 @@ struct IDLeakAnalysis<'a> {
     binary_view: &'a BinaryIndexedView<'a>,
     function_view: &'a FunctionView<'a>,
     stack: Vec<AbstractValue>,
+    /// Checked at the end of every block, see `verify_module`
+    deadline: Option<Instant>,
+    /// Steps recorded after every bytecode, only when tracing
+    trace: Option<Vec<TraceStep>>,
+    /// Time spent on every bytecode, only when collecting stats
+    stats: Option<VerifierStats>,
+    /// Whether the next bytecode interpreted starts a block
+    at_block_start: bool,
+    /// The bytecode the analysis failed at, if it did
+    failed_at: Option<CodeOffset>,
+    /// Leaks that do not reject the function, by the offset of the bytecode leaking the UID
+    leaks: BTreeMap<CodeOffset, (IdLeakKind, String)>,
 }
 
 impl<'a> IDLeakAnalysis<'a> {
-    fn new(binary_view: &'a BinaryIndexedView<'a>, function_view: &'a FunctionView<'a>) -> Self {
+    fn new(
+        binary_view: &'a BinaryIndexedView<'a>,
+        function_view: &'a FunctionView<'a>,
+        deadline: Option<Instant>,
+        trace: bool,
+        stats: bool,
+    ) -> Self {
         Self {
             binary_view,
             function_view,
             stack: vec![],
+            deadline,
+            trace: trace.then(Vec::new),
+            stats: stats.then(VerifierStats::default),
+            at_block_start: true,
+            failed_at: None,
+            leaks: BTreeMap::new(),
+        }
+    }
+
+    /// Record a leak of `kind` of a fresh UID at `offset`, which does not reject the function
+    fn report_leak(&mut self, offset: CodeOffset, kind: IdLeakKind, leak: &str) {
+        debug_assert!(!kind.rejects());
+        let (cur_package, cur_module, cur_function) = self.cur_function();
+        let message = format!(
+            "A newly created UID is {leak} in {cur_package}::{cur_module}::{cur_function}."
+        );
+        self.leaks.insert(offset, (kind, message));
+    }
+
+    /// Whether one of the top `n` values of the stack is a fresh UID
+    fn any_fresh_in_top(&self, n: usize) -> bool {
+        self.stack
+            .iter()
+            .rev()
+            .take(n)
+            .any(|value| *value == AbstractValue::Fresh)
+    }
+
+    /// Pop the top of the stack. The Move bytecode verifier has already checked the stack is
+    /// balanced, so an empty stack is reported as an invariant violation rather than a panic.
+    fn pop_checked(&mut self) -> Result<AbstractValue, PartialVMError> {
+        match self.stack.pop() {
+            Some(value) => Ok(value),
+            None => Err(stack_underflow(1, 0)),
         }
     }
 
-    fn stack_popn(&mut self, n: usize) {
-        let new_len = self.stack.len() - n;
+    fn stack_popn(&mut self, n: usize) -> Result<(), PartialVMError> {
+        let Some(new_len) = self.stack.len().checked_sub(n) else {
+            return Err(stack_underflow(n, self.stack.len()));
+        };
         self.stack.drain(new_len..);
+        Ok(())
     }
 
     fn stack_pushn(&mut self, n: usize, val: AbstractValue) {
@@ -215,6 +1008,24 @@ impl<'a> IDLeakAnalysis<'a> {
     }
 }
 
+fn is_past(deadline: Option<Instant>) -> bool {
+    deadline.map_or(false, |deadline| Instant::now() >= deadline)
+}
+
+fn stack_underflow(expected: usize, len: usize) -> PartialVMError {
+    let msg = format!(
+        "Invalid stack transitions. Expected at least {expected} values on the stack, found {len}"
+    );
+    invariant_violation(msg)
+}
+
+/// An error for a state the Move bytecode verifier should have ruled out. It is reachable when
+/// this verifier runs on its own, e.g. in tooling or fuzzing, so it fails gracefully rather than
+/// panicking.
+fn invariant_violation(msg: String) -> PartialVMError {
+    PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR).with_message(msg)
+}
+
 impl<'a> TransferFunctions for IDLeakAnalysis<'a> {
     type Error = ExecutionError;
     type State = AbstractState;
@@ -227,46 +1038,99 @@ impl<'a> TransferFunctions for IDLeakAnalysis<'a> {
         last_index: CodeOffset,
         _meter: &mut impl Meter,
     ) -> Result<(), PartialVMError> {
-        execute_inner(self, state, bytecode, index)?;
-        // invariant: the stack should be empty at the end of the block
-        // If it is not, something is wrong with the implementation, so throw an invariant
-        // violation
-        if index == last_index && !self.stack.is_empty() {
-            let msg = "Invalid stack transitions. Non-zero stack size at the end of the block"
-                .to_string();
-            debug_assert!(false, "{msg}",);
-            return Err(
-                PartialVMError::new(StatusCode::VERIFIER_INVARIANT_VIOLATION).with_message(msg),
-            );
+        let result = execute_checked(self, state, bytecode, index, last_index);
+        if result.is_err() {
+            self.failed_at = Some(index);
         }
-        Ok(())
+        result
     }
 }
 
+/// Interpret `bytecode` at `index`, then check the invariants of the end of its block if it is
+/// the last one, at `last_index`
+fn execute_checked(
+    verifier: &mut IDLeakAnalysis,
+    state: &mut AbstractState,
+    bytecode: &Bytecode,
+    index: CodeOffset,
+    last_index: CodeOffset,
+) -> Result<(), PartialVMError> {
+    let _span = trace_span!("id_leak_bytecode", offset = index).entered();
+    if verifier.at_block_start {
+        let function = verifier.function_view.index().unwrap();
+        if let Some(stats) = &mut verifier.stats {
+            stats.record_block_visit(function);
+        }
+    }
+    verifier.at_block_start = index == last_index;
+    let start = verifier.stats.is_some().then(Instant::now);
+    let result = execute_inner(verifier, state, bytecode, index);
+    if let (Some(stats), Some(start)) = (&mut verifier.stats, start) {
+        stats.record(bytecode, start.elapsed());
+    }
+    // Fields are only evaluated when trace level is enabled for this module
+    trace!(
+        ?bytecode,
+        stack = ?verifier.stack,
+        locals = ?state.locals(),
+        failed = result.is_err(),
+        "interpreted bytecode"
+    );
+    if let Some(trace) = &mut verifier.trace {
+        trace.push(TraceStep {
+            offset: index,
+            state: state.clone(),
+            stack: verifier.stack.clone(),
+        });
+    }
+    result?;
+    // invariant: the stack should be empty at the end of the block
+    // If it is not, something is wrong with the implementation, so throw an invariant
+    // violation
+    if index == last_index && !verifier.stack.is_empty() {
+        return Err(invariant_violation(
+            "Invalid stack transitions. Non-zero stack size at the end of the block".to_string(),
+        ));
+    }
+    if index == last_index && is_past(verifier.deadline) {
+        return Err(PartialVMError::new(StatusCode::UNKNOWN_VERIFICATION_ERROR)
+            .with_message("Verification timed out.".to_string())
+            .with_sub_status(VMMVerifierErrorSubStatusCode::VERIFICATION_TIMEOUT as u64));
+    }
+    Ok(())
+}
+
 impl<'a> AbstractInterpreter for IDLeakAnalysis<'a> {}
 
 fn call(
     verifier: &mut IDLeakAnalysis,
     function_handle: &FunctionHandle,
+    offset: CodeOffset,
 ) -> Result<(), PartialVMError> {
     let parameters = verifier
         .binary_view
         .signature_at(function_handle.parameters);
-    verifier.stack_popn(parameters.len());
+    let function = verifier.resolve_function(function_handle);
+    if function == EVENT_EMIT && verifier.any_fresh_in_top(parameters.len()) {
+        verifier.report_leak(offset, IdLeakKind::Event, "emitted as an event");
+    }
+    verifier.stack_popn(parameters.len())?;
 
     let return_ = verifier.binary_view.signature_at(function_handle.return_);
-    let function = verifier.resolve_function(function_handle);
     if FRESH_ID_FUNCTIONS
         .iter()
         .any(|makes_fresh| function == *makes_fresh)
     {
         if return_.0.len() != 1 {
-            debug_assert!(false, "{:?} should have a single return value", function);
-            return Err(PartialVMError::new(StatusCode::UNKNOWN_VERIFICATION_ERROR)
-                .with_message("Should have a single return value".to_string())
-                .with_sub_status(
-                    VMMVerifierErrorSubStatusCode::MULTIPLE_RETURN_VALUES_NOT_ALLOWED as u64,
-                ));
+            // Only linking against the framework rejects a handle with another signature
+            debug_assert!(
+                cfg!(feature = "fuzzing"),
+                "{:?} should have a single return value",
+                function
+            );
+            return Err(
+                IdLeakKind::FunctionCall.error("Should have a single return value".to_string())
+            );
         }
         verifier.stack.push(AbstractValue::Fresh);
     } else {
@@ -282,6 +1146,9 @@ fn num_fields(struct_def: &StructDefinition) -> usize {
     }
 }
 
+/// Shared by `Pack` and `PackGeneric`: the type arguments of an instantiation, phantom or not, do
+/// not matter, as the UID is always the first field of the definition and a field of a type
+/// parameter holds an object that was checked when it was itself packed.
 fn pack(
     verifier: &mut IDLeakAnalysis,
     struct_def: &StructDefinition,
@@ -291,9 +1158,13 @@ fn pack(
     let handle = verifier
         .binary_view
         .struct_handle_at(struct_def.struct_handle);
-    let num_fields = num_fields(struct_def);
-    verifier.stack_popn(num_fields - 1);
-    let last_value = verifier.stack.pop().unwrap();
+    // The Move bytecode verifier rejects structs without fields, and packing native structs
+    let Some(other_fields) = num_fields(struct_def).checked_sub(1) else {
+        let name = verifier.binary_view.identifier_at(handle.name);
+        return Err(invariant_violation(format!("Struct {name} without fields was packed")));
+    };
+    verifier.stack_popn(other_fields)?;
+    let last_value = verifier.pop_checked()?;
     if handle.abilities.has_key() && last_value != AbstractValue::Fresh {
         let (cur_package, cur_module, cur_function) = verifier.cur_function();
         let msg = format!(
@@ -304,40 +1175,60 @@ fn pack(
             OBJECT_NEW.1, OBJECT_NEW.2, TS_NEW_OBJECT.1, TS_NEW_OBJECT.2,
         );
 
-        return Err(PartialVMError::new(StatusCode::UNKNOWN_VERIFICATION_ERROR)
-            .with_message(msg)
-            .with_sub_status(VMMVerifierErrorSubStatusCode::INVALID_OBJECT_CREATION as u64));
+        return Err(IdLeakKind::Struct.error(msg));
     }
     verifier.stack.push(AbstractValue::Other);
     Ok(())
 }
 
-fn unpack(verifier: &mut IDLeakAnalysis, struct_def: &StructDefinition) {
-    verifier.stack.pop().unwrap();
+/// A fresh UID written through a mutable reference leaks out of the function
+fn write_ref(verifier: &mut IDLeakAnalysis, value: AbstractValue) -> Result<(), PartialVMError> {
+    if value != AbstractValue::Fresh {
+        return Ok(());
+    }
+    let (cur_package, cur_module, cur_function) = verifier.cur_function();
+    Err(IdLeakKind::Reference.error(format!(
+        "Invalid write in {cur_package}::{cur_module}::{cur_function}. \
+            A newly created UID was written through a mutable reference, \
+            leaking it out of the function."
+    )))
+}
+
+fn unpack(
+    verifier: &mut IDLeakAnalysis,
+    struct_def: &StructDefinition,
+) -> Result<(), PartialVMError> {
+    verifier.pop_checked()?;
     verifier.stack_pushn(num_fields(struct_def), AbstractValue::Other);
+    Ok(())
 }
 
 fn execute_inner(
     verifier: &mut IDLeakAnalysis,
     state: &mut AbstractState,
     bytecode: &Bytecode,
-    _: CodeOffset,
+    offset: CodeOffset,
 ) -> Result<(), PartialVMError> {
     // TODO: Better diagnostics with location
     match bytecode {
         Bytecode::Pop => {
-            verifier.stack.pop().unwrap();
+            verifier.pop_checked()?;
         }
         Bytecode::CopyLoc(_local) => {
             // cannot copy a UID
             verifier.stack.push(AbstractValue::Other);
         }
         Bytecode::MoveLoc(local) => {
-            let value = state.locals.remove(local).unwrap();
+            // The Move bytecode verifier has already checked the local is available
+            let Some(value) = state.locals.remove(local) else {
+                return Err(invariant_violation(format!(
+                    "Moved local {local} which holds no value"
+                )));
+            };
             verifier.stack.push(value);
         }
         Bytecode::StLoc(local) => {
-            let value = verifier.stack.pop().unwrap();
+            let value = verifier.pop_checked()?;
             state.locals.insert(*local, value);
         }
 
@@ -355,7 +1246,7 @@ fn execute_inner(
         | Bytecode::Not
         | Bytecode::VecLen(_)
         | Bytecode::VecPopBack(_) => {
-            verifier.stack.pop().unwrap();
+            verifier.pop_checked()?;
             verifier.stack.push(AbstractValue::Other);
         }
 
@@ -384,24 +1275,25 @@ fn execute_inner(
         | Bytecode::Ge
         | Bytecode::VecImmBorrow(_)
         | Bytecode::VecMutBorrow(_) => {
-            verifier.stack.pop().unwrap();
-            verifier.stack.pop().unwrap();
+            verifier.pop_checked()?;
+            verifier.pop_checked()?;
             verifier.stack.push(AbstractValue::Other);
         }
         Bytecode::WriteRef => {
-            verifier.stack.pop().unwrap();
-            verifier.stack.pop().unwrap();
+            verifier.pop_checked()?;
+            let value = verifier.pop_checked()?;
+            write_ref(verifier, value)?;
         }
 
         // These bytecodes produce references, and hence cannot be ID.
         Bytecode::MutBorrowLoc(_)
         | Bytecode::ImmBorrowLoc(_) => verifier.stack.push(AbstractValue::Other),
 
-        | Bytecode::MutBorrowField(_)
+        Bytecode::MutBorrowField(_)
         | Bytecode::MutBorrowFieldGeneric(_)
         | Bytecode::ImmBorrowField(_)
         | Bytecode::ImmBorrowFieldGeneric(_) => {
-            verifier.stack.pop().unwrap();
+            verifier.pop_checked()?;
             verifier.stack.push(AbstractValue::Other);
         }
 
@@ -417,25 +1309,44 @@ fn execute_inner(
                 | Bytecode::MutBorrowGlobalGeneric(_)
                 | Bytecode::Exists(_)
                 | Bytecode::ExistsGeneric(_) => {
-            panic!("Should have been checked by global_storage_access_verifier.");
+            return Err(invariant_violation(
+                "Should have been checked by global_storage_access_verifier.".to_string(),
+            ));
         }
 
         Bytecode::Call(idx) => {
             let function_handle = verifier.binary_view.function_handle_at(*idx);
-            call(verifier, function_handle)?;
+            call(verifier, function_handle, offset)?;
         }
         Bytecode::CallGeneric(idx) => {
             let func_inst = verifier.binary_view.function_instantiation_at(*idx);
             let function_handle = verifier.binary_view.function_handle_at(func_inst.handle);
-            call(verifier, function_handle)?;
+            call(verifier, function_handle, offset)?;
         }
 
         Bytecode::Ret => {
-            verifier.stack_popn(verifier.function_view.return_().len())
+            let num_returns = verifier.function_view.return_().len();
+            if verifier.any_fresh_in_top(num_returns) {
+                verifier.report_leak(offset, IdLeakKind::Return, "returned to the caller");
+            }
+            verifier.stack_popn(num_returns)?;
         }
 
-        Bytecode::BrTrue(_) | Bytecode::BrFalse(_) | Bytecode::Abort => {
-            verifier.stack.pop().unwrap();
+        Bytecode::BrTrue(_) | Bytecode::BrFalse(_) => {
+            verifier.pop_checked()?;
+        }
+
+        // The abort code is a u64, so the Move bytecode verifier has already rejected any fresh
+        // UID reaching here. This only guards against that check being bypassed or miscompiled,
+        // which would otherwise let the UID be dropped without the analysis noticing.
+        Bytecode::Abort => {
+            if verifier.pop_checked()? == AbstractValue::Fresh {
+                let (cur_package, cur_module, cur_function) = verifier.cur_function();
+                return Err(invariant_violation(format!(
+                    "A newly created UID was used as an abort code in \
+                    {cur_package}::{cur_module}::{cur_function}"
+                )));
+            }
         }
 
         // These bytecodes produce constants, and hence cannot be ID.
@@ -454,33 +1365,38 @@ fn execute_inner(
         }
         Bytecode::Unpack(idx) => {
             let struct_def = expect_ok(verifier.binary_view.struct_def_at(*idx))?;
-            unpack(verifier, struct_def);
+            unpack(verifier, struct_def)?;
         }
         Bytecode::UnpackGeneric(idx) => {
             let struct_inst = expect_ok(verifier.binary_view.struct_instantiation_at(*idx))?;
             let struct_def = expect_ok(verifier.binary_view.struct_def_at(struct_inst.def))?;
-            unpack(verifier, struct_def);
+            unpack(verifier, struct_def)?;
         }
 
         Bytecode::VecPack(_, num) => {
-            verifier.stack_popn(*num as usize);
+            if verifier.any_fresh_in_top(*num as usize) {
+                verifier.report_leak(offset, IdLeakKind::Vector, "added to a vector");
+            }
+            verifier.stack_popn(*num as usize)?;
             verifier.stack.push(AbstractValue::Other);
         }
 
         Bytecode::VecPushBack(_) => {
-            verifier.stack.pop().unwrap();
-            verifier.stack.pop().unwrap();
+            if verifier.pop_checked()? == AbstractValue::Fresh {
+                verifier.report_leak(offset, IdLeakKind::Vector, "added to a vector");
+            }
+            verifier.pop_checked()?;
         }
 
         Bytecode::VecUnpack(_, num) => {
-            verifier.stack.pop().unwrap();
+            verifier.pop_checked()?;
             verifier.stack_pushn(*num as usize, AbstractValue::Other);
         }
 
         Bytecode::VecSwap(_) => {
-            verifier.stack.pop().unwrap();
-            verifier.stack.pop().unwrap();
-            verifier.stack.pop().unwrap();
+            verifier.pop_checked()?;
+            verifier.pop_checked()?;
+            verifier.pop_checked()?;
         }
     };
     Ok(())
@@ -494,9 +1410,12 @@ fn expect_ok<T>(res: Result<T, PartialVMError>) -> Result<T, PartialVMError> {
                 "Should have been verified to be safe by the Move bytecode verifier, \
             Got error: {partial_vm_error:?}"
             );
-            debug_assert!(false, "{msg}");
             // This is an internal error, but we cannot accept the module as safe
-            Err(PartialVMError::new(StatusCode::VERIFIER_INVARIANT_VIOLATION).with_message(msg))
+            Err(invariant_violation(msg))
         }
     }
 }
+
+#[cfg(test)]
+#[path = "unit_tests/id_leak_lattice_tests.rs"]
+mod id_leak_lattice_tests;
@@ -8,6 +8,7 @@ pub mod global_storage_access_verifier;
 pub mod id_leak_verifier;
 pub mod one_time_witness_verifier;
 pub mod private_generics;
+pub mod sarif;
 pub mod struct_with_key_verifier;
 
 use move_binary_format::{
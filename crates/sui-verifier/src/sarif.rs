@@ -0,0 +1,183 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The subset of the SARIF 2.1.0 format (Static Analysis Results Interchange Format) that the
+//! verifiers report their findings in, for code scanning tools to ingest. Serialize a `SarifLog`
+//! to JSON to get a log file. Field names follow the specification, in camel case.
+
+use serde::Serialize;
+
+/// Version of the SARIF specification the logs follow
+pub const SARIF_VERSION: &str = "2.1.0";
+/// JSON schema of `SARIF_VERSION`
+pub const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+#[derive(Debug, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub version: String,
+    pub runs: Vec<Run>,
+}
+
+impl SarifLog {
+    /// A log of a single run of a tool
+    pub fn new(run: Run) -> Self {
+        Self {
+            schema: SARIF_SCHEMA.to_string(),
+            version: SARIF_VERSION.to_string(),
+            runs: vec![run],
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Run {
+    pub tool: Tool,
+    pub invocations: Vec<Invocation>,
+    pub results: Vec<SarifResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Tool {
+    pub driver: ToolComponent,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ToolComponent {
+    pub name: String,
+    pub rules: Vec<ReportingDescriptor>,
+}
+
+/// A rule of the tool, which results refer to by id and by index in `ToolComponent::rules`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportingDescriptor {
+    pub id: String,
+    pub short_description: Message,
+    pub default_configuration: ReportingConfiguration,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReportingConfiguration {
+    pub level: Level,
+}
+
+/// Whether the run of the tool completed, and the errors that kept it from completing if not
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Invocation {
+    pub execution_successful: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tool_execution_notifications: Vec<Notification>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Notification {
+    pub level: Level,
+    pub message: Message,
+    pub locations: Vec<Location>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: String,
+    pub rule_index: usize,
+    pub level: Level,
+    pub message: Message,
+    pub locations: Vec<Location>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    None,
+    Note,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Message {
+    pub text: String,
+}
+
+impl Message {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self { text: text.into() }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Location {
+    /// The source line, only known with the source of the module
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub physical_location: Option<PhysicalLocation>,
+    pub logical_locations: Vec<LogicalLocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<LocationProperties>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhysicalLocation {
+    pub artifact_location: ArtifactLocation,
+    pub region: Region,
+}
+
+impl PhysicalLocation {
+    /// Line `line`, starting from 1, of the file at `path`
+    pub fn new(path: &str, line: usize) -> Self {
+        Self {
+            artifact_location: ArtifactLocation {
+                uri: path.to_string(),
+            },
+            region: Region { start_line: line },
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArtifactLocation {
+    pub uri: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Region {
+    pub start_line: usize,
+}
+
+/// A function or module, named `address::module` or `address::module::function`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogicalLocation {
+    pub fully_qualified_name: String,
+    pub kind: String,
+}
+
+impl LogicalLocation {
+    pub fn function(fully_qualified_name: String) -> Self {
+        Self {
+            fully_qualified_name,
+            kind: "function".to_string(),
+        }
+    }
+
+    pub fn module(fully_qualified_name: &str) -> Self {
+        Self {
+            fully_qualified_name: fully_qualified_name.to_string(),
+            kind: "module".to_string(),
+        }
+    }
+}
+
+/// SARIF has no location within bytecode, so the offset of the bytecode is kept in the property
+/// bag of the location
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocationProperties {
+    pub code_offset: u16,
+}
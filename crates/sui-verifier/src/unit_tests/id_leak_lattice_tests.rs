@@ -0,0 +1,101 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The id_leak analysis only reaches a fixpoint if `AbstractValue::join` is a join of a lattice
+//! and `AbstractState::join` reports `Changed` exactly when the state moved up it.
+
+use std::collections::BTreeMap;
+
+use move_binary_format::file_format::LocalIndex;
+use move_bytecode_verifier::{
+    absint::{AbstractDomain, JoinResult},
+    meter::DummyMeter,
+};
+use proptest::{collection, prelude::*};
+
+use super::{AbstractState, AbstractValue};
+
+fn value() -> impl Strategy<Value = AbstractValue> {
+    prop_oneof![Just(AbstractValue::Fresh), Just(AbstractValue::Other)]
+}
+
+/// Two states over the same locals
+fn states() -> impl Strategy<Value = (Vec<AbstractValue>, Vec<AbstractValue>)> {
+    (0..8usize).prop_flat_map(|n| (collection::vec(value(), n), collection::vec(value(), n)))
+}
+
+fn state(values: &[AbstractValue]) -> AbstractState {
+    AbstractState::from_locals(
+        values
+            .iter()
+            .enumerate()
+            .map(|(local, value)| (local as LocalIndex, *value))
+            .collect::<BTreeMap<_, _>>(),
+    )
+}
+
+/// Join `other` into `state`, returning whether a change was reported
+fn join(state: &mut AbstractState, other: &AbstractState) -> bool {
+    match state.join(other, &mut DummyMeter).unwrap() {
+        JoinResult::Changed => true,
+        JoinResult::Unchanged => false,
+    }
+}
+
+proptest! {
+    #[test]
+    fn value_join_is_idempotent(a in value()) {
+        prop_assert_eq!(a.join(&a), a);
+    }
+
+    #[test]
+    fn value_join_is_commutative(a in value(), b in value()) {
+        prop_assert_eq!(a.join(&b), b.join(&a));
+    }
+
+    #[test]
+    fn value_join_is_associative(a in value(), b in value(), c in value()) {
+        prop_assert_eq!(a.join(&b).join(&c), a.join(&b.join(&c)));
+    }
+
+    #[test]
+    fn value_join_is_an_upper_bound(a in value(), b in value()) {
+        let joined = a.join(&b);
+        prop_assert_eq!(a.join(&joined), joined);
+        prop_assert_eq!(b.join(&joined), joined);
+        prop_assert_eq!(a.join(&AbstractValue::Other), AbstractValue::Other);
+    }
+
+    #[test]
+    fn state_join_with_itself_is_unchanged(values in collection::vec(value(), 0..8)) {
+        let mut joined = state(&values);
+        prop_assert!(!join(&mut joined, &state(&values)));
+        prop_assert_eq!(joined, state(&values));
+    }
+
+    #[test]
+    fn state_join_is_pointwise((a, b) in states()) {
+        let mut joined = state(&a);
+        join(&mut joined, &state(&b));
+        let expected: Vec<_> = a.iter().zip(&b).map(|(a, b)| a.join(b)).collect();
+        prop_assert_eq!(joined, state(&expected));
+    }
+
+    #[test]
+    fn state_join_is_commutative((a, b) in states()) {
+        let mut ab = state(&a);
+        join(&mut ab, &state(&b));
+        let mut ba = state(&b);
+        join(&mut ba, &state(&a));
+        prop_assert_eq!(ab, ba);
+    }
+
+    #[test]
+    fn state_join_reports_changes((a, b) in states()) {
+        let mut joined = state(&a);
+        let changed = join(&mut joined, &state(&b));
+        prop_assert_eq!(changed, joined != state(&a));
+        // Joining the same state again must then be a fixpoint
+        prop_assert!(!join(&mut joined, &state(&b)));
+    }
+}
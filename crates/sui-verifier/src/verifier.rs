@@ -5,6 +5,7 @@
 
 use move_binary_format::file_format::CompiledModule;
 use move_bytecode_verifier::meter::DummyMeter;
+use rayon::{ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
 use sui_types::{error::ExecutionError, move_package::FnInfoMap};
 
 use crate::{
@@ -19,8 +20,49 @@ pub fn verify_module(
 ) -> Result<(), ExecutionError> {
     struct_with_key_verifier::verify_module(module)?;
     global_storage_access_verifier::verify_module(module)?;
-    id_leak_verifier::verify_module(module, &mut DummyMeter)?;
+    id_leak_verifier::verify_module(module, &mut DummyMeter, None, None)?;
     private_generics::verify_module(module)?;
     entry_points_verifier::verify_module(module, fn_info_map)?;
     one_time_witness_verifier::verify_module(module, fn_info_map)
 }
+
+/// A dedicated pool of threads to verify modules on, so that verification does not compete for
+/// CPU with the other work of the process, e.g. transaction execution on the global rayon pool or
+/// on an async runtime. Verifying a module is sequential, so the size of the pool bounds how many
+/// modules are verified at once rather than speeding a single module up. Any rayon parallelism
+/// within verification runs on the pool it was installed in, so it would stay on these threads.
+#[derive(Debug)]
+pub struct VerifierThreadPool {
+    pool: ThreadPool,
+}
+
+impl VerifierThreadPool {
+    /// A pool of `num_threads` threads, or one per CPU if `num_threads` is 0
+    pub fn new(num_threads: usize) -> Result<Self, ThreadPoolBuildError> {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .thread_name(|index| format!("sui-verifier-{index}"))
+            .build()?;
+        Ok(Self { pool })
+    }
+
+    pub fn num_threads(&self) -> usize {
+        self.pool.current_num_threads()
+    }
+
+    /// Runs `op` on a thread of the pool, blocking the calling thread until it returns.
+    pub fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> R {
+        self.pool.install(op)
+    }
+
+    /// `verify_module` on a thread of the pool. The calling thread blocks until it is done, waiting
+    /// for a thread to be free first if all of them are busy verifying other modules, so async
+    /// callers should call this from a blocking task.
+    pub fn verify_module(
+        &self,
+        module: &CompiledModule,
+        fn_info_map: &FnInfoMap,
+    ) -> Result<(), ExecutionError> {
+        self.install(|| verify_module(module, fn_info_map))
+    }
+}
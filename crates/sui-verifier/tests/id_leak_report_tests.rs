@@ -0,0 +1,811 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::file_format::{
+    empty_module, Ability, AbilitySet, AddressIdentifierIndex, Bytecode, CodeUnit, CompiledModule,
+    FieldDefinition, FieldHandle, FieldHandleIndex, FunctionDefinition, FunctionDefinitionIndex,
+    FunctionHandle, FunctionHandleIndex, IdentifierIndex, ModuleHandle, ModuleHandleIndex,
+    Signature, SignatureIndex, SignatureToken, StructDefInstantiation, StructDefInstantiationIndex,
+    StructDefinition, StructDefinitionIndex, StructFieldInformation, StructHandle,
+    StructHandleIndex, StructTypeParameter, TypeSignature, Visibility,
+};
+use move_bytecode_source_map::source_map::SourceMap;
+use move_bytecode_verifier::meter::DummyMeter;
+use move_command_line_common::files::FileHash;
+use move_core_types::{account_address::AccountAddress, identifier::Identifier};
+use move_ir_types::location::Loc;
+use std::{collections::BTreeMap, time::Instant};
+use sui_types::{error::VMMVerifierErrorSubStatusCode, SUI_FRAMEWORK_ADDRESS};
+use sui_verifier::id_leak_verifier::{
+    trace_function, verify_module, verify_module_with_mode, verify_module_with_stats,
+    verify_modules_report, AbstractValue, BytecodeCategory, IdLeakError, IdLeakKind, ModuleSource,
+    VerifierMode,
+};
+
+fn module_at(address: AccountAddress, name: &str) -> CompiledModule {
+    let mut module = empty_module();
+    module.address_identifiers[0] = address;
+    module.identifiers[0] = Identifier::new(name).unwrap();
+    module
+}
+
+/// A module at `address` with a handle to `sui::object`, as a module using the framework has
+fn module_using_framework(address: AccountAddress, name: &str) -> CompiledModule {
+    let mut module = module_at(address, name);
+    module.address_identifiers.push(SUI_FRAMEWORK_ADDRESS);
+    module.identifiers.push(Identifier::new("object").unwrap());
+    module.module_handles.push(ModuleHandle {
+        address: AddressIdentifierIndex(1),
+        name: IdentifierIndex(1),
+    });
+    module
+}
+
+/// A module with a single function `f()` whose body is `code`
+fn module_with_code(code: Vec<Bytecode>) -> CompiledModule {
+    let mut module = module_at(AccountAddress::from_hex_literal("0x42").unwrap(), "m");
+    module.identifiers.push(Identifier::new("f").unwrap());
+    module.function_handles.push(FunctionHandle {
+        module: ModuleHandleIndex(0),
+        name: IdentifierIndex(1),
+        parameters: SignatureIndex(0),
+        return_: SignatureIndex(0),
+        type_parameters: vec![],
+    });
+    module.function_defs.push(FunctionDefinition {
+        function: FunctionHandleIndex(0),
+        visibility: Visibility::Private,
+        is_entry: false,
+        acquires_global_resources: vec![],
+        code: Some(CodeUnit {
+            locals: SignatureIndex(0),
+            code,
+        }),
+    });
+    module
+}
+
+/// Instantiation `Obj<u64>` of `struct Obj<phantom T> has key { id: UID }` in the module built by
+/// `module_with_generic_objects`
+const OBJ_U64: StructDefInstantiationIndex = StructDefInstantiationIndex(0);
+/// Instantiation `Wrapper<Obj<u64>>` of `struct Wrapper<T: key + store> has key { id, inner: T }`
+const WRAPPER_OBJ_U64: StructDefInstantiationIndex = StructDefInstantiationIndex(1);
+/// Handle of `sui::object::new` in the module built by `module_with_generic_objects`
+const OBJECT_NEW: FunctionHandleIndex = FunctionHandleIndex(0);
+/// The `id` field of `struct Holder has store { id: UID }`, which is not an object, in the module
+/// built by `module_with_generic_objects`
+const HOLDER_ID: FieldHandleIndex = FieldHandleIndex(0);
+
+/// A module declaring a struct with a phantom type parameter and a struct with a real one, both
+/// objects, a struct holding a UID which is not an object, and a single function `f(parameters)`
+/// whose body is `code`
+fn module_with_generic_objects(
+    parameters: Vec<SignatureToken>,
+    code: Vec<Bytecode>,
+) -> CompiledModule {
+    let mut module = module_using_framework(AccountAddress::from_hex_literal("0x42").unwrap(), "m");
+    let ident = |module: &mut CompiledModule, name: &str| {
+        module.identifiers.push(Identifier::new(name).unwrap());
+        IdentifierIndex(module.identifiers.len() as u16 - 1)
+    };
+    let key = AbilitySet::EMPTY | Ability::Key;
+
+    let uid_name = ident(&mut module, "UID");
+    let obj_name = ident(&mut module, "Obj");
+    let wrapper_name = ident(&mut module, "Wrapper");
+    let holder_name = ident(&mut module, "Holder");
+    let uid = SignatureToken::Struct(StructHandleIndex(0));
+    let obj = StructHandleIndex(1);
+    module.struct_handles = vec![
+        StructHandle {
+            module: ModuleHandleIndex(1),
+            name: uid_name,
+            abilities: AbilitySet::EMPTY | Ability::Store,
+            type_parameters: vec![],
+        },
+        StructHandle {
+            module: ModuleHandleIndex(0),
+            name: obj_name,
+            abilities: key,
+            type_parameters: vec![StructTypeParameter {
+                constraints: AbilitySet::EMPTY,
+                is_phantom: true,
+            }],
+        },
+        StructHandle {
+            module: ModuleHandleIndex(0),
+            name: wrapper_name,
+            abilities: key,
+            type_parameters: vec![StructTypeParameter {
+                constraints: key | Ability::Store,
+                is_phantom: false,
+            }],
+        },
+        StructHandle {
+            module: ModuleHandleIndex(0),
+            name: holder_name,
+            abilities: AbilitySet::EMPTY | Ability::Store,
+            type_parameters: vec![],
+        },
+    ];
+    let id = FieldDefinition {
+        name: ident(&mut module, "id"),
+        signature: TypeSignature(uid.clone()),
+    };
+    let inner = FieldDefinition {
+        name: ident(&mut module, "inner"),
+        signature: TypeSignature(SignatureToken::TypeParameter(0)),
+    };
+    module.struct_defs = vec![
+        StructDefinition {
+            struct_handle: obj,
+            field_information: StructFieldInformation::Declared(vec![id.clone()]),
+        },
+        StructDefinition {
+            struct_handle: StructHandleIndex(2),
+            field_information: StructFieldInformation::Declared(vec![id.clone(), inner]),
+        },
+        StructDefinition {
+            struct_handle: StructHandleIndex(3),
+            field_information: StructFieldInformation::Declared(vec![id]),
+        },
+    ];
+    module.field_handles = vec![FieldHandle {
+        owner: StructDefinitionIndex(2),
+        field: 0,
+    }];
+
+    // 0 is the empty signature of `empty_module`
+    module.signatures.extend([
+        Signature(vec![SignatureToken::U64]),
+        Signature(vec![SignatureToken::StructInstantiation(
+            obj,
+            vec![SignatureToken::U64],
+        )]),
+        Signature(vec![uid]),
+        Signature(parameters),
+    ]);
+    module.struct_def_instantiations = vec![
+        StructDefInstantiation {
+            def: StructDefinitionIndex(0),
+            type_parameters: SignatureIndex(1),
+        },
+        StructDefInstantiation {
+            def: StructDefinitionIndex(1),
+            type_parameters: SignatureIndex(2),
+        },
+    ];
+
+    let new_name = ident(&mut module, "new");
+    let f_name = ident(&mut module, "f");
+    module.function_handles = vec![
+        FunctionHandle {
+            module: ModuleHandleIndex(1),
+            name: new_name,
+            parameters: SignatureIndex(0),
+            return_: SignatureIndex(3),
+            type_parameters: vec![],
+        },
+        FunctionHandle {
+            module: ModuleHandleIndex(0),
+            name: f_name,
+            parameters: SignatureIndex(4),
+            return_: SignatureIndex(0),
+            type_parameters: vec![],
+        },
+    ];
+    module.function_defs.push(FunctionDefinition {
+        function: FunctionHandleIndex(1),
+        visibility: Visibility::Private,
+        is_entry: false,
+        acquires_global_resources: vec![],
+        code: Some(CodeUnit {
+            locals: SignatureIndex(0),
+            code,
+        }),
+    });
+    module
+}
+
+fn warning_kinds(module: &CompiledModule) -> Vec<IdLeakKind> {
+    verify_module_with_mode(module, &mut DummyMeter, VerifierMode::Warn)
+        .unwrap()
+        .into_iter()
+        .map(|warning| warning.kind)
+        .collect()
+}
+
+fn reported_modules(modules: &[CompiledModule], skip_framework: bool) -> Vec<String> {
+    verify_modules_report(modules, skip_framework)
+        .modules
+        .into_iter()
+        .map(|report| report.module)
+        .collect()
+}
+
+#[test]
+fn report_skips_only_framework_modules() {
+    let user_address = AccountAddress::from_hex_literal("0x42").unwrap();
+    let modules = vec![
+        module_at(SUI_FRAMEWORK_ADDRESS, "object"),
+        module_using_framework(SUI_FRAMEWORK_ADDRESS, "transfer"),
+        module_using_framework(user_address, "m"),
+        module_at(user_address, "n"),
+    ];
+
+    let all = reported_modules(&modules, false);
+    assert_eq!(all.len(), 4);
+
+    let user = reported_modules(&modules, true);
+    assert_eq!(
+        user,
+        vec![
+            modules[2].self_id().to_string(),
+            modules[3].self_id().to_string()
+        ]
+    );
+    assert!(verify_modules_report(&modules, true).is_clean());
+}
+
+#[test]
+fn warn_mode_reports_unreachable_code() {
+    // The second `Ret` starts a block no path reaches
+    let module = module_with_code(vec![Bytecode::Ret, Bytecode::Ret]);
+
+    let warnings = verify_module_with_mode(&module, &mut DummyMeter, VerifierMode::Warn).unwrap();
+    let kinds: Vec<_> = warnings.iter().map(|warning| warning.kind).collect();
+    assert_eq!(kinds, vec![IdLeakKind::UnreachableCode]);
+    let message = &IdLeakError::of(&warnings[0].error).unwrap().message;
+    assert!(message.contains("offset 1"), "{message}");
+
+    let warnings =
+        verify_module_with_mode(&module, &mut DummyMeter, VerifierMode::Enforce).unwrap();
+    assert!(warnings.is_empty());
+
+    let report = verify_modules_report(&[module], false);
+    assert_eq!(report.counts[&IdLeakKind::UnreachableCode], 1);
+    assert!(report.is_clean());
+
+    let module = module_with_code(vec![Bytecode::Ret]);
+    let warnings = verify_module_with_mode(&module, &mut DummyMeter, VerifierMode::Warn).unwrap();
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn generic_objects_need_a_fresh_uid() {
+    // A phantom type argument does not change how `Obj<u64>` is packed
+    let module = module_with_generic_objects(
+        vec![],
+        vec![
+            Bytecode::Call(OBJECT_NEW),
+            Bytecode::PackGeneric(OBJ_U64),
+            Bytecode::Pop,
+            Bytecode::Ret,
+        ],
+    );
+    assert!(warning_kinds(&module).is_empty());
+
+    let uid = SignatureToken::Struct(StructHandleIndex(0));
+    let module = module_with_generic_objects(
+        vec![uid],
+        vec![
+            Bytecode::MoveLoc(0),
+            Bytecode::PackGeneric(OBJ_U64),
+            Bytecode::Pop,
+            Bytecode::Ret,
+        ],
+    );
+    assert_eq!(warning_kinds(&module), vec![IdLeakKind::Struct]);
+
+    // The wrapped object carries its own UID, only the wrapper's must be fresh
+    let module = module_with_generic_objects(
+        vec![],
+        vec![
+            Bytecode::Call(OBJECT_NEW),
+            Bytecode::Call(OBJECT_NEW),
+            Bytecode::PackGeneric(OBJ_U64),
+            Bytecode::PackGeneric(WRAPPER_OBJ_U64),
+            Bytecode::Pop,
+            Bytecode::Ret,
+        ],
+    );
+    assert!(warning_kinds(&module).is_empty());
+}
+
+#[test]
+fn unpacked_generic_objects_cannot_be_repacked() {
+    let wrapper = SignatureToken::StructInstantiation(
+        StructHandleIndex(2),
+        vec![SignatureToken::StructInstantiation(
+            StructHandleIndex(1),
+            vec![SignatureToken::U64],
+        )],
+    );
+
+    // Unpacking leaves the wrapper's UID and the inner object on the stack, neither is fresh
+    let module = module_with_generic_objects(
+        vec![wrapper.clone()],
+        vec![
+            Bytecode::MoveLoc(0),
+            Bytecode::UnpackGeneric(WRAPPER_OBJ_U64),
+            Bytecode::Pop,
+            Bytecode::Pop,
+            Bytecode::Ret,
+        ],
+    );
+    assert!(warning_kinds(&module).is_empty());
+
+    let module = module_with_generic_objects(
+        vec![wrapper],
+        vec![
+            Bytecode::MoveLoc(0),
+            Bytecode::UnpackGeneric(WRAPPER_OBJ_U64),
+            Bytecode::PackGeneric(WRAPPER_OBJ_U64),
+            Bytecode::Pop,
+            Bytecode::Ret,
+        ],
+    );
+    assert_eq!(warning_kinds(&module), vec![IdLeakKind::Struct]);
+}
+
+#[test]
+fn enforce_mode_errors_carry_the_kind_of_leak() {
+    let uid = SignatureToken::Struct(StructHandleIndex(0));
+    let module = module_with_generic_objects(
+        vec![uid],
+        vec![
+            Bytecode::MoveLoc(0),
+            Bytecode::PackGeneric(OBJ_U64),
+            Bytecode::Pop,
+            Bytecode::Ret,
+        ],
+    );
+    let err = verify_module(&module, &mut DummyMeter, None, None).unwrap_err();
+    let error = IdLeakError::of(&err).unwrap();
+    assert_eq!(error.kind(), Some(IdLeakKind::Struct));
+    assert_eq!(
+        error.sub_status,
+        Some(VMMVerifierErrorSubStatusCode::INVALID_OBJECT_CREATION as u64)
+    );
+    assert!(
+        error.message.contains("Invalid object creation"),
+        "{}",
+        error.message
+    );
+    // Execution errors print their source with `Debug`, which must stay the quoted message that
+    // transactional test baselines expect
+    assert!(
+        format!("{err:?}").contains(&format!("source: Some({:?})", error.message)),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn timeouts_carry_the_timeout_sub_status() {
+    let module = module_with_generic_objects(vec![], vec![Bytecode::Ret]);
+    verify_module(&module, &mut DummyMeter, None, None).unwrap();
+    let err = verify_module(&module, &mut DummyMeter, Some(Instant::now()), None).unwrap_err();
+    let error = IdLeakError::of(&err).unwrap();
+    assert_eq!(
+        error.sub_status,
+        Some(VMMVerifierErrorSubStatusCode::VERIFICATION_TIMEOUT as u64)
+    );
+    assert_eq!(error.kind(), None);
+    assert!(error.message.contains("timed out"), "{}", error.message);
+}
+
+/// Message of the failure of `verify_module`, given `source`, on a module packing an object with
+/// a UID which is not fresh
+fn leak_message(source: Option<&ModuleSource>) -> String {
+    let uid = SignatureToken::Struct(StructHandleIndex(0));
+    let module = module_with_generic_objects(
+        vec![uid],
+        vec![
+            Bytecode::MoveLoc(0),
+            Bytecode::PackGeneric(OBJ_U64),
+            Bytecode::Pop,
+            Bytecode::Ret,
+        ],
+    );
+    let err = verify_module(&module, &mut DummyMeter, None, source).unwrap_err();
+    IdLeakError::of(&err).unwrap().message.clone()
+}
+
+#[test]
+fn failures_point_at_the_source_when_it_is_known() {
+    let contents = "module 0x42::m {\n    fun f(id: UID) {\n        Obj { id };\n    }\n}\n";
+    let file_hash = FileHash::new(contents);
+    let loc = |text: &str| {
+        let start = contents.find(text).unwrap() as u32;
+        Loc::new(file_hash, start, start + text.len() as u32)
+    };
+    let f = FunctionDefinitionIndex(0);
+    let mut source_map = SourceMap::new(loc("module"), None);
+    source_map
+        .add_top_level_function_mapping(f, loc("fun f"), false)
+        .unwrap();
+    source_map.add_code_mapping(f, 0, loc("id: UID")).unwrap();
+    source_map.add_code_mapping(f, 1, loc("Obj")).unwrap();
+    let files = BTreeMap::from([(
+        file_hash,
+        ("sources/m.move".to_string(), contents.to_string()),
+    )]);
+
+    // Without the source, failures have no location
+    let message = leak_message(None);
+    assert!(!message.contains("m.move"), "{message}");
+
+    // The PackGeneric at offset 1 is on line 3
+    let message = leak_message(Some(&ModuleSource {
+        source_map: &source_map,
+        files: &files,
+    }));
+    assert!(message.ends_with(" at sources/m.move:3"), "{message}");
+
+    // Locations in files which are not given are left out
+    let message = leak_message(Some(&ModuleSource {
+        source_map: &source_map,
+        files: &BTreeMap::new(),
+    }));
+    assert!(!message.contains("m.move"), "{message}");
+
+    // As are the locations of functions missing from the source map
+    let message = leak_message(Some(&ModuleSource {
+        source_map: &SourceMap::new(loc("module"), None),
+        files: &files,
+    }));
+    assert!(!message.contains("m.move"), "{message}");
+}
+
+#[test]
+fn warn_mode_reports_uids_leaking_without_rejecting_them() {
+    // 3 is the signature `(UID)` of `module_with_generic_objects`
+    let uid = SignatureIndex(3);
+    let mut module =
+        module_with_generic_objects(vec![], vec![Bytecode::Call(OBJECT_NEW), Bytecode::Ret]);
+    module.function_handles[1].return_ = uid;
+    assert!(verify_module(&module, &mut DummyMeter, None, None).is_ok());
+    assert_eq!(warning_kinds(&module), vec![IdLeakKind::Return]);
+
+    let module = module_with_generic_objects(
+        vec![],
+        vec![
+            Bytecode::Call(OBJECT_NEW),
+            Bytecode::VecPack(uid, 1),
+            Bytecode::Pop,
+            Bytecode::Ret,
+        ],
+    );
+    assert!(verify_module(&module, &mut DummyMeter, None, None).is_ok());
+    let warnings = verify_module_with_mode(&module, &mut DummyMeter, VerifierMode::Warn).unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].kind, IdLeakKind::Vector);
+    assert_eq!(warnings[0].offset, Some(1));
+    assert_eq!(
+        IdLeakError::of(&warnings[0].error).unwrap().kind(),
+        Some(IdLeakKind::Vector)
+    );
+
+    // A handle to `sui::event::emit` taking a UID, which only the Move bytecode verifier rejects
+    let mut module = module_with_generic_objects(
+        vec![],
+        vec![
+            Bytecode::Call(OBJECT_NEW),
+            Bytecode::Call(FunctionHandleIndex(2)),
+            Bytecode::Ret,
+        ],
+    );
+    module.identifiers.push(Identifier::new("event").unwrap());
+    module.identifiers.push(Identifier::new("emit").unwrap());
+    let num_identifiers = module.identifiers.len() as u16;
+    module.module_handles.push(ModuleHandle {
+        address: AddressIdentifierIndex(1),
+        name: IdentifierIndex(num_identifiers - 2),
+    });
+    module.function_handles.push(FunctionHandle {
+        module: ModuleHandleIndex(2),
+        name: IdentifierIndex(num_identifiers - 1),
+        parameters: uid,
+        return_: SignatureIndex(0),
+        type_parameters: vec![],
+    });
+    assert!(verify_module(&module, &mut DummyMeter, None, None).is_ok());
+    assert_eq!(warning_kinds(&module), vec![IdLeakKind::Event]);
+}
+
+#[test]
+fn fresh_uids_cannot_be_written_into_fields() {
+    let holder =
+        SignatureToken::MutableReference(Box::new(SignatureToken::Struct(StructHandleIndex(3))));
+    let module = module_with_generic_objects(
+        vec![holder],
+        vec![
+            Bytecode::Call(OBJECT_NEW),
+            Bytecode::MoveLoc(0),
+            Bytecode::MutBorrowField(HOLDER_ID),
+            Bytecode::WriteRef,
+            Bytecode::Ret,
+        ],
+    );
+    let warnings = verify_module_with_mode(&module, &mut DummyMeter, VerifierMode::Warn).unwrap();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].kind, IdLeakKind::Reference);
+    let message = &IdLeakError::of(&warnings[0].error).unwrap().message;
+    assert!(message.contains("through a mutable reference"), "{message}");
+}
+
+/// The message of the invariant violation `verify_module` fails on for `module`
+fn invariant_violation(module: &CompiledModule) -> String {
+    let err = verify_module(module, &mut DummyMeter, None, None).unwrap_err();
+    let error = IdLeakError::of(&err).unwrap();
+    assert_eq!(error.kind(), None);
+    assert!(
+        error.message.contains("UNKNOWN_INVARIANT_VIOLATION_ERROR"),
+        "{}",
+        error.message
+    );
+    error.message.clone()
+}
+
+#[test]
+fn popping_an_empty_stack_is_an_invariant_violation() {
+    // Only the Move bytecode verifier rejects the unbalanced stack
+    let module = module_with_generic_objects(vec![], vec![Bytecode::Pop, Bytecode::Ret]);
+    let message = invariant_violation(&module);
+    assert!(message.contains("Invalid stack transitions"), "{message}");
+}
+
+#[test]
+fn moving_a_local_without_a_value_is_an_invariant_violation() {
+    // `f` has no parameters, so local 0 never held a value, which only the Move bytecode verifier
+    // rejects
+    let module = module_with_generic_objects(
+        vec![],
+        vec![Bytecode::MoveLoc(0), Bytecode::Pop, Bytecode::Ret],
+    );
+    let message = invariant_violation(&module);
+    assert!(message.contains("local 0"), "{message}");
+}
+
+#[test]
+fn fresh_uids_used_as_abort_codes_are_an_invariant_violation() {
+    // Abort codes are u64, so only the Move bytecode verifier rejects this
+    let module =
+        module_with_generic_objects(vec![], vec![Bytecode::Call(OBJECT_NEW), Bytecode::Abort]);
+    let message = invariant_violation(&module);
+    assert!(message.contains("abort code"), "{message}");
+
+    // Aborting while a fresh UID is only held by a local is fine
+    let module = module_with_generic_objects(
+        vec![],
+        vec![
+            Bytecode::Call(OBJECT_NEW),
+            Bytecode::StLoc(0),
+            Bytecode::LdU64(0),
+            Bytecode::Abort,
+        ],
+    );
+    verify_module(&module, &mut DummyMeter, None, None).unwrap();
+}
+
+#[test]
+fn global_storage_access_is_an_invariant_violation() {
+    // Rejected by `global_storage_access_verifier`, which runs before this verifier
+    let module = module_with_generic_objects(
+        vec![],
+        vec![
+            Bytecode::Exists(StructDefinitionIndex(0)),
+            Bytecode::Pop,
+            Bytecode::Ret,
+        ],
+    );
+    let message = invariant_violation(&module);
+    assert!(
+        message.contains("global_storage_access_verifier"),
+        "{message}"
+    );
+}
+
+#[test]
+fn stats_count_bytecodes_by_category() {
+    let module = module_with_generic_objects(
+        vec![],
+        vec![
+            Bytecode::Call(OBJECT_NEW),
+            Bytecode::Call(OBJECT_NEW),
+            Bytecode::PackGeneric(OBJ_U64),
+            Bytecode::PackGeneric(WRAPPER_OBJ_U64),
+            Bytecode::UnpackGeneric(WRAPPER_OBJ_U64),
+            Bytecode::Pop,
+            Bytecode::Pop,
+            Bytecode::Ret,
+        ],
+    );
+    let (result, stats) = verify_module_with_stats(&module, &mut DummyMeter);
+    assert!(result.is_ok());
+    let counts: Vec<_> = stats
+        .by_category
+        .iter()
+        .map(|(category, stats)| (*category, stats.count))
+        .collect();
+    assert_eq!(
+        counts,
+        vec![
+            (BytecodeCategory::Call, 2),
+            (BytecodeCategory::Pack, 2),
+            (BytecodeCategory::Unpack, 1),
+            (BytecodeCategory::Other, 3),
+        ]
+    );
+
+    // The stats stop at the bytecode the module is rejected at
+    let module = module_with_generic_objects(
+        vec![],
+        vec![
+            Bytecode::Call(OBJECT_NEW),
+            Bytecode::PackGeneric(OBJ_U64),
+            Bytecode::UnpackGeneric(OBJ_U64),
+            Bytecode::PackGeneric(OBJ_U64),
+            Bytecode::Pop,
+            Bytecode::Ret,
+        ],
+    );
+    let (result, stats) = verify_module_with_stats(&module, &mut DummyMeter);
+    assert!(result.is_err());
+    assert_eq!(stats.by_category[&BytecodeCategory::Pack].count, 2);
+    assert!(!stats.by_category.contains_key(&BytecodeCategory::Other));
+}
+
+#[test]
+fn stats_count_block_visits_per_function() {
+    let function = FunctionDefinitionIndex(0);
+    let module = module_with_generic_objects(
+        vec![],
+        vec![Bytecode::Call(OBJECT_NEW), Bytecode::Pop, Bytecode::Ret],
+    );
+    let (result, stats) = verify_module_with_stats(&module, &mut DummyMeter);
+    assert!(result.is_ok());
+    assert_eq!(stats.block_visits, BTreeMap::from([(function, 1)]));
+
+    // A loop storing another value into a local holding a fresh UID changes the state at the loop
+    // head, so both blocks of the loop are interpreted again, 6 visits of 4 blocks in all
+    let module = module_with_generic_objects(
+        vec![],
+        vec![
+            Bytecode::Call(OBJECT_NEW),
+            Bytecode::StLoc(0),
+            Bytecode::LdTrue,
+            Bytecode::BrFalse(7),
+            Bytecode::LdU64(0),
+            Bytecode::StLoc(0),
+            Bytecode::Branch(2),
+            Bytecode::Ret,
+        ],
+    );
+    let (result, stats) = verify_module_with_stats(&module, &mut DummyMeter);
+    assert!(result.is_ok());
+    assert_eq!(stats.block_visits, BTreeMap::from([(function, 6)]));
+}
+
+#[test]
+fn trace_records_the_stack_after_every_bytecode() {
+    let function = FunctionDefinitionIndex(0);
+    let module = module_with_generic_objects(
+        vec![],
+        vec![
+            Bytecode::Call(OBJECT_NEW),
+            Bytecode::StLoc(0),
+            Bytecode::MoveLoc(0),
+            Bytecode::PackGeneric(OBJ_U64),
+            Bytecode::Pop,
+            Bytecode::Ret,
+        ],
+    );
+    let (trace, result) = trace_function(&module, function, &mut DummyMeter);
+    assert!(result.is_ok());
+    let steps: Vec<_> = trace
+        .iter()
+        .map(|step| (step.offset, step.stack.clone()))
+        .collect();
+    assert_eq!(
+        steps,
+        vec![
+            (0, vec![AbstractValue::Fresh]),
+            (1, vec![]),
+            (2, vec![AbstractValue::Fresh]),
+            (3, vec![AbstractValue::Other]),
+            (4, vec![]),
+            (5, vec![]),
+        ]
+    );
+    assert_eq!(
+        trace[1].state.locals(),
+        &BTreeMap::from([(0, AbstractValue::Fresh)])
+    );
+    assert!(trace[2].state.locals().is_empty());
+
+    // The last step is at the bytecode the function is rejected at
+    let uid = SignatureToken::Struct(StructHandleIndex(0));
+    let module = module_with_generic_objects(
+        vec![uid],
+        vec![
+            Bytecode::MoveLoc(0),
+            Bytecode::PackGeneric(OBJ_U64),
+            Bytecode::Pop,
+            Bytecode::Ret,
+        ],
+    );
+    let (trace, result) = trace_function(&module, function, &mut DummyMeter);
+    assert!(result.is_err());
+    let offsets: Vec<_> = trace.iter().map(|step| step.offset).collect();
+    assert_eq!(offsets, vec![0, 1]);
+    assert_eq!(
+        trace[0].state.locals(),
+        &BTreeMap::new(),
+        "the parameter was moved"
+    );
+}
+
+#[test]
+fn sarif_log_has_the_required_properties_of_the_schema() {
+    let uid = SignatureToken::Struct(StructHandleIndex(0));
+    let leaking = module_with_generic_objects(
+        vec![uid],
+        vec![
+            Bytecode::MoveLoc(0),
+            Bytecode::PackGeneric(OBJ_U64),
+            Bytecode::Pop,
+            Bytecode::Ret,
+        ],
+    );
+    let unreachable = module_with_code(vec![Bytecode::Ret, Bytecode::Ret]);
+    let report = verify_modules_report(&[leaking, unreachable], false);
+    let log = serde_json::to_value(report.to_sarif(&BTreeMap::new())).unwrap();
+
+    // The properties required by https://json.schemastore.org/sarif-2.1.0.json, which no crate
+    // of the workspace can validate against
+    assert_eq!(log["version"], "2.1.0");
+    assert!(log["$schema"].as_str().unwrap().starts_with("https://"));
+    let runs = log["runs"].as_array().unwrap();
+    assert_eq!(runs.len(), 1);
+    let driver = &runs[0]["tool"]["driver"];
+    assert!(driver["name"].is_string());
+    let rules = driver["rules"].as_array().unwrap();
+    assert_eq!(rules.len(), IdLeakKind::ALL.len());
+    for rule in rules {
+        assert!(rule["id"].is_string());
+        assert!(rule["shortDescription"]["text"].is_string());
+    }
+    let invocations = runs[0]["invocations"].as_array().unwrap();
+    assert_eq!(invocations[0]["executionSuccessful"], true);
+
+    let results = runs[0]["results"].as_array().unwrap();
+    let rule_ids: Vec<_> = results
+        .iter()
+        .map(|result| result["ruleId"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        rule_ids,
+        vec![
+            IdLeakKind::Struct.rule_id(),
+            IdLeakKind::UnreachableCode.rule_id()
+        ]
+    );
+    let levels: Vec<_> = results.iter().map(|result| &result["level"]).collect();
+    assert_eq!(levels, vec!["error", "warning"]);
+    for result in results {
+        let rule_index = result["ruleIndex"].as_u64().unwrap() as usize;
+        assert_eq!(rules[rule_index]["id"], result["ruleId"]);
+        assert!(result["message"]["text"].is_string());
+        let location = &result["locations"][0];
+        // Without the source of the modules, findings are only located by function and offset
+        assert!(location.get("physicalLocation").is_none());
+        let logical = &location["logicalLocations"][0];
+        assert_eq!(logical["kind"], "function");
+        assert!(logical["fullyQualifiedName"]
+            .as_str()
+            .unwrap()
+            .ends_with("::m::f"));
+    }
+    assert_eq!(results[1]["locations"][0]["properties"]["codeOffset"], 1);
+}
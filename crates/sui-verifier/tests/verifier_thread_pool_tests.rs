@@ -0,0 +1,55 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::file_format::{
+    empty_module, Bytecode, CodeUnit, FunctionDefinition, FunctionHandle, FunctionHandleIndex,
+    IdentifierIndex, ModuleHandleIndex, SignatureIndex, Visibility,
+};
+use move_core_types::identifier::Identifier;
+use std::collections::BTreeMap;
+use sui_verifier::verifier::{verify_module, VerifierThreadPool};
+
+#[test]
+fn verifies_large_module_on_single_threaded_pool() {
+    let mut module = empty_module();
+    for index in 0..1000 {
+        module
+            .identifiers
+            .push(Identifier::new(format!("f{index}")).unwrap());
+        module.function_handles.push(FunctionHandle {
+            module: ModuleHandleIndex(0),
+            name: IdentifierIndex(module.identifiers.len() as u16 - 1),
+            parameters: SignatureIndex(0),
+            return_: SignatureIndex(0),
+            type_parameters: vec![],
+        });
+        module.function_defs.push(FunctionDefinition {
+            function: FunctionHandleIndex(index),
+            visibility: Visibility::Private,
+            is_entry: false,
+            acquires_global_resources: vec![],
+            code: Some(CodeUnit {
+                locals: SignatureIndex(0),
+                code: vec![Bytecode::LdU64(0), Bytecode::Pop, Bytecode::Ret],
+            }),
+        });
+    }
+
+    let pool = VerifierThreadPool::new(1).unwrap();
+    assert_eq!(pool.num_threads(), 1);
+    pool.verify_module(&module, &BTreeMap::new()).unwrap();
+    pool.install(|| {
+        let thread = std::thread::current();
+        let name = thread.name().unwrap_or_default();
+        assert!(name.starts_with("sui-verifier-"), "ran on {name:?}");
+        verify_module(&module, &BTreeMap::new()).unwrap();
+    });
+    assert!(verify_module(&module, &BTreeMap::new()).is_ok());
+
+    // Modules verified from several threads at once queue up for the single thread of the pool
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| pool.verify_module(&module, &BTreeMap::new()).unwrap());
+        }
+    });
+}
@@ -4,7 +4,7 @@
 use move_core_types::account_address::AccountAddress;
 use move_core_types::ident_str;
 use move_core_types::language_storage::{StructTag, TypeTag};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use sui::client_commands::WalletContext;
 use sui::client_commands::{SuiClientCommandResult, SuiClientCommands};
 use sui_core::test_utils::dummy_transaction_effects;
@@ -283,6 +283,17 @@ pub fn test_shared_object_transactions(
     transactions
 }
 
+/// Build the move package at `path`, returning the bytes of its modules and the ids of the
+/// packages it depends on, as expected by a publish transaction.
+pub fn compile_move_package(path: &Path) -> (Vec<Vec<u8>>, Vec<ObjectID>) {
+    let build_config = BuildConfig::new_for_testing();
+    let compiled_package = sui_framework::build_move_package(path, build_config).unwrap();
+    let all_module_bytes =
+        compiled_package.get_package_bytes(/* with_unpublished_deps */ false);
+    let dependencies = compiled_package.get_dependency_original_package_ids();
+    (all_module_bytes, dependencies)
+}
+
 /// Make a transaction to publish a test move contracts package.
 pub fn create_publish_move_package_transaction(
     gas_object_ref: ObjectRef,
@@ -292,11 +303,7 @@ pub fn create_publish_move_package_transaction(
     gas_budget: u64,
     gas_price: u64,
 ) -> VerifiedTransaction {
-    let build_config = BuildConfig::new_for_testing();
-    let compiled_package = sui_framework::build_move_package(&path, build_config).unwrap();
-    let all_module_bytes =
-        compiled_package.get_package_bytes(/* with_unpublished_deps */ false);
-    let dependencies = compiled_package.get_dependency_original_package_ids();
+    let (all_module_bytes, dependencies) = compile_move_package(&path);
 
     let data = TransactionData::new_module(
         sender,
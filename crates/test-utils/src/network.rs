@@ -10,7 +10,12 @@ use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
 use jsonrpsee::ws_client::WsClient;
 use jsonrpsee::ws_client::WsClientBuilder;
 use prometheus::Registry;
-use rand::{distributions::*, rngs::OsRng, seq::SliceRandom};
+use rand::{
+    distributions::*,
+    rngs::{OsRng, StdRng},
+    seq::SliceRandom,
+    Rng, SeedableRng,
+};
 use tokio::time::timeout;
 use tokio::{task::JoinHandle, time::sleep};
 use tracing::info;
@@ -31,18 +36,38 @@ use sui_protocol_config::{ProtocolVersion, SupportedProtocolVersions};
 use sui_sdk::error::SuiRpcResult;
 use sui_sdk::{SuiClient, SuiClientBuilder};
 use sui_swarm::memory::{Swarm, SwarmBuilder};
-use sui_types::base_types::{AuthorityName, SuiAddress};
+use sui_types::base_types::{AuthorityName, ObjectRef, SuiAddress};
 use sui_types::committee::EpochId;
-use sui_types::crypto::KeypairTraits;
-use sui_types::crypto::SuiKeyPair;
-use sui_types::messages::VerifiedTransaction;
+use sui_types::crypto::{
+    generate_proof_of_possession, KeypairTraits, Signature, Signer, SuiKeyPair, ToFromBytes,
+};
+use sui_types::gas_coin::MIST_PER_SUI;
+use sui_types::governance::MIN_VALIDATOR_JOINING_STAKE_MIST;
+use sui_types::messages::{
+    CallArg, ExecuteTransactionRequestType, ObjectArg, TransactionData, VerifiedTransaction,
+    TEST_ONLY_GAS_UNIT_FOR_GENERIC, TEST_ONLY_GAS_UNIT_FOR_STAKING,
+    TEST_ONLY_GAS_UNIT_FOR_TRANSFER, TEST_ONLY_GAS_UNIT_FOR_VALIDATOR,
+};
 use sui_types::object::Object;
 use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemStateTrait;
 use sui_types::sui_system_state::SuiSystemState;
 use sui_types::sui_system_state::SuiSystemStateTrait;
+use sui_types::sui_system_state::SUI_SYSTEM_MODULE_NAME;
+use sui_types::utils::to_sender_signed_transaction;
+use sui_types::{
+    SUI_SYSTEM_PACKAGE_ID, SUI_SYSTEM_STATE_OBJECT_ID, SUI_SYSTEM_STATE_OBJECT_SHARED_VERSION,
+};
 
 const NUM_VALIDAOTR: usize = 4;
 
+/// The system refuses to let validators leave a committee of this size, see
+/// `min_validator_count` in the system parameters.
+const MIN_COMMITTEE_SIZE: usize = 4;
+
+/// What validators of a committee schedule are given to pay for their own join and leave
+/// transactions.
+const COMMITTEE_SCHEDULE_VALIDATOR_GAS: u64 = 10_000 * MIST_PER_SUI;
+
 pub struct FullNodeHandle {
     pub sui_node: Arc<SuiNode>,
     pub sui_client: SuiClient,
@@ -57,6 +82,7 @@ pub struct TestCluster {
     pub accounts: Vec<SuiAddress>,
     pub wallet: WalletContext,
     pub fullnode_handle: FullNodeHandle,
+    committee_schedule: Vec<usize>,
 }
 
 impl TestCluster {
@@ -136,6 +162,17 @@ impl TestCluster {
         self.swarm.validator(name).unwrap().stop();
     }
 
+    /// Crash a validator without letting it shut down, see `restart_crashed_validator`.
+    #[cfg(msim)]
+    pub fn crash_validator(&self, name: AuthorityName) {
+        self.swarm.validator(name).unwrap().crash();
+    }
+
+    #[cfg(msim)]
+    pub fn restart_crashed_validator(&self, name: AuthorityName) {
+        self.swarm.validator(name).unwrap().restart();
+    }
+
     pub async fn start_validator(&self, name: AuthorityName) {
         let node = self.swarm.validator(name).unwrap();
         if node.is_running() {
@@ -183,6 +220,22 @@ impl TestCluster {
         .expect("Timed out waiting for cluster to target epoch")
     }
 
+    /// Close the current epoch on every running validator and wait for the network to reach the
+    /// next one, to change epoch at a chosen point of a test instead of after the epoch duration.
+    /// As in `wait_for_epoch`, the fullnode tells when the network changed epoch.
+    pub async fn trigger_reconfiguration(&self) {
+        let fullnode = SuiNodeHandle::new(self.fullnode_handle.sui_node.clone());
+        let epoch = fullnode.with(|node| node.current_epoch_for_testing());
+        info!("Closing epoch {epoch}");
+        for handle in self.swarm.validator_node_handles() {
+            handle
+                .with_async(|node| async move { node.close_epoch_for_testing().await.unwrap() })
+                .await;
+        }
+        wait_for_node_transition_to_epoch(&fullnode, epoch + 1).await;
+        info!("Network reached epoch {}", epoch + 1);
+    }
+
     /// Upgrade the network protocol version, by restarting every validator with a new
     /// supported versions.
     /// Note that we don't restart the fullnode here, and it is assumed that the fulnode supports
@@ -235,12 +288,329 @@ impl TestCluster {
             .await
     }
 
+    /// Cut the network links between validators in different `groups` for `duration`, then heal
+    /// them. Validators are given by their index in `get_validator_addresses`, those left out of
+    /// every group keep all of their links.
+    #[cfg(msim)]
+    pub async fn partition(&self, groups: Vec<Vec<usize>>, duration: Duration) {
+        use sui_simulator::{net::NetSim, plugin, task::NodeId};
+
+        let validators = self.get_validator_addresses();
+        let groups: Vec<Vec<NodeId>> = groups
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|i| {
+                        self.swarm
+                            .validator(validators[*i])
+                            .unwrap()
+                            .sim_node_id()
+                            .expect("Only running validators can be partitioned")
+                    })
+                    .collect()
+            })
+            .collect();
+        let mut links = vec![];
+        for (i, group) in groups.iter().enumerate() {
+            for (j, other) in groups.iter().enumerate() {
+                if i != j {
+                    links.extend(
+                        group
+                            .iter()
+                            .flat_map(|src| other.iter().map(|dst| (*src, *dst))),
+                    );
+                }
+            }
+        }
+
+        let net = plugin::simulator::<NetSim>();
+        info!("Partitioning validators into {groups:?} for {duration:?}");
+        for (src, dst) in &links {
+            net.clog_link(*src, *dst);
+        }
+        sleep(duration).await;
+        info!("Healing partition");
+        for (src, dst) in &links {
+            net.unclog_link(*src, *dst);
+        }
+    }
+
     #[cfg(msim)]
     pub fn set_safe_mode_expected(&self, value: bool) {
         for n in self.all_node_handles() {
             n.with(|node| node.set_safe_mode_expected(value));
         }
     }
+
+    /// Apply the committee schedule given to `TestClusterBuilder::with_committee_schedule`, one
+    /// size per epoch boundary from the current epoch on. Validators are asked to join or leave
+    /// the committee during each epoch, so epochs must be long enough for a few transactions, and
+    /// the task panics if the next committee does not have the scheduled size. Joining
+    /// validators are staked with coins of the last account of the wallet.
+    pub fn run_committee_schedule(self: &Arc<Self>) -> JoinHandle<()> {
+        let test_cluster = self.clone();
+        tokio::task::spawn(async move {
+            test_cluster.fund_committee_schedule_validators().await;
+            for size in &test_cluster.committee_schedule {
+                let system_state = test_cluster
+                    .fullnode_handle
+                    .sui_node
+                    .state()
+                    .get_sui_system_state_object_for_testing()
+                    .unwrap();
+                let epoch = system_state.epoch();
+                let active: Vec<SuiAddress> = system_state
+                    .into_sui_system_state_summary()
+                    .active_validators
+                    .into_iter()
+                    .map(|validator| validator.sui_address)
+                    .collect();
+                let validators = test_cluster.validator_accounts();
+                if active.len() > *size {
+                    // The last validators to have joined are the first to leave
+                    for (address, config) in validators
+                        .iter()
+                        .rev()
+                        .filter(|(address, _)| active.contains(address))
+                        .take(active.len() - size)
+                    {
+                        info!("Validator {address} leaving the committee after epoch {epoch}");
+                        test_cluster.leave_committee(*address, config).await;
+                    }
+                } else {
+                    for (address, config) in validators
+                        .iter()
+                        .filter(|(address, _)| !active.contains(address))
+                        .take(size - active.len())
+                    {
+                        info!("Validator {address} joining the committee after epoch {epoch}");
+                        test_cluster.join_committee(*address, config).await;
+                    }
+                }
+                let system_state = test_cluster.wait_for_epoch(Some(epoch + 1)).await;
+                assert_eq!(
+                    system_state
+                        .into_sui_system_state_summary()
+                        .active_validators
+                        .len(),
+                    *size,
+                    "Committee of epoch {} does not have the scheduled size",
+                    epoch + 1
+                );
+            }
+        })
+    }
+
+    /// The account address of each validator of the swarm, which is its address in the system
+    /// state, along with its config, in a stable order.
+    fn validator_accounts(&self) -> Vec<(SuiAddress, NodeConfig)> {
+        self.swarm
+            .config()
+            .validator_configs()
+            .iter()
+            .map(|config| {
+                let address = (&config.account_key_pair.keypair().public()).into();
+                (address, config.clone())
+            })
+            .collect()
+    }
+
+    /// The account paying for the stake of validators joining the committee, and for the gas
+    /// of all validators.
+    fn committee_schedule_funder(&self) -> (SuiAddress, &dyn Signer<Signature>) {
+        let key = self.swarm.config().account_keys.last().unwrap();
+        (key.public().into(), key)
+    }
+
+    async fn fund_committee_schedule_validators(&self) {
+        let (funder, key) = self.committee_schedule_funder();
+        let recipients: Vec<_> = self
+            .validator_accounts()
+            .into_iter()
+            .map(|(address, _)| address)
+            .collect();
+        let amounts = vec![COMMITTEE_SCHEDULE_VALIDATOR_GAS; recipients.len()];
+        let gas_price = self.get_reference_gas_price().await;
+        let gas = self.coins_owned_by(funder).await[0];
+        let data = TransactionData::new_pay_sui(
+            funder,
+            vec![],
+            recipients,
+            amounts,
+            gas,
+            TEST_ONLY_GAS_UNIT_FOR_TRANSFER * gas_price,
+            gas_price,
+        )
+        .unwrap();
+        self.execute_committee_transaction(data, key).await;
+    }
+
+    async fn join_committee(&self, address: SuiAddress, config: &NodeConfig) {
+        let key = config.account_key_pair.keypair();
+        let metadata = self
+            .swarm
+            .config()
+            .genesis
+            .validator_set_for_tooling()
+            .into_iter()
+            .find(|validator| validator.verified_metadata().sui_address == address)
+            .unwrap()
+            .verified_metadata()
+            .clone();
+        let proof_of_possession = generate_proof_of_possession(config.protocol_key_pair(), address);
+        let gas_price = self.get_reference_gas_price().await;
+
+        // Validators that left keep their address, but become candidates again to rejoin
+        let gas = self.coins_owned_by(address).await[0];
+        let data = TransactionData::new_move_call(
+            address,
+            SUI_SYSTEM_PACKAGE_ID,
+            SUI_SYSTEM_MODULE_NAME.to_owned(),
+            "request_add_validator_candidate".parse().unwrap(),
+            vec![],
+            gas,
+            vec![
+                system_state_arg(),
+                CallArg::Pure(bcs::to_bytes(metadata.protocol_pubkey.as_bytes()).unwrap()),
+                CallArg::Pure(bcs::to_bytes(metadata.network_pubkey.as_bytes()).unwrap()),
+                CallArg::Pure(bcs::to_bytes(metadata.worker_pubkey.as_bytes()).unwrap()),
+                CallArg::Pure(bcs::to_bytes(proof_of_possession.as_ref()).unwrap()),
+                CallArg::Pure(bcs::to_bytes(metadata.name.as_bytes()).unwrap()),
+                CallArg::Pure(bcs::to_bytes(metadata.description.as_bytes()).unwrap()),
+                CallArg::Pure(bcs::to_bytes(metadata.image_url.as_bytes()).unwrap()),
+                CallArg::Pure(bcs::to_bytes(metadata.project_url.as_bytes()).unwrap()),
+                CallArg::Pure(bcs::to_bytes(&metadata.net_address).unwrap()),
+                CallArg::Pure(bcs::to_bytes(&metadata.p2p_address).unwrap()),
+                CallArg::Pure(bcs::to_bytes(&metadata.primary_address).unwrap()),
+                CallArg::Pure(bcs::to_bytes(&metadata.worker_address).unwrap()),
+                CallArg::Pure(bcs::to_bytes(&gas_price).unwrap()),
+                CallArg::Pure(bcs::to_bytes(&0u64).unwrap()), // commission_rate
+            ],
+            TEST_ONLY_GAS_UNIT_FOR_VALIDATOR * gas_price,
+            gas_price,
+        )
+        .unwrap();
+        self.execute_committee_transaction(data, key).await;
+
+        // Each join spends a whole coin of the funder on stake, keeping its smallest one for gas
+        let (funder, funder_key) = self.committee_schedule_funder();
+        let mut coins = self.coins_owned_by_with_balance(funder).await;
+        coins.sort_by_key(|(_, balance)| *balance);
+        let (gas, _) = coins[0];
+        let (stake, balance) = *coins
+            .last()
+            .filter(|(stake, _)| *stake != gas)
+            .expect("Ran out of coins to stake joining validators");
+        assert!(
+            balance >= MIN_VALIDATOR_JOINING_STAKE_MIST,
+            "Coins are too small to stake joining validators"
+        );
+        let data = TransactionData::new_move_call(
+            funder,
+            SUI_SYSTEM_PACKAGE_ID,
+            SUI_SYSTEM_MODULE_NAME.to_owned(),
+            "request_add_stake".parse().unwrap(),
+            vec![],
+            gas,
+            vec![
+                system_state_arg(),
+                CallArg::Object(ObjectArg::ImmOrOwnedObject(stake)),
+                CallArg::Pure(bcs::to_bytes(&address).unwrap()),
+            ],
+            TEST_ONLY_GAS_UNIT_FOR_STAKING * gas_price,
+            gas_price,
+        )
+        .unwrap();
+        self.execute_committee_transaction(data, funder_key).await;
+
+        let gas = self.coins_owned_by(address).await[0];
+        let data = TransactionData::new_move_call(
+            address,
+            SUI_SYSTEM_PACKAGE_ID,
+            SUI_SYSTEM_MODULE_NAME.to_owned(),
+            "request_add_validator".parse().unwrap(),
+            vec![],
+            gas,
+            vec![system_state_arg()],
+            TEST_ONLY_GAS_UNIT_FOR_GENERIC * gas_price,
+            gas_price,
+        )
+        .unwrap();
+        self.execute_committee_transaction(data, key).await;
+    }
+
+    async fn leave_committee(&self, address: SuiAddress, config: &NodeConfig) {
+        let gas_price = self.get_reference_gas_price().await;
+        let gas = self.coins_owned_by(address).await[0];
+        let data = TransactionData::new_move_call(
+            address,
+            SUI_SYSTEM_PACKAGE_ID,
+            SUI_SYSTEM_MODULE_NAME.to_owned(),
+            "request_remove_validator".parse().unwrap(),
+            vec![],
+            gas,
+            vec![system_state_arg()],
+            TEST_ONLY_GAS_UNIT_FOR_VALIDATOR * gas_price,
+            gas_price,
+        )
+        .unwrap();
+        self.execute_committee_transaction(data, config.account_key_pair.keypair())
+            .await;
+    }
+
+    async fn coins_owned_by(&self, address: SuiAddress) -> Vec<ObjectRef> {
+        self.coins_owned_by_with_balance(address)
+            .await
+            .into_iter()
+            .map(|(coin, _)| coin)
+            .collect()
+    }
+
+    async fn coins_owned_by_with_balance(&self, address: SuiAddress) -> Vec<(ObjectRef, u64)> {
+        self.sui_client()
+            .coin_read_api()
+            .get_coins(address, None, None, None)
+            .await
+            .expect("Failed to get coins")
+            .data
+            .into_iter()
+            .map(|coin| (coin.object_ref(), coin.balance))
+            .collect()
+    }
+
+    /// Execute a transaction of the committee schedule, waiting for the fullnode to have executed
+    /// it so that the next one sees the objects it changed.
+    async fn execute_committee_transaction(
+        &self,
+        data: TransactionData,
+        signer: &dyn Signer<Signature>,
+    ) {
+        let response = self
+            .fullnode_handle
+            .sui_client
+            .quorum_driver()
+            .execute_transaction_block(
+                to_sender_signed_transaction(data, signer),
+                SuiTransactionBlockResponseOptions::new().with_effects(),
+                Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+            )
+            .await
+            .expect("Committee schedule transaction failed");
+        assert!(
+            response.status_ok().unwrap(),
+            "Committee schedule transaction failed: {:?}",
+            response.effects
+        );
+    }
+}
+
+fn system_state_arg() -> CallArg {
+    CallArg::Object(ObjectArg::SharedObject {
+        id: SUI_SYSTEM_STATE_OBJECT_ID,
+        initial_shared_version: SUI_SYSTEM_STATE_OBJECT_SHARED_VERSION,
+        mutable: true,
+    })
 }
 
 pub struct RandomNodeRestarter {
@@ -250,6 +620,10 @@ pub struct RandomNodeRestarter {
     kill_interval: Uniform<Duration>,
     // How long should we wait before restarting them.
     restart_delay: Uniform<Duration>,
+    // Seeds the choice of nodes and delays, so that a schedule can be replayed.
+    seed: u64,
+    // Whether to crash nodes instead of stopping them, which can only be set in the simulator.
+    crash_only: bool,
 }
 
 impl RandomNodeRestarter {
@@ -258,6 +632,8 @@ impl RandomNodeRestarter {
             test_cluster,
             kill_interval: Uniform::new(Duration::from_secs(10), Duration::from_secs(11)),
             restart_delay: Uniform::new(Duration::from_secs(1), Duration::from_secs(2)),
+            seed: OsRng.gen(),
+            crash_only: false,
         }
     }
 
@@ -271,26 +647,59 @@ impl RandomNodeRestarter {
         self
     }
 
+    /// Replay the kill and restart schedule of a previous run that used the same seed, along with
+    /// the same cluster and intervals. Otherwise the seed is drawn from `OsRng`.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Crash nodes, killing them without letting them shut down as if they lost power, instead of
+    /// stopping them. This exercises crash recovery when they restart.
+    #[cfg(msim)]
+    pub fn with_crash_only(mut self, crash_only: bool) -> Self {
+        self.crash_only = crash_only;
+        self
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
     pub fn run(&self) -> JoinHandle<()> {
         let test_cluster = self.test_cluster.clone();
         let kill_interval = self.kill_interval;
         let restart_delay = self.restart_delay;
         let validators = self.test_cluster.get_validator_addresses();
+        let crash_only = self.crash_only;
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        info!("Running RandomNodeRestarter with seed {}", self.seed);
         tokio::task::spawn(async move {
             loop {
-                let delay = kill_interval.sample(&mut OsRng);
+                let delay = kill_interval.sample(&mut rng);
                 info!("Sleeping {delay:?} before killing a validator");
                 sleep(delay).await;
 
-                let validator = validators.choose(&mut OsRng).unwrap();
-                info!("Killing validator {:?}", validator.concise());
-                test_cluster.stop_validator(*validator);
+                let validator = validators.choose(&mut rng).unwrap();
+                if crash_only {
+                    info!("Crashing validator {:?}", validator.concise());
+                    #[cfg(msim)]
+                    test_cluster.crash_validator(*validator);
+                } else {
+                    info!("Killing validator {:?}", validator.concise());
+                    test_cluster.stop_validator(*validator);
+                }
 
-                let delay = restart_delay.sample(&mut OsRng);
+                let delay = restart_delay.sample(&mut rng);
                 info!("Sleeping {delay:?} before restarting");
                 sleep(delay).await;
                 info!("Starting validator {:?}", validator.concise());
-                test_cluster.start_validator(*validator).await;
+                if crash_only {
+                    #[cfg(msim)]
+                    test_cluster.restart_crashed_validator(*validator);
+                } else {
+                    test_cluster.start_validator(*validator).await;
+                }
             }
         })
     }
@@ -308,6 +717,9 @@ pub struct TestClusterBuilder {
     fullnode_supported_protocol_versions_config: Option<ProtocolVersionsConfig>,
     db_checkpoint_config_validators: DBCheckpointConfig,
     db_checkpoint_config_fullnodes: DBCheckpointConfig,
+    committee_schedule: Vec<usize>,
+    #[cfg(msim)]
+    store_shards: Option<usize>,
 }
 
 impl TestClusterBuilder {
@@ -323,6 +735,9 @@ impl TestClusterBuilder {
             fullnode_supported_protocol_versions_config: None,
             db_checkpoint_config_validators: DBCheckpointConfig::default(),
             db_checkpoint_config_fullnodes: DBCheckpointConfig::default(),
+            committee_schedule: vec![],
+            #[cfg(msim)]
+            store_shards: None,
         }
     }
 
@@ -341,11 +756,44 @@ impl TestClusterBuilder {
         self
     }
 
+    /// Add `count` dummy owned objects to genesis, each owned by a new random address, so that
+    /// tests run against a store of a realistic size rather than a nearly empty one.
+    pub fn with_preseeded_objects(self, count: usize) -> Self {
+        self.with_objects(
+            (0..count)
+                .map(|_| Object::with_owner_for_testing(SuiAddress::random_for_testing_only())),
+        )
+    }
+
     pub fn with_num_validators(mut self, num: usize) -> Self {
         self.num_validators = Some(num);
         self
     }
 
+    /// Change the size of the committee at the epoch boundaries that follow a call to
+    /// `TestCluster::run_committee_schedule`, the committee after the `i`-th of them having
+    /// `schedule[i]` validators. Genesis starts with the largest size in the schedule, raising the
+    /// number of validators if needed, so that every validator that is ever active has a node.
+    /// Validators that leave the committee keep running, and rejoin it when it grows back.
+    pub fn with_committee_schedule(mut self, schedule: Vec<usize>) -> Self {
+        assert!(
+            schedule.iter().all(|size| *size >= MIN_COMMITTEE_SIZE),
+            "Committee sizes must be at least {MIN_COMMITTEE_SIZE}"
+        );
+        self.committee_schedule = schedule;
+        self
+    }
+
+    /// Partition the object lock tables of the nodes' stores across `num_shards` shards instead of
+    /// the simulator's default of 4, to benchmark how contention on the store scales with them.
+    /// See `AuthorityStore::set_num_shards_for_testing`.
+    #[cfg(msim)]
+    pub fn with_store_shards(mut self, num_shards: usize) -> Self {
+        assert!(num_shards > 0, "The store needs at least one shard");
+        self.store_shards = Some(num_shards);
+        self
+    }
+
     pub fn enable_fullnode_events(mut self) -> Self {
         self.enable_fullnode_events = true;
         self
@@ -456,15 +904,21 @@ impl TestClusterBuilder {
             accounts,
             wallet,
             fullnode_handle,
+            committee_schedule: self.committee_schedule,
         })
     }
 
     /// Start a Swarm and set up WalletConfig
     async fn start_swarm(&mut self) -> Result<Swarm, anyhow::Error> {
+        let committee_size = self
+            .committee_schedule
+            .iter()
+            .copied()
+            .chain(Some(self.num_validators.unwrap_or(NUM_VALIDAOTR)))
+            .max()
+            .unwrap();
         let mut builder: SwarmBuilder = Swarm::builder()
-            .committee_size(
-                NonZeroUsize::new(self.num_validators.unwrap_or(NUM_VALIDAOTR)).unwrap(),
-            )
+            .committee_size(NonZeroUsize::new(committee_size).unwrap())
             .with_objects(self.additional_objects.clone())
             .with_protocol_version(self.initial_protocol_version)
             .with_db_checkpoint_config(self.db_checkpoint_config_validators.clone())
@@ -476,6 +930,10 @@ impl TestClusterBuilder {
             builder = builder.initial_accounts_config(genesis_config);
         }
 
+        // Also resets the shards left over by an earlier cluster of the same test
+        #[cfg(msim)]
+        sui_core::authority::AuthorityStore::set_num_shards_for_testing(self.store_shards);
+
         let mut swarm = builder.build();
         swarm.launch().await?;
 
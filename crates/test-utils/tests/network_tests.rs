@@ -9,7 +9,7 @@ use sui_types::{
     base_types::ObjectID, digests::TransactionDigest, object::Object, MOVE_STDLIB_OBJECT_ID,
     SUI_FRAMEWORK_OBJECT_ID, SUI_SYSTEM_ADDRESS, SUI_SYSTEM_PACKAGE_ID,
 };
-use test_utils::network::TestClusterBuilder;
+use test_utils::network::{TestCluster, TestClusterBuilder};
 
 use sui_macros::sim_test;
 
@@ -28,6 +28,22 @@ async fn test_additional_objects() {
     assert!(matches!(resp, SuiObjectResponse { data: Some(_), .. }));
 }
 
+#[sim_test]
+async fn test_preseeded_objects() {
+    let num_genesis_objects =
+        |cluster: &TestCluster| cluster.swarm.config().genesis.objects().len();
+    let default_cluster = TestClusterBuilder::new().build().await.unwrap();
+    let cluster = TestClusterBuilder::new()
+        .with_preseeded_objects(100)
+        .build()
+        .await
+        .unwrap();
+    assert_eq!(
+        num_genesis_objects(&cluster),
+        num_genesis_objects(&default_cluster) + 100
+    );
+}
+
 #[sim_test]
 async fn test_package_override() {
     // `with_objects` can be used to override existing packages.
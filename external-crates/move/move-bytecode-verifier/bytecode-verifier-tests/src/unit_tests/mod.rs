@@ -50,5 +50,6 @@ pub(crate) fn production_config() -> VerifierConfig {
         max_per_fun_meter_units: Some(1000 * 8000),
         max_per_mod_meter_units: Some(1000 * 8000),
         max_constant_vector_len: Some(MAX_CONSTANT_VECTOR_LEN),
+        verifier_threads: None,
     }
 }
@@ -40,6 +40,9 @@ pub struct VerifierConfig {
     pub max_basic_blocks_in_script: Option<usize>,
     pub max_per_fun_meter_units: Option<u128>,
     pub max_per_mod_meter_units: Option<u128>,
+    /// Size of a dedicated thread pool that clients run their own verification passes on, or
+    /// `None` to run them on the calling thread. Not used by the Move verifier itself.
+    pub verifier_threads: Option<usize>,
 }
 
 /// Helper for a "canonical" verification of a module.
@@ -192,6 +195,7 @@ impl Default for VerifierConfig {
             max_per_fun_meter_units: Some(1000 * 8000),
             max_per_mod_meter_units: Some(1000 * 8000),
             max_constant_vector_len: Some(MAX_CONSTANT_VECTOR_LEN),
+            verifier_threads: None,
         }
     }
 }